@@ -1,4 +1,11 @@
-use zed_extension_api::{self as zed, LanguageServerId, Result, Worktree};
+use zed_extension_api::{
+    self as zed, serde_json::Value, settings::LspSettings, LanguageServerId, Result, Worktree,
+};
+
+const JSON_LANGUAGE_SERVER: &str = "json-language-server";
+const CONFIG_SCHEMA: &str = include_str!("../schemas/i18n.schema.json");
+/// The server's own config file; i18n-ally's files also hold settings the schema lacks.
+const CONFIG_FILE_MATCH: &str = "**/.zed/i18n.json";
 
 struct IntlLensExtension {
     cached_binary_path: Option<String>,
@@ -24,6 +31,49 @@ impl zed::Extension for IntlLensExtension {
             env: vec![],
         })
     }
+
+    fn language_server_initialization_options(
+        &mut self,
+        language_server_id: &LanguageServerId,
+        worktree: &Worktree,
+    ) -> Result<Option<Value>> {
+        // The server reads its config from `settings` too, so it has it from the start
+        let settings = LspSettings::for_worktree(language_server_id.as_ref(), worktree)?;
+        Ok(settings.initialization_options.or(settings.settings))
+    }
+
+    fn language_server_workspace_configuration(
+        &mut self,
+        language_server_id: &LanguageServerId,
+        worktree: &Worktree,
+    ) -> Result<Option<Value>> {
+        let settings = LspSettings::for_worktree(language_server_id.as_ref(), worktree)?;
+        Ok(settings
+            .settings
+            .map(|settings| zed::serde_json::json!({ language_server_id.as_ref(): settings })))
+    }
+
+    fn language_server_additional_workspace_configuration(
+        &mut self,
+        _language_server_id: &LanguageServerId,
+        target_language_server_id: &LanguageServerId,
+        _worktree: &Worktree,
+    ) -> Result<Option<Value>> {
+        if target_language_server_id.as_ref() != JSON_LANGUAGE_SERVER {
+            return Ok(None);
+        }
+
+        // Completion and validation of the config file in the JSON language server
+        let schema: Value = zed::serde_json::from_str(CONFIG_SCHEMA).map_err(|e| e.to_string())?;
+        Ok(Some(zed::serde_json::json!({
+            "json": {
+                "schemas": [{
+                    "fileMatch": [CONFIG_FILE_MATCH],
+                    "schema": schema,
+                }]
+            }
+        })))
+    }
 }
 
 impl IntlLensExtension {
@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -8,9 +9,29 @@ use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
 
-use crate::config::I18nConfig;
-use crate::document::DocumentStore;
-use crate::i18n::{KeyFinder, TranslationStore};
+use crate::config::{I18nConfig, CONFIG_FILES, DETECTION_FILES};
+use crate::config_check::{check_config, range_of, JsonSpans, OWN_CONFIG_FILE};
+use crate::diagnostics::{
+    diagnostic_code, split_namespace, FileCandidate, KeyDiagnosticData, ARB_NOT_IN_TEMPLATE,
+    ARB_PLACEHOLDER, INCOMPLETE_TRANSLATION, MACHINE_TRANSLATED, MISSING_TRANSLATION,
+    RAW_TRANSLATION, UNMATCHED_DYNAMIC_KEY,
+};
+use crate::document::{Document, DocumentStore};
+use crate::i18n::{check_arb, ArbIssue, KeyPattern, SourceLanguage, TranslationStore};
+use crate::project::{self, Project};
+use crate::pseudo::{self, PseudoStyle};
+use crate::translator::{self, TranslationProvider};
+
+const MAX_KEY_SUGGESTIONS: usize = 3;
+const MAX_PATTERN_HOVER_KEYS: usize = 10;
+const CREATE_RAW_TRANSLATION_KEY_COMMAND: &str = "intl-lens.createRawTranslationKey";
+const TRANSLATE_MISSING_LOCALES_COMMAND: &str = "intl-lens.translateMissingLocales";
+const TRANSLATE_ALL_MISSING_LOCALES_COMMAND: &str = "intl-lens.translateAllMissingLocales";
+const MARK_TRANSLATION_REVIEWED_COMMAND: &str = "intl-lens.markTranslationReviewed";
+const GENERATE_PSEUDO_LOCALE_COMMAND: &str = "intl-lens.generatePseudoLocale";
+const TRANSLATION_BATCH_SIZE: usize = 50;
+/// Section of the editor settings the server reads its config from.
+const SETTINGS_SECTION: &str = "intl-lens";
 
 fn truncate_string(s: &str, max_chars: usize) -> String {
     if s.chars().count() <= max_chars {
@@ -21,13 +42,21 @@ fn truncate_string(s: &str, max_chars: usize) -> String {
     format!("{}...", truncated)
 }
 
+struct TranslationJob {
+    key: String,
+    source_value: String,
+    file_path: PathBuf,
+}
+
 pub struct I18nBackend {
     client: Client,
-    config: Arc<RwLock<I18nConfig>>,
     documents: Arc<RwLock<DocumentStore>>,
-    translation_store: Arc<RwLock<Option<TranslationStore>>>,
-    key_finder: Arc<RwLock<KeyFinder>>,
-    workspace_root: Arc<RwLock<Option<PathBuf>>>,
+    workspace_folders: Arc<RwLock<Vec<PathBuf>>>,
+    /// Projects of every workspace folder; a document belongs to the nearest one above it.
+    projects: Arc<RwLock<Vec<Arc<Project>>>>,
+    /// Config from the editor's settings, which overrides the project config files.
+    settings: Arc<RwLock<Value>>,
+    configuration_pull_supported: Arc<RwLock<bool>>,
     inlay_hint_dynamic_registration_supported: Arc<RwLock<bool>>,
     inlay_hint_refresh_supported: Arc<RwLock<bool>>,
     watched_files_dynamic_registration_supported: Arc<RwLock<bool>>,
@@ -38,11 +67,11 @@ impl I18nBackend {
     pub fn new(client: Client) -> Self {
         Self {
             client,
-            config: Arc::new(RwLock::new(I18nConfig::default())),
             documents: Arc::new(RwLock::new(DocumentStore::new())),
-            translation_store: Arc::new(RwLock::new(None)),
-            key_finder: Arc::new(RwLock::new(KeyFinder::default())),
-            workspace_root: Arc::new(RwLock::new(None)),
+            workspace_folders: Arc::new(RwLock::new(Vec::new())),
+            projects: Arc::new(RwLock::new(Vec::new())),
+            settings: Arc::new(RwLock::new(Value::Null)),
+            configuration_pull_supported: Arc::new(RwLock::new(false)),
             inlay_hint_dynamic_registration_supported: Arc::new(RwLock::new(false)),
             inlay_hint_refresh_supported: Arc::new(RwLock::new(false)),
             watched_files_dynamic_registration_supported: Arc::new(RwLock::new(false)),
@@ -50,20 +79,35 @@ impl I18nBackend {
         }
     }
 
-    async fn initialize_workspace(&self, root: PathBuf) {
-        tracing::info!("Initializing workspace at {:?}", root);
+    /// Load the projects of a workspace folder: the folder itself and the projects nested
+    /// below it, such as the apps and packages of a monorepo.
+    async fn add_workspace_folder(&self, folder: PathBuf) {
+        tracing::info!("Initializing workspace folder {:?}", folder);
+
+        for root in project::discover_roots(&folder) {
+            self.add_project(root).await;
+        }
 
-        let config = I18nConfig::load_from_workspace(&root);
-        tracing::info!("Config loaded, locale_paths: {:?}", config.locale_paths);
+        self.workspace_folders.write().await.push(folder);
+    }
 
-        let key_finder = KeyFinder::new(&config.function_patterns);
-        *self.key_finder.write().await = key_finder;
+    /// Load the project at `root`, unless it is loaded already. Returns whether it was added.
+    async fn add_project(&self, root: PathBuf) -> bool {
+        if self.projects.read().await.iter().any(|p| p.root == root) {
+            return false;
+        }
 
-        let store = TranslationStore::new(root.clone());
-        store.scan_and_load(&config.locale_paths);
+        let settings = self.settings.read().await.clone();
+        let (project, skipped) = Project::load(root, &settings);
+        tracing::info!(
+            "Config loaded for {:?}, locale_paths: {:?}",
+            project.root,
+            project.config.locale_paths
+        );
+        self.log_skipped_files(&project.root, &skipped).await;
 
-        let locales = store.get_locales();
-        let keys = store.get_all_keys();
+        let locales = project.store.get_locales();
+        let keys = project.store.get_all_keys();
 
         tracing::info!("Found {} locales: {:?}", locales.len(), locales);
         tracing::info!("Found {} translation keys", keys.len());
@@ -75,14 +119,45 @@ impl I18nBackend {
                     "i18n-lsp initialized: {} locales, {} keys in {:?}",
                     locales.len(),
                     keys.len(),
-                    root
+                    project.root
                 ),
             )
             .await;
 
-        *self.translation_store.write().await = Some(store);
-        *self.config.write().await = config;
-        *self.workspace_root.write().await = Some(root);
+        let root = project.root.clone();
+        self.projects.write().await.push(Arc::new(project));
+        self.publish_config_diagnostics(&root).await;
+        true
+    }
+
+    /// Drop the projects of a workspace folder, except those of other folders nested in it.
+    async fn remove_workspace_folder(&self, folder: &Path) {
+        let mut folders = self.workspace_folders.write().await;
+        folders.retain(|f| f != folder);
+        self.projects
+            .write()
+            .await
+            .retain(|project| folders.iter().any(|f| project.root.starts_with(f)));
+    }
+
+    /// The project of a document. Documents outside every workspace folder have none, as
+    /// checking them against another folder's translations would only mislead.
+    async fn project_for(&self, uri: &Url) -> Option<Arc<Project>> {
+        let projects = self.projects.read().await;
+        let path = uri.to_file_path().ok()?;
+        project::nearest(&projects, &path).cloned()
+    }
+
+    /// The projects a command applies to: the project of the document given as its
+    /// argument, or every project.
+    async fn command_projects(&self, uri: Option<&Value>) -> Vec<Arc<Project>> {
+        let uri = uri
+            .and_then(|v| v.as_str())
+            .and_then(|v| Url::parse(v).ok());
+        match uri {
+            Some(uri) => self.project_for(&uri).await.into_iter().collect(),
+            None => self.projects.read().await.clone(),
+        }
     }
 
     async fn register_inlay_hint_capability(&self) {
@@ -198,15 +273,27 @@ impl I18nBackend {
             return;
         }
 
-        let locale_paths = { self.config.read().await.locale_paths.clone() };
-        let workspace_root = { self.workspace_root.read().await.clone() };
         let relative_pattern_support = *self.watched_files_relative_pattern_supported.read().await;
 
-        let watchers = Self::build_file_watchers(
-            &locale_paths,
-            workspace_root.as_deref(),
-            relative_pattern_support,
-        );
+        let mut watchers: Vec<FileSystemWatcher> = self
+            .projects
+            .read()
+            .await
+            .iter()
+            .flat_map(|project| {
+                Self::build_file_watchers(
+                    &project.config.locale_paths,
+                    Some(&project.root),
+                    relative_pattern_support,
+                )
+            })
+            .collect();
+        for folder in self.workspace_folders.read().await.iter() {
+            watchers.extend(Self::project_root_watchers(
+                folder,
+                relative_pattern_support,
+            ));
+        }
         if watchers.is_empty() {
             tracing::debug!("Skipping watched files registration (no locale paths)");
             return;
@@ -236,95 +323,186 @@ impl I18nBackend {
         }
     }
 
+    /// Replace the file watchers, after the projects or their locale paths changed.
+    async fn reregister_watched_files_capability(&self) {
+        if *self
+            .watched_files_dynamic_registration_supported
+            .read()
+            .await
+        {
+            let unregistration = Unregistration {
+                id: "intl-lens-watched-files".to_string(),
+                method: "workspace/didChangeWatchedFiles".to_string(),
+            };
+            if let Err(err) = self
+                .client
+                .unregister_capability(vec![unregistration])
+                .await
+            {
+                tracing::debug!("Watched files unregistration failed: {:?}", err);
+            }
+        }
+        self.register_watched_files_capability().await;
+    }
+
+    /// Index the messages a document defines itself (`<i18n>` blocks, `addMessages`).
+    async fn index_document(&self, uri: &Url, content: &str) {
+        let Ok(path) = uri.to_file_path() else {
+            return;
+        };
+        let source = {
+            let docs = self.documents.read().await;
+            Self::source_language(uri, docs.get(uri.as_str()))
+        };
+        if let Some(project) = self.project_for(uri).await {
+            project.store.index_document(&path, content, &source);
+        }
+    }
+
     async fn diagnose_document(&self, uri: &Url, content: &str) {
-        let diagnostics = self.compute_diagnostics(content).await;
+        let diagnostics = self.compute_diagnostics(uri, content).await;
 
         self.client
             .publish_diagnostics(uri.clone(), diagnostics, None)
             .await;
     }
 
-    async fn compute_diagnostics(&self, content: &str) -> Vec<Diagnostic> {
-        let key_finder = self.key_finder.read().await;
-        let found_keys = key_finder.find_keys(content);
+    async fn compute_diagnostics(&self, uri: &Url, content: &str) -> Vec<Diagnostic> {
+        let Some(project) = self.project_for(uri).await else {
+            return vec![];
+        };
 
-        let translation_store = self.translation_store.read().await;
+        if let Ok(path) = uri.to_file_path() {
+            if Self::is_config_file(&project.root, &path) {
+                return check_config(content, path.ends_with(OWN_CONFIG_FILE));
+            }
+            if path.extension().and_then(|e| e.to_str()) == Some("arb") {
+                return self.compute_arb_diagnostics(&project, &path, content).await;
+            }
+        }
 
-        let Some(store) = translation_store.as_ref() else {
-            return vec![];
+        let source = {
+            let docs = self.documents.read().await;
+            Self::source_language(uri, docs.get(uri.as_str()))
         };
+        let found_keys = project.key_finder.find_keys_in(content, &source);
 
+        let store = &project.store;
         let mut diagnostics = Vec::new();
-        let source_locale = self.config.read().await.source_locale.clone();
+        let config = &project.config;
+        let review_log = project.review_log.read().await;
+        let source_locale = &config.source_locale;
 
         for found_key in found_keys {
-            if !store.key_exists(&found_key.key) {
+            let range = Range {
+                start: Position {
+                    line: found_key.line as u32,
+                    character: found_key.start_char as u32,
+                },
+                end: Position {
+                    line: found_key.line as u32,
+                    character: found_key.end_char as u32,
+                },
+            };
+
+            if let Some(pattern) = &found_key.pattern {
+                diagnostics.extend(Self::dynamic_key_diagnostic(
+                    store,
+                    &found_key.key,
+                    pattern,
+                    range,
+                ));
+                continue;
+            }
+
+            // Messages the component defines itself take precedence over the global ones
+            let local = source
+                .path
+                .as_deref()
+                .map(|path| store.component_translations(path, &found_key.key))
+                .unwrap_or_default();
+
+            if !store.key_exists(&found_key.key) && local.is_empty() {
+                let suggestions = store.find_similar_keys(&found_key.key, MAX_KEY_SUGGESTIONS);
+                let mut message = format!("Translation key '{}' not found", found_key.key);
+                if let Some(suggestion) = suggestions.first() {
+                    message.push_str(&format!(". Did you mean '{}'?", suggestion));
+                }
+
+                let mut data =
+                    Self::key_diagnostic_data(store, config, &found_key.key, store.get_locales());
+                data.suggestions = suggestions;
+                data.key_prefix = found_key.key_prefix.clone();
+
                 diagnostics.push(Diagnostic {
-                    range: Range {
-                        start: Position {
-                            line: found_key.line as u32,
-                            character: found_key.start_char as u32,
-                        },
-                        end: Position {
-                            line: found_key.line as u32,
-                            character: found_key.end_char as u32,
-                        },
-                    },
+                    range,
                     severity: Some(DiagnosticSeverity::WARNING),
-                    code: Some(NumberOrString::String("missing-translation".to_string())),
+                    code: Some(NumberOrString::String(MISSING_TRANSLATION.to_string())),
                     source: Some("i18n".to_string()),
-                    message: format!("Translation key '{}' not found", found_key.key),
+                    message,
+                    data: data.to_value(),
                     ..Default::default()
                 });
             } else {
                 // Check if the source locale value is a raw placeholder (_key_)
-                if let Some(value) = store.get_translation(&found_key.key, &source_locale) {
+                if let Some(value) = store.get_translation(&found_key.key, source_locale) {
                     if value.starts_with('_') && value.ends_with('_') && value.len() > 2 {
+                        let data = Self::key_diagnostic_data(store, config, &found_key.key, vec![]);
                         diagnostics.push(Diagnostic {
-                            range: Range {
-                                start: Position {
-                                    line: found_key.line as u32,
-                                    character: found_key.start_char as u32,
-                                },
-                                end: Position {
-                                    line: found_key.line as u32,
-                                    character: found_key.end_char as u32,
-                                },
-                            },
+                            range,
                             severity: Some(DiagnosticSeverity::WARNING),
-                            code: Some(NumberOrString::String("raw-translation".to_string())),
+                            code: Some(NumberOrString::String(RAW_TRANSLATION.to_string())),
                             source: Some("i18n".to_string()),
                             message: format!(
                                 "Translation '{}' has a raw placeholder value — use Go to Definition to edit",
                                 found_key.key
                             ),
+                            data: data.to_value(),
                             ..Default::default()
                         });
                         continue;
                     }
                 }
 
-                let missing_locales = store.get_missing_locales(&found_key.key);
-                if !missing_locales.is_empty() {
+                let pending_review = review_log.pending_locales(&found_key.key);
+                if !pending_review.is_empty() {
+                    let data = Self::key_diagnostic_data(store, config, &found_key.key, vec![]);
                     diagnostics.push(Diagnostic {
-                        range: Range {
-                            start: Position {
-                                line: found_key.line as u32,
-                                character: found_key.start_char as u32,
-                            },
-                            end: Position {
-                                line: found_key.line as u32,
-                                character: found_key.end_char as u32,
-                            },
-                        },
+                        range,
                         severity: Some(DiagnosticSeverity::HINT),
-                        code: Some(NumberOrString::String("incomplete-translation".to_string())),
+                        code: Some(NumberOrString::String(MACHINE_TRANSLATED.to_string())),
                         source: Some("i18n".to_string()),
                         message: format!(
-                            "Translation '{}' missing in: {}",
+                            "Translation '{}' was machine translated in: {} — needs review",
                             found_key.key,
-                            missing_locales.join(", ")
+                            pending_review.join(", ")
                         ),
+                        data: data.to_value(),
+                        ..Default::default()
+                    });
+                }
+
+                let missing_locales: Vec<String> = store
+                    .get_missing_locales(&found_key.key)
+                    .into_iter()
+                    .filter(|locale| !local.contains_key(locale))
+                    .collect();
+                if !missing_locales.is_empty() {
+                    let message = format!(
+                        "Translation '{}' missing in: {}",
+                        found_key.key,
+                        missing_locales.join(", ")
+                    );
+                    let data =
+                        Self::key_diagnostic_data(store, config, &found_key.key, missing_locales);
+
+                    diagnostics.push(Diagnostic {
+                        range,
+                        severity: Some(DiagnosticSeverity::HINT),
+                        code: Some(NumberOrString::String(INCOMPLETE_TRANSLATION.to_string())),
+                        source: Some("i18n".to_string()),
+                        message,
+                        data: data.to_value(),
                         ..Default::default()
                     });
                 }
@@ -334,28 +512,311 @@ impl I18nBackend {
         diagnostics
     }
 
-    async fn get_hover_content(&self, key: &str) -> Option<String> {
-        let translation_store = self.translation_store.read().await;
-        let config = self.config.read().await;
-        let store = translation_store.as_ref()?;
+    /// Diagnose a dynamic key: a warning when no key matches it, or a hint listing the
+    /// matching keys that some locales lack. The hint carries no key data, since there is
+    /// no single key to create.
+    fn dynamic_key_diagnostic(
+        store: &TranslationStore,
+        template: &str,
+        pattern: &KeyPattern,
+        range: Range,
+    ) -> Option<Diagnostic> {
+        let matching = store.keys_matching(pattern);
+        if matching.is_empty() {
+            return Some(Diagnostic {
+                range,
+                severity: Some(DiagnosticSeverity::WARNING),
+                code: Some(NumberOrString::String(UNMATCHED_DYNAMIC_KEY.to_string())),
+                source: Some("i18n".to_string()),
+                message: format!(
+                    "No translation key matches '{}' (prefix '{}')",
+                    template,
+                    pattern.static_prefix()
+                ),
+                ..Default::default()
+            });
+        }
+
+        let incomplete: Vec<String> = matching
+            .iter()
+            .filter_map(|key| {
+                let mut missing = store.get_missing_locales(key);
+                if missing.is_empty() {
+                    return None;
+                }
+                missing.sort();
+                Some(format!("{} ({})", key, missing.join(", ")))
+            })
+            .collect();
+        if incomplete.is_empty() {
+            return None;
+        }
+
+        let mut message = format!(
+            "Keys matching '{}' missing in some locales: {}",
+            template,
+            incomplete
+                .iter()
+                .take(MAX_PATTERN_HOVER_KEYS)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        if incomplete.len() > MAX_PATTERN_HOVER_KEYS {
+            message.push_str(&format!(
+                " and {} more",
+                incomplete.len() - MAX_PATTERN_HOVER_KEYS
+            ));
+        }
+        Some(Diagnostic {
+            range,
+            severity: Some(DiagnosticSeverity::HINT),
+            code: Some(NumberOrString::String(INCOMPLETE_TRANSLATION.to_string())),
+            source: Some("i18n".to_string()),
+            message,
+            ..Default::default()
+        })
+    }
+
+    /// Check an ARB document against the template ARB of its directory, the source
+    /// locale's file: keys the template lacks and placeholders it does not declare.
+    async fn compute_arb_diagnostics(
+        &self,
+        project: &Project,
+        path: &Path,
+        content: &str,
+    ) -> Vec<Diagnostic> {
+        let template = project
+            .store
+            .get_locale_file_paths(&project.config.source_locale)
+            .into_iter()
+            .find(|file| {
+                file.extension().and_then(|e| e.to_str()) == Some("arb")
+                    && file.parent() == path.parent()
+            });
+        let Some(template) = template else {
+            return vec![];
+        };
+
+        let template_content = if template == path {
+            None
+        } else {
+            let open = match Url::from_file_path(&template) {
+                Ok(uri) => {
+                    let docs = self.documents.read().await;
+                    docs.get(uri.as_str()).map(|doc| doc.content.to_string())
+                }
+                Err(_) => None,
+            };
+            match open.or_else(|| std::fs::read_to_string(&template).ok()) {
+                Some(content) => Some(content),
+                None => return vec![],
+            }
+        };
+        let template_name = template
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let issues = check_arb(content, template_content.as_deref());
+        // Issues only come from valid JSON, whose keys have spans
+        let spans = JsonSpans::parse(content);
+        issues
+            .into_iter()
+            .map(|(key, line, issue)| {
+                let range = Self::json_key_range(content, &spans, &key, line);
+
+                let (severity, code, message) = match issue {
+                    ArbIssue::NotInTemplate => (
+                        DiagnosticSeverity::WARNING,
+                        ARB_NOT_IN_TEMPLATE,
+                        format!(
+                            "'{}' is not in the template {}, so no getter is generated for it",
+                            key, template_name
+                        ),
+                    ),
+                    ArbIssue::UndeclaredPlaceholder(name) => (
+                        DiagnosticSeverity::WARNING,
+                        ARB_PLACEHOLDER,
+                        format!(
+                            "Placeholder '{}' of '{}' is not declared in {}",
+                            name, key, template_name
+                        ),
+                    ),
+                    ArbIssue::UnusedPlaceholder(name) => (
+                        DiagnosticSeverity::INFORMATION,
+                        ARB_PLACEHOLDER,
+                        format!(
+                            "Placeholder '{}' is declared for '{}' but not used in the message",
+                            name, key
+                        ),
+                    ),
+                };
+
+                Diagnostic {
+                    range,
+                    severity: Some(severity),
+                    code: Some(NumberOrString::String(code.to_string())),
+                    source: Some("i18n".to_string()),
+                    message,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    fn key_diagnostic_data(
+        store: &TranslationStore,
+        config: &I18nConfig,
+        key: &str,
+        mut missing_locales: Vec<String>,
+    ) -> KeyDiagnosticData {
+        missing_locales.sort();
+
+        let namespace = if config.namespace_enabled {
+            split_namespace(key).0.map(|ns| ns.to_string())
+        } else {
+            None
+        };
+
+        let file_candidates = missing_locales
+            .iter()
+            .flat_map(|locale| {
+                Self::target_files_for_key(store, locale, key, namespace.as_deref())
+                    .into_iter()
+                    .map(|path| FileCandidate {
+                        locale: locale.clone(),
+                        path,
+                    })
+            })
+            .collect();
+
+        KeyDiagnosticData {
+            key: key.to_string(),
+            namespace,
+            missing_locales,
+            file_candidates,
+            suggestions: Vec::new(),
+            key_prefix: None,
+        }
+    }
+
+    /// Writable files of `locale` that a new `key` should go into. More than one result
+    /// means the choice is ambiguous and is left to the user.
+    fn target_files_for_key(
+        store: &TranslationStore,
+        locale: &str,
+        key: &str,
+        namespace: Option<&str>,
+    ) -> Vec<PathBuf> {
+        let writable: Vec<PathBuf> = store
+            .get_locale_file_paths(locale)
+            .into_iter()
+            .filter(|path| Self::is_writable_translation_file(path))
+            .collect();
+        let key = match namespace {
+            Some(_) => split_namespace(key).1,
+            None => key,
+        };
+        store.resolve_target_files(locale, key, namespace, &writable)
+    }
+
+    /// Range of the top-level `key` of a JSON document, or the start of `line` when the
+    /// key has no span.
+    fn json_key_range(content: &str, spans: &JsonSpans, key: &str, line: usize) -> Range {
+        match spans.keys.get(&format!("/{}", key)) {
+            Some(&(start, end)) => range_of(content, start, end),
+            None => {
+                let start = Position::new(line as u32, 0);
+                Range::new(start, start)
+            }
+        }
+    }
+
+    /// Whether `path` is one of the config files of the project at `root`, which get
+    /// checked as config; detection files such as `pubspec.yaml` are not config.
+    fn is_config_file(root: &Path, path: &Path) -> bool {
+        CONFIG_FILES.iter().any(|name| root.join(name) == path)
+    }
+
+    /// The project that loaded translations from `file`, if any did.
+    fn translation_file_owner<'a>(
+        projects: &'a [Arc<Project>],
+        file: &Path,
+    ) -> Option<&'a Arc<Project>> {
+        projects.iter().find(|project| {
+            project.store.get_locales().iter().any(|locale| {
+                project
+                    .store
+                    .get_locale_file_paths(locale)
+                    .iter()
+                    .any(|p| p == file)
+            })
+        })
+    }
+
+    fn is_writable_translation_file(path: &Path) -> bool {
+        path.extension().and_then(|e| e.to_str()) == Some("json")
+    }
+
+    fn source_language(uri: &Url, document: Option<&Document>) -> SourceLanguage {
+        SourceLanguage::new(
+            document.map(|doc| doc.language_id.clone()),
+            uri.to_file_path().ok(),
+        )
+    }
+
+    /// Hover for a key, preferring messages the component at `component` defines itself.
+    fn get_hover_content(project: &Project, key: &str, component: Option<&Path>) -> Option<String> {
+        let store = &project.store;
 
-        let translations = store.get_all_translations(key);
+        let translations = store.get_all_translations_in(key, component);
         if translations.is_empty() {
             return None;
         }
 
+        let source_locale = &project.config.source_locale;
         let mut content = format!("### 🌍 `{}`\n\n", key);
 
-        let source_locale = &config.source_locale;
+        // Translator notes usually live in the source locale's file only
+        let mut noted: Vec<_> = translations.iter().collect();
+        noted.sort_by_key(|(locale, _)| (*locale != source_locale, *locale));
+        let context = noted.iter().find_map(|(_, entry)| entry.context.as_ref());
+        let description = noted
+            .iter()
+            .find_map(|(_, entry)| entry.description.as_ref());
+        if let Some(context) = context {
+            content.push_str(&format!("Context: `{}`\n\n", context));
+        }
+        if let Some(description) = description {
+            content.push_str(&format!("_{}_\n\n", description));
+        }
+
+        if let Some(metadata) = store.arb_metadata(key) {
+            for placeholder in &metadata.placeholders {
+                content.push_str(&format!("- `{}`", placeholder.parameter()));
+                if let Some(example) = &placeholder.example {
+                    content.push_str(&format!(" — e.g. `{}`", example));
+                }
+                content.push('\n');
+            }
+            if !metadata.placeholders.is_empty() {
+                content.push('\n');
+            }
+        }
+
         let format_line = |locale: &str| -> Option<String> {
-            let entry = translations.get(locale)?;
+            let from = store.resolve_locale(locale, &translations)?;
+            let entry = &translations[from];
             let mut line = format!("**{}**: {}", locale, entry.value);
+            if from != locale {
+                line.push_str(&format!(" _(from {})_", from));
+            }
 
-            if let Some(location) = store.get_translation_location(key, locale) {
-                if let Ok(uri) = Url::from_file_path(&location.file_path) {
-                    let link = format!("{}#L{}", uri, location.line + 1);
-                    line.push_str(&format!(" ([↗]({} \"Go to Definition\"))", link));
-                }
+            let location = TranslationStore::location_of(key, entry);
+            if let Ok(uri) = Url::from_file_path(&location.file_path) {
+                let link = format!("{}#L{}", uri, location.line + 1);
+                line.push_str(&format!(" ([↗]({} \"Go to Definition\"))", link));
             }
 
             line.push_str("\n\n");
@@ -368,12 +829,15 @@ impl I18nBackend {
 
         content.push_str("---\n\n");
 
+        // Locales without the key show the message they fall back to
         let mut other_locales: Vec<String> = translations
             .keys()
-            .filter(|locale| *locale != source_locale)
             .cloned()
+            .chain(store.get_locales())
+            .filter(|locale| locale != source_locale)
             .collect();
         other_locales.sort();
+        other_locales.dedup();
 
         for locale in other_locales {
             if let Some(line) = format_line(&locale) {
@@ -384,25 +848,82 @@ impl I18nBackend {
         Some(content)
     }
 
-    async fn get_completions(&self, prefix: &str) -> Vec<CompletionItem> {
-        let translation_store = self.translation_store.read().await;
-        let config = self.config.read().await;
+    /// Hover for a dynamic key: how many keys it can resolve to, with their source
+    /// locale values.
+    fn get_pattern_hover_content(project: &Project, pattern: &KeyPattern) -> Option<String> {
+        let store = &project.store;
 
-        let Some(store) = translation_store.as_ref() else {
-            return vec![];
-        };
+        let keys = store.keys_matching(pattern);
+        let mut content = format!("### 🌍 `{}`\n\n", pattern);
 
-        let all_keys = store.get_all_keys();
-        let source_locale = &config.source_locale;
+        if keys.is_empty() {
+            content.push_str(&format!(
+                "No translation key matches this dynamic key (prefix `{}`)\n",
+                pattern.static_prefix()
+            ));
+            return Some(content);
+        }
+
+        let noun = if keys.len() == 1 { "key" } else { "keys" };
+        content.push_str(&format!("{} matching {}\n\n---\n\n", keys.len(), noun));
+
+        for key in keys.iter().take(MAX_PATTERN_HOVER_KEYS) {
+            match store.get_translation(key, &project.config.source_locale) {
+                Some(value) => content.push_str(&format!("`{}`: {}\n\n", key, value)),
+                None => content.push_str(&format!("`{}`\n\n", key)),
+            }
+        }
+
+        if keys.len() > MAX_PATTERN_HOVER_KEYS {
+            content.push_str(&format!(
+                "…and {} more\n",
+                keys.len() - MAX_PATTERN_HOVER_KEYS
+            ));
+        }
+
+        Some(content)
+    }
+
+    /// Keys starting with `prefix`, including the component's own messages. Under a
+    /// scoped translation function (`key_prefix`) only keys inside the scope are
+    /// offered, relative to it.
+    fn get_completions(
+        project: &Project,
+        prefix: &str,
+        key_prefix: Option<&str>,
+        component: Option<&Path>,
+    ) -> Vec<CompletionItem> {
+        let store = &project.store;
+
+        let local_keys = component
+            .map(|component| store.component_keys(component))
+            .unwrap_or_default();
+        let global_keys = store
+            .get_all_keys()
+            .into_iter()
+            .filter(|key| !local_keys.contains(key));
+        let all_keys: Vec<String> = local_keys.iter().cloned().chain(global_keys).collect();
+        let source_locale = &project.config.source_locale;
+
+        let scope = key_prefix.map(|key_prefix| format!("{}.", key_prefix));
 
         all_keys
             .into_iter()
-            .filter(|key| key.starts_with(prefix) || prefix.is_empty())
+            .filter_map(|key| {
+                let relative = match &scope {
+                    Some(scope) => key.strip_prefix(scope.as_str())?.to_string(),
+                    None => key.clone(),
+                };
+                relative.starts_with(prefix).then_some((key, relative))
+            })
             .take(100)
-            .map(|key| {
-                let translation = store.get_translation(&key, source_locale);
+            .map(|(key, relative)| {
+                let translation = store
+                    .get_all_translations_in(&key, component)
+                    .remove(source_locale)
+                    .map(|entry| entry.value);
                 CompletionItem {
-                    label: key.clone(),
+                    label: relative.clone(),
                     kind: Some(CompletionItemKind::TEXT),
                     detail: translation.clone(),
                     documentation: translation.map(|t| {
@@ -411,7 +932,7 @@ impl I18nBackend {
                             value: format!("**{}**: {}", source_locale, t),
                         })
                     }),
-                    insert_text: Some(key.clone()),
+                    insert_text: Some(relative),
                     ..Default::default()
                 }
             })
@@ -441,9 +962,32 @@ impl I18nBackend {
             }
         }
 
+        // Config changes reload the project
+        patterns.extend(Self::config_file_names().map(str::to_string));
+
         patterns.sort();
         patterns.dedup();
 
+        Self::watchers_for(patterns, workspace_root, relative_pattern_support)
+    }
+
+    /// Watchers for the files that make a directory below a workspace folder a project,
+    /// so packages created later are picked up.
+    fn project_root_watchers(
+        folder: &Path,
+        relative_pattern_support: bool,
+    ) -> Vec<FileSystemWatcher> {
+        let patterns = project::root_file_names()
+            .map(|name| format!("**/{}", name))
+            .collect();
+        Self::watchers_for(patterns, Some(folder), relative_pattern_support)
+    }
+
+    fn watchers_for(
+        patterns: Vec<String>,
+        workspace_root: Option<&Path>,
+        relative_pattern_support: bool,
+    ) -> Vec<FileSystemWatcher> {
         let base_uri = if relative_pattern_support {
             workspace_root.and_then(|root| Url::from_directory_path(root).ok())
         } else {
@@ -472,6 +1016,10 @@ impl I18nBackend {
             .collect()
     }
 
+    fn config_file_names() -> impl Iterator<Item = &'static str> {
+        CONFIG_FILES.into_iter().chain(DETECTION_FILES)
+    }
+
     fn to_absolute_pattern(root: &Path, pattern: &str) -> String {
         let mut root_str = root.to_string_lossy().replace('\\', "/");
         root_str = root_str.trim_end_matches('/').to_string();
@@ -487,8 +1035,11 @@ impl I18nBackend {
         }
     }
 
-    fn translation_extensions() -> [&'static str; 5] {
-        [".json", ".yaml", ".yml", ".php", ".arb"]
+    fn translation_extensions() -> [&'static str; 12] {
+        [
+            ".json", ".yaml", ".yml", ".php", ".arb", ".po", ".js", ".mjs", ".cjs", ".ts", ".mts",
+            ".cts",
+        ]
     }
 
     fn has_translation_extension(path: &Path) -> bool {
@@ -534,47 +1085,238 @@ impl I18nBackend {
         false
     }
 
-    async fn is_translation_uri(&self, uri: &Url) -> bool {
+    /// Roots of the projects whose translations include the file at `uri`.
+    async fn translation_project_roots(&self, uri: &Url) -> Vec<PathBuf> {
         let Some(path) = uri.to_file_path().ok() else {
-            return false;
+            return Vec::new();
         };
 
-        let workspace_root = { self.workspace_root.read().await.clone() };
-        let locale_paths = { self.config.read().await.locale_paths.clone() };
+        self.projects
+            .read()
+            .await
+            .iter()
+            .filter(|project| {
+                Self::is_translation_file_in_paths(
+                    &path,
+                    &project.root,
+                    &project.config.locale_paths,
+                )
+            })
+            .map(|project| project.root.clone())
+            .collect()
+    }
 
-        let Some(root) = workspace_root.as_ref() else {
-            return false;
+    /// Roots of the projects whose config is read from the file at `uri`.
+    async fn config_project_roots(&self, uri: &Url) -> Vec<PathBuf> {
+        let Some(path) = uri.to_file_path().ok() else {
+            return Vec::new();
         };
 
-        Self::is_translation_file_in_paths(&path, root, &locale_paths)
+        self.projects
+            .read()
+            .await
+            .iter()
+            .filter(|project| Self::config_file_names().any(|name| project.root.join(name) == path))
+            .map(|project| project.root.clone())
+            .collect()
     }
 
-    async fn reload_translations(&self) {
-        let workspace_root = { self.workspace_root.read().await.clone() };
-        let locale_paths = { self.config.read().await.locale_paths.clone() };
+    /// Check the config files of the project at `root`, open or not, since editors rarely
+    /// attach the server to JSON files; clears the diagnostics of files that are gone.
+    async fn publish_config_diagnostics(&self, root: &Path) {
+        for name in CONFIG_FILES {
+            let path = root.join(name);
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+            let open = {
+                let docs = self.documents.read().await;
+                docs.get(uri.as_str()).map(|doc| doc.content.to_string())
+            };
+            let diagnostics = match open.or_else(|| std::fs::read_to_string(&path).ok()) {
+                Some(content) => check_config(&content, name == OWN_CONFIG_FILE),
+                None => Vec::new(),
+            };
+            self.client
+                .publish_diagnostics(uri, diagnostics, None)
+                .await;
+        }
+    }
 
-        let Some(root) = workspace_root.as_ref() else {
+    /// Tell the user about translation files whose locale could not be read from their
+    /// path or content, since their messages are silently missing otherwise.
+    async fn log_skipped_files(&self, root: &Path, skipped: &[PathBuf]) {
+        const LISTED: usize = 10;
+
+        if skipped.is_empty() {
             return;
+        }
+
+        let mut message = format!(
+            "Skipped {} translation file(s) with no locale in their path:",
+            skipped.len()
+        );
+        for path in skipped.iter().take(LISTED) {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            message.push_str(&format!("\n  {}", relative.display()));
+        }
+        if skipped.len() > LISTED {
+            message.push_str(&format!("\n  and {} more", skipped.len() - LISTED));
+        }
+        tracing::warn!("{}", message);
+        self.client.log_message(MessageType::WARNING, message).await;
+    }
+
+    /// Reload the translations of the project at `root` with its current config.
+    async fn reload_project(&self, root: &Path) {
+        let config = {
+            let projects = self.projects.read().await;
+            let Some(project) = projects.iter().find(|project| project.root == root) else {
+                return;
+            };
+            project.config.clone()
         };
 
-        let store = TranslationStore::new(root.clone());
-        store.scan_and_load(&locale_paths);
+        let (project, skipped) = Project::with_config(root.to_path_buf(), config);
+        self.replace_project(project, &skipped, "Reloaded translations")
+            .await;
+    }
+
+    /// Read the config of the project at `root` again and reload the project with it: its
+    /// key patterns and its translations.
+    async fn reload_project_config(&self, root: &Path) {
+        if !self.projects.read().await.iter().any(|p| p.root == root) {
+            return;
+        }
 
-        let locales = store.get_locales();
-        let keys = store.get_all_keys();
+        let settings = self.settings.read().await.clone();
+        let (project, skipped) = Project::load(root.to_path_buf(), &settings);
+        self.replace_project(project, &skipped, "Reloaded config")
+            .await;
+    }
+
+    /// The server's section of settings pushed by the client, or the settings themselves
+    /// when they have no such section, as initialization options usually do not.
+    fn settings_section(settings: Value) -> Value {
+        match settings {
+            Value::Object(mut object) if object.contains_key(SETTINGS_SECTION) => {
+                object.remove(SETTINGS_SECTION).unwrap_or_default()
+            }
+            settings => settings,
+        }
+    }
+
+    /// Ask the client for the server's settings, when it supports `workspace/configuration`.
+    async fn pull_settings(&self) -> Option<Value> {
+        if !*self.configuration_pull_supported.read().await {
+            return None;
+        }
+
+        let item = ConfigurationItem {
+            scope_uri: None,
+            section: Some(SETTINGS_SECTION.to_string()),
+        };
+        match self.client.configuration(vec![item]).await {
+            Ok(values) => values.into_iter().next().filter(|value| !value.is_null()),
+            Err(err) => {
+                tracing::warn!("workspace/configuration failed: {:?}", err);
+                None
+            }
+        }
+    }
+
+    /// Use new editor settings, reloading every project when they changed.
+    async fn apply_settings(&self, settings: Value) {
+        {
+            let mut current = self.settings.write().await;
+            if *current == settings {
+                return;
+            }
+            *current = settings.clone();
+        }
+
+        self.report_invalid_settings(&settings).await;
+        tracing::info!("Editor settings changed, reloading...");
+        let roots: Vec<PathBuf> = self
+            .projects
+            .read()
+            .await
+            .iter()
+            .map(|project| project.root.clone())
+            .collect();
+        self.reload_configs(&roots).await;
+    }
+
+    /// Tell the user about editor settings the config skips, which would otherwise look
+    /// like they were applied.
+    async fn report_invalid_settings(&self, settings: &Value) {
+        let errors = I18nConfig::invalid_settings(settings);
+        if errors.is_empty() {
+            return;
+        }
+
+        let message = format!("Ignoring invalid i18n settings: {}", errors.join("; "));
+        tracing::warn!("{}", message);
+        self.client
+            .show_message(MessageType::WARNING, message)
+            .await;
+    }
+
+    /// Reload the config of the projects at `roots`, then everything that depends on it.
+    async fn reload_configs(&self, roots: &[PathBuf]) {
+        for root in roots {
+            self.reload_project_config(root).await;
+        }
+        self.reregister_watched_files_capability().await;
+        self.re_diagnose_open_documents().await;
+    }
+
+    /// Put a reloaded project in place of the one with the same root, with the messages
+    /// of the open documents that belong to it.
+    async fn replace_project(&self, project: Project, skipped: &[PathBuf], action: &str) {
+        let root = project.root.clone();
+        self.log_skipped_files(&root, skipped).await;
+
+        {
+            let projects = self.projects.read().await;
+            let docs = self.documents.read().await;
+            for uri in docs.uris() {
+                let (Some(doc), Ok(url)) = (docs.get(&uri), Url::parse(&uri)) else {
+                    continue;
+                };
+                let Ok(path) = url.to_file_path() else {
+                    continue;
+                };
+                if project::nearest(&projects, &path).is_some_and(|owner| owner.root == root) {
+                    let source = Self::source_language(&url, Some(doc));
+                    project.store.index_document(&path, &doc.content, &source);
+                }
+            }
+        }
+
+        let locales = project.store.get_locales();
+        let keys = project.store.get_all_keys();
 
         self.client
             .log_message(
                 MessageType::INFO,
                 format!(
-                    "Reloaded translations: {} locales, {} keys",
+                    "{}: {} locales, {} keys in {:?}",
+                    action,
                     locales.len(),
-                    keys.len()
+                    keys.len(),
+                    root
                 ),
             )
             .await;
 
-        *self.translation_store.write().await = Some(store);
+        {
+            let mut projects = self.projects.write().await;
+            if let Some(slot) = projects.iter_mut().find(|p| p.root == root) {
+                *slot = Arc::new(project);
+            }
+        }
+        self.publish_config_diagnostics(&root).await;
         self.refresh_inlay_hints().await;
     }
 
@@ -605,19 +1347,385 @@ impl I18nBackend {
         }
     }
 
-    async fn get_definition_locations(&self, key: &str) -> Vec<Location> {
-        let translation_store = self.translation_store.read().await;
-        let config = self.config.read().await;
-        let Some(store) = translation_store.as_ref() else {
-            return Vec::new();
+    async fn create_raw_translation_key(&self, arguments: &[Value]) {
+        let key = arguments
+            .first()
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let Some(key) = key else {
+            tracing::warn!("createRawTranslationKey: missing key argument");
+            return;
         };
 
-        let translations = store.get_all_translations(key);
+        // Optional second argument: the exact files to write into, as chosen by a code action
+        let requested_files: Option<Vec<PathBuf>> =
+            arguments.get(1).and_then(|v| v.as_array()).map(|files| {
+                files
+                    .iter()
+                    .filter_map(|f| f.as_str())
+                    .map(PathBuf::from)
+                    .collect()
+            });
+
+        let raw_value = format!("_{}_", key);
+
+        let (all_files, mut roots) = match requested_files {
+            Some(files) => {
+                // Only files the projects loaded translations from, so a crafted command
+                // cannot write into arbitrary files
+                let projects = self.projects.read().await;
+                let mut accepted = Vec::new();
+                let mut roots = Vec::new();
+                for file in files {
+                    match Self::translation_file_owner(&projects, &file) {
+                        Some(project) => {
+                            roots.push(project.root.clone());
+                            accepted.push(file);
+                        }
+                        None => tracing::warn!(
+                            "createRawTranslationKey: {:?} is not a translation file, skipping",
+                            file
+                        ),
+                    }
+                }
+                (accepted, roots)
+            }
+            None => {
+                // Pick the best file of every locale that lacks the key; on a tie the
+                // first candidate wins since there is nobody to ask
+                let mut all_files: Vec<PathBuf> = Vec::new();
+                let mut roots = Vec::new();
+                for project in self.command_projects(arguments.get(2)).await {
+                    let store = &project.store;
+                    for locale in store.get_missing_locales(&key) {
+                        let targets = Self::target_files_for_key(store, &locale, &key, None);
+                        if let Some(path) = targets.into_iter().next() {
+                            if !all_files.contains(&path) {
+                                all_files.push(path);
+                            }
+                        }
+                    }
+                    roots.push(project.root.clone());
+                }
+                (all_files, roots)
+            }
+        };
+        roots.sort();
+        roots.dedup();
+
+        tracing::info!(
+            "Creating raw translation key '{}' with value '{}' in {} locale files",
+            key,
+            raw_value,
+            all_files.len()
+        );
+
+        let mut files_written = 0;
+        for file_path in &all_files {
+            if !Self::is_writable_translation_file(file_path) {
+                tracing::debug!("Skipping non-JSON file: {:?}", file_path);
+                continue;
+            }
+
+            let file_content = match std::fs::read_to_string(file_path) {
+                Ok(content) => content,
+                Err(e) => {
+                    tracing::warn!("Failed to read {:?}: {}", file_path, e);
+                    continue;
+                }
+            };
+
+            let result = Self::insert_key_into_json(&file_content, &key, &raw_value);
+            let Some((new_content, _, _)) = result else {
+                tracing::warn!("Failed to insert key into {:?}", file_path);
+                continue;
+            };
+
+            if let Err(e) = std::fs::write(file_path, &new_content) {
+                tracing::warn!("Failed to write {:?}: {}", file_path, e);
+                continue;
+            }
+
+            files_written += 1;
+        }
+
+        tracing::info!(
+            "Inserted raw key '{}' into {}/{} locale files",
+            key,
+            files_written,
+            all_files.len()
+        );
+
+        // Reload translations so the new key is recognized immediately
+        for root in &roots {
+            self.reload_project(root).await;
+        }
+
+        // Re-diagnose all open documents to clear stale warnings
+        self.re_diagnose_open_documents().await;
+    }
+
+    /// Fill locales that lack `keys` (or every key of the source locale when `None`)
+    /// with machine translations of the source value in each project that has a
+    /// translation provider, and mark the results for review.
+    async fn translate_missing_locales(
+        &self,
+        projects: Vec<Arc<Project>>,
+        keys: Option<&[String]>,
+    ) {
+        let projects: Vec<Arc<Project>> = projects
+            .into_iter()
+            .filter(|project| project.config.translation_provider.is_some())
+            .collect();
+        if projects.is_empty() {
+            self.client
+                .show_message(
+                    MessageType::WARNING,
+                    "No translationProvider configured in .zed/i18n.json",
+                )
+                .await;
+            return;
+        }
+
+        for project in projects {
+            self.translate_project(&project, keys).await;
+        }
+        self.re_diagnose_open_documents().await;
+    }
+
+    async fn translate_project(&self, project: &Project, keys: Option<&[String]>) {
+        let source_locale = project.config.source_locale.clone();
+        let Some(provider_config) = project.config.translation_provider.clone() else {
+            return;
+        };
+
+        let provider = match TranslationProvider::new(provider_config) {
+            Ok(provider) => provider,
+            Err(err) => {
+                self.client
+                    .show_message(MessageType::ERROR, format!("Translation failed: {:#}", err))
+                    .await;
+                return;
+            }
+        };
+
+        let (jobs, skipped) = {
+            let store = &project.store;
+            let keys = keys.map(<[String]>::to_vec).unwrap_or_else(|| {
+                let mut keys = store.get_all_keys();
+                keys.sort();
+                keys
+            });
+            Self::collect_translation_jobs(store, &source_locale, &keys)
+        };
+
+        let mut writes: BTreeMap<PathBuf, Vec<(String, String)>> = BTreeMap::new();
+        let mut translated: Vec<(String, String)> = Vec::new();
+        let mut failures = 0;
+
+        for (locale, locale_jobs) in &jobs {
+            for batch in locale_jobs.chunks(TRANSLATION_BATCH_SIZE) {
+                let protected: Vec<_> = batch
+                    .iter()
+                    .map(|job| translator::protect(&job.source_value))
+                    .collect();
+                let texts: Vec<String> = protected.iter().map(|p| p.text.clone()).collect();
+
+                let results = match provider.translate(&texts, &source_locale, locale).await {
+                    Ok(results) => results,
+                    Err(err) => {
+                        tracing::warn!("Translating into {} failed: {:#}", locale, err);
+                        failures += batch.len();
+                        continue;
+                    }
+                };
+
+                for ((job, protected), result) in batch.iter().zip(&protected).zip(results) {
+                    match protected.restore(&result) {
+                        Ok(value) => {
+                            writes
+                                .entry(job.file_path.clone())
+                                .or_default()
+                                .push((job.key.clone(), value));
+                            translated.push((locale.clone(), job.key.clone()));
+                        }
+                        Err(err) => {
+                            tracing::warn!("Discarding translation of '{}': {}", job.key, err);
+                            failures += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        for (file_path, entries) in &writes {
+            let Ok(mut content) = std::fs::read_to_string(file_path) else {
+                tracing::warn!("Failed to read {:?}", file_path);
+                continue;
+            };
+            for (key, value) in entries {
+                match Self::insert_key_into_json(&content, key, value) {
+                    Some((new_content, _, _)) => content = new_content,
+                    None => tracing::warn!("Failed to insert key '{}' into {:?}", key, file_path),
+                }
+            }
+            if let Err(e) = std::fs::write(file_path, &content) {
+                tracing::warn!("Failed to write {:?}: {}", file_path, e);
+            }
+        }
+
+        {
+            let mut review_log = project.review_log.write().await;
+            for (locale, key) in &translated {
+                review_log.mark(locale, key);
+            }
+            if let Err(err) = review_log.save() {
+                tracing::warn!("Failed to save review log: {}", err);
+            }
+        }
+
+        let mut message = format!(
+            "Machine translated {} values into {} locales; marked for review",
+            translated.len(),
+            jobs.len()
+        );
+        if failures > 0 {
+            message.push_str(&format!(", {} failed (see log)", failures));
+        }
+        if skipped > 0 {
+            message.push_str(&format!(", {} ICU plural/select values skipped", skipped));
+        }
+        self.client.show_message(MessageType::INFO, message).await;
+
+        self.reload_project(&project.root).await;
+    }
+
+    /// Group the missing `(locale, key)` pairs by target locale. Returns the jobs and the
+    /// number of values skipped because they are ICU plural/select messages.
+    fn collect_translation_jobs(
+        store: &TranslationStore,
+        source_locale: &str,
+        keys: &[String],
+    ) -> (BTreeMap<String, Vec<TranslationJob>>, usize) {
+        let mut jobs: BTreeMap<String, Vec<TranslationJob>> = BTreeMap::new();
+        let mut skipped = 0;
+
+        for key in keys {
+            let Some(source_value) = store.get_translation(key, source_locale) else {
+                continue;
+            };
+            if source_value.starts_with('_') && source_value.ends_with('_') {
+                continue;
+            }
+
+            let missing_locales: Vec<String> = store
+                .get_missing_locales(key)
+                .into_iter()
+                .filter(|locale| locale != source_locale)
+                .filter(|locale| !PseudoStyle::is_pseudo_locale(locale))
+                .collect();
+            if missing_locales.is_empty() {
+                continue;
+            }
+            if translator::has_icu_branches(&source_value) {
+                skipped += 1;
+                continue;
+            }
+
+            for locale in missing_locales {
+                let targets = Self::target_files_for_key(store, &locale, key, None);
+                let Some(file_path) = targets.into_iter().next() else {
+                    continue;
+                };
+                jobs.entry(locale).or_default().push(TranslationJob {
+                    key: key.clone(),
+                    source_value: source_value.clone(),
+                    file_path,
+                });
+            }
+        }
+
+        (jobs, skipped)
+    }
+
+    async fn generate_pseudo_locales(&self, styles: &[PseudoStyle]) {
+        let projects = self.projects.read().await.clone();
+
+        let mut written = Vec::new();
+        let mut roots = Vec::new();
+        for project in projects {
+            let source_locale = &project.config.source_locale;
+            let count = written.len();
+            for style in styles {
+                match pseudo::generate(&project.store, source_locale, *style) {
+                    Ok(paths) => written.extend(paths),
+                    Err(err) => {
+                        self.client
+                            .show_message(
+                                MessageType::ERROR,
+                                format!("Failed to generate {}: {:#}", style.locale(), err),
+                            )
+                            .await;
+                    }
+                }
+            }
+            if written.len() > count {
+                roots.push(project.root.clone());
+            }
+        }
+
+        if written.is_empty() {
+            return;
+        }
+
+        let locales: Vec<&str> = styles.iter().map(|style| style.locale()).collect();
+        self.client
+            .show_message(
+                MessageType::INFO,
+                format!(
+                    "Generated pseudo locales {} ({} files)",
+                    locales.join(", "),
+                    written.len()
+                ),
+            )
+            .await;
+
+        for root in &roots {
+            self.reload_project(root).await;
+        }
+        self.re_diagnose_open_documents().await;
+    }
+
+    async fn mark_translation_reviewed(&self, projects: Vec<Arc<Project>>, key: &str) {
+        let mut resolved = false;
+        for project in projects {
+            let mut review_log = project.review_log.write().await;
+            if !review_log.resolve(key) {
+                continue;
+            }
+            resolved = true;
+            if let Err(err) = review_log.save() {
+                tracing::warn!("Failed to save review log: {}", err);
+            }
+        }
+
+        if resolved {
+            self.re_diagnose_open_documents().await;
+        }
+    }
+
+    fn get_definition_locations(
+        project: &Project,
+        key: &str,
+        component: Option<&Path>,
+    ) -> Vec<Location> {
+        let translations = project.store.get_all_translations_in(key, component);
         if translations.is_empty() {
             return Vec::new();
         }
 
-        let source_locale = &config.source_locale;
+        let source_locale = &project.config.source_locale;
         let mut other_locales: Vec<String> = translations
             .keys()
             .filter(|locale| *locale != source_locale)
@@ -633,7 +1741,8 @@ impl I18nBackend {
 
         let mut locations = Vec::new();
         for locale in locales {
-            if let Some(location) = store.get_translation_location(key, &locale) {
+            if let Some(entry) = translations.get(&locale) {
+                let location = TranslationStore::location_of(key, entry);
                 if let Ok(uri) = Url::from_file_path(&location.file_path) {
                     locations.push(Location {
                         uri,
@@ -721,23 +1830,39 @@ impl LanguageServer for I18nBackend {
             watched_files_relative_pattern_support
         );
 
-        let root_path = params
-            .workspace_folders
+        let configuration_pull_supported = params
+            .capabilities
+            .workspace
             .as_ref()
-            .and_then(|folders| folders.first())
-            .and_then(|folder| folder.uri.to_file_path().ok())
-            .or_else(|| {
+            .and_then(|workspace| workspace.configuration)
+            .unwrap_or(false);
+        *self.configuration_pull_supported.write().await = configuration_pull_supported;
+
+        if let Some(options) = params.initialization_options.clone() {
+            *self.settings.write().await = Self::settings_section(options);
+        }
+
+        let mut folders: Vec<PathBuf> = params
+            .workspace_folders
+            .iter()
+            .flatten()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect();
+        if folders.is_empty() {
+            folders.extend(
                 params
                     .root_uri
                     .as_ref()
-                    .and_then(|uri| uri.to_file_path().ok())
-            });
+                    .and_then(|uri| uri.to_file_path().ok()),
+            );
+        }
 
-        if let Some(root) = root_path {
-            self.initialize_workspace(root).await;
-        } else {
+        if folders.is_empty() {
             tracing::warn!("No workspace root found in initialize params");
         }
+        for folder in folders {
+            self.add_workspace_folder(folder).await;
+        }
 
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
@@ -761,6 +1886,11 @@ impl LanguageServer for I18nBackend {
                     ]),
                     ..Default::default()
                 }),
+                signature_help_provider: Some(SignatureHelpOptions {
+                    trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+                    retrigger_characters: None,
+                    work_done_progress_options: Default::default(),
+                }),
                 definition_provider: Some(OneOf::Left(true)),
                 inlay_hint_provider: Some(OneOf::Right(InlayHintServerCapabilities::Options(
                     InlayHintOptions {
@@ -776,9 +1906,22 @@ impl LanguageServer for I18nBackend {
                     },
                 )),
                 execute_command_provider: Some(ExecuteCommandOptions {
-                    commands: vec!["intl-lens.createRawTranslationKey".to_string()],
+                    commands: vec![
+                        CREATE_RAW_TRANSLATION_KEY_COMMAND.to_string(),
+                        TRANSLATE_MISSING_LOCALES_COMMAND.to_string(),
+                        TRANSLATE_ALL_MISSING_LOCALES_COMMAND.to_string(),
+                        MARK_TRANSLATION_REVIEWED_COMMAND.to_string(),
+                        GENERATE_PSEUDO_LOCALE_COMMAND.to_string(),
+                    ],
                     work_done_progress_options: Default::default(),
                 }),
+                workspace: Some(WorkspaceServerCapabilities {
+                    workspace_folders: Some(WorkspaceFoldersServerCapabilities {
+                        supported: Some(true),
+                        change_notifications: Some(OneOf::Left(true)),
+                    }),
+                    file_operations: None,
+                }),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
@@ -794,24 +1937,91 @@ impl LanguageServer for I18nBackend {
             .await;
         self.register_inlay_hint_capability().await;
         self.register_watched_files_capability().await;
+
+        let initial_settings = self.settings.read().await.clone();
+        self.report_invalid_settings(&initial_settings).await;
+        if let Some(settings) = self.pull_settings().await {
+            self.apply_settings(settings).await;
+        }
+    }
+
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        // Clients that answer `workspace/configuration` may send nothing useful here
+        let settings = match self.pull_settings().await {
+            Some(settings) => settings,
+            None => Self::settings_section(params.settings),
+        };
+        self.apply_settings(settings).await;
     }
 
     async fn shutdown(&self) -> Result<()> {
         Ok(())
     }
 
+    async fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
+        for folder in &params.event.removed {
+            if let Ok(path) = folder.uri.to_file_path() {
+                tracing::info!("Removing workspace folder {:?}", path);
+                self.remove_workspace_folder(&path).await;
+            }
+        }
+        for folder in &params.event.added {
+            if let Ok(path) = folder.uri.to_file_path() {
+                self.add_workspace_folder(path).await;
+            }
+        }
+
+        // Watch the locale directories of the new set of projects
+        self.reregister_watched_files_capability().await;
+
+        self.re_diagnose_open_documents().await;
+        self.refresh_inlay_hints().await;
+    }
+
     async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
-        let mut has_translation_changes = false;
+        // Packages created since their workspace folder was opened
+        let folders = self.workspace_folders.read().await.clone();
+        let mut added = false;
         for change in &params.changes {
-            if self.is_translation_uri(&change.uri).await {
-                has_translation_changes = true;
-                break;
+            let Ok(path) = change.uri.to_file_path() else {
+                continue;
+            };
+            if change.typ == FileChangeType::DELETED {
+                continue;
+            }
+            for folder in &folders {
+                if let Some(root) = project::discovered_root(folder, &path) {
+                    tracing::info!("Found new project {:?}", root);
+                    added |= self.add_project(root).await;
+                }
             }
         }
+        if added {
+            self.reregister_watched_files_capability().await;
+        }
 
-        if has_translation_changes {
+        let mut config_roots = Vec::new();
+        let mut roots = Vec::new();
+        for change in &params.changes {
+            config_roots.extend(self.config_project_roots(&change.uri).await);
+            roots.extend(self.translation_project_roots(&change.uri).await);
+        }
+        config_roots.sort();
+        config_roots.dedup();
+        roots.sort();
+        roots.dedup();
+        // A config reload rescans the translations as well
+        roots.retain(|root| !config_roots.contains(root));
+
+        if !config_roots.is_empty() {
+            tracing::info!("Config files changed, reloading...");
+            self.reload_configs(&config_roots).await;
+        }
+        if !roots.is_empty() {
             tracing::info!("Translation files changed, reloading...");
-            self.reload_translations().await;
+        }
+        for root in &roots {
+            self.reload_project(root).await;
         }
     }
 
@@ -822,9 +2032,15 @@ impl LanguageServer for I18nBackend {
 
         {
             let mut docs = self.documents.write().await;
-            docs.open(uri.to_string(), content.clone(), version);
+            docs.open(
+                uri.to_string(),
+                content.clone(),
+                version,
+                params.text_document.language_id.clone(),
+            );
         }
 
+        self.index_document(&uri, &content).await;
         self.diagnose_document(&uri, &content).await;
     }
 
@@ -840,14 +2056,23 @@ impl LanguageServer for I18nBackend {
                 docs.update(uri.as_str(), content.clone(), version);
             }
 
+            self.index_document(&uri, &content).await;
             self.diagnose_document(&uri, &content).await;
         }
     }
 
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
-        if self.is_translation_uri(&params.text_document.uri).await {
-            tracing::info!("Translation file saved, reloading...");
-            self.reload_translations().await;
+        let uri = &params.text_document.uri;
+        let config_roots = self.config_project_roots(uri).await;
+        if !config_roots.is_empty() {
+            tracing::info!("Config file saved, reloading...");
+            self.reload_configs(&config_roots).await;
+            return;
+        }
+
+        for root in self.translation_project_roots(uri).await {
+            tracing::info!("Translation file saved, reloading {:?}...", root);
+            self.reload_project(&root).await;
         }
     }
 
@@ -860,23 +2085,33 @@ impl LanguageServer for I18nBackend {
         let uri = params.text_document_position_params.text_document.uri;
         let position = params.text_document_position_params.position;
 
+        let Some(project) = self.project_for(&uri).await else {
+            return Ok(None);
+        };
         let docs = self.documents.read().await;
         let Some(doc) = docs.get(uri.as_str()) else {
             return Ok(None);
         };
 
         let content = doc.content.to_string();
-        let key_finder = self.key_finder.read().await;
 
-        let Some(found_key) = key_finder.find_key_at_position(
+        let Some(found_key) = project.key_finder.find_key_at_position_in(
             &content,
+            &Self::source_language(&uri, Some(doc)),
             position.line as usize,
             position.character as usize,
         ) else {
             return Ok(None);
         };
 
-        let Some(hover_content) = self.get_hover_content(&found_key.key).await else {
+        let hover_content = match &found_key.pattern {
+            Some(pattern) => Self::get_pattern_hover_content(&project, pattern),
+            None => {
+                let component = uri.to_file_path().ok();
+                Self::get_hover_content(&project, &found_key.key, component.as_deref())
+            }
+        };
+        let Some(hover_content) = hover_content else {
             return Ok(None);
         };
 
@@ -893,6 +2128,9 @@ impl LanguageServer for I18nBackend {
         let uri = params.text_document_position.text_document.uri;
         let position = params.text_document_position.position;
 
+        let Some(project) = self.project_for(&uri).await else {
+            return Ok(None);
+        };
         let docs = self.documents.read().await;
         let Some(doc) = docs.get(uri.as_str()) else {
             return Ok(None);
@@ -911,7 +2149,19 @@ impl LanguageServer for I18nBackend {
             return Ok(None);
         };
 
-        let completions = self.get_completions(&prefix).await;
+        let offset = Self::position_offset(&content, position);
+        let key_prefix =
+            project
+                .key_finder
+                .prefix_at(&content, &Self::source_language(&uri, Some(doc)), offset);
+
+        let component = uri.to_file_path().ok();
+        let completions = Self::get_completions(
+            &project,
+            &prefix,
+            key_prefix.as_deref(),
+            component.as_deref(),
+        );
 
         if completions.is_empty() {
             return Ok(None);
@@ -920,6 +2170,80 @@ impl LanguageServer for I18nBackend {
         Ok(Some(CompletionResponse::Array(completions)))
     }
 
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let (content, source) = {
+            let docs = self.documents.read().await;
+            let Some(doc) = docs.get(uri.as_str()) else {
+                return Ok(None);
+            };
+            (
+                doc.content.to_string(),
+                Self::source_language(&uri, Some(doc)),
+            )
+        };
+
+        let Some(project) = self.project_for(&uri).await else {
+            return Ok(None);
+        };
+        let offset = Self::position_offset(&content, position);
+        let Some((found_key, argument)) = project.key_finder.call_at(&content, &source, offset)
+        else {
+            return Ok(None);
+        };
+
+        let store = &project.store;
+        let Some(metadata) = store
+            .arb_metadata(&found_key.key)
+            .filter(|metadata| !metadata.placeholders.is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let parameters: Vec<String> = metadata
+            .placeholders
+            .iter()
+            .map(|placeholder| placeholder.parameter())
+            .collect();
+        let source_locale = &project.config.source_locale;
+        let documentation = metadata
+            .description
+            .iter()
+            .cloned()
+            .chain(store.get_translation(&found_key.key, source_locale))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let signature = SignatureInformation {
+            label: format!("{}({})", found_key.key, parameters.join(", ")),
+            documentation: (!documentation.is_empty())
+                .then_some(Documentation::String(documentation)),
+            parameters: Some(
+                metadata
+                    .placeholders
+                    .iter()
+                    .zip(parameters)
+                    .map(|(placeholder, label)| ParameterInformation {
+                        label: ParameterLabel::Simple(label),
+                        documentation: placeholder
+                            .example
+                            .as_ref()
+                            .map(|example| Documentation::String(format!("e.g. {}", example))),
+                    })
+                    .collect(),
+            ),
+            active_parameter: None,
+        };
+
+        Ok(Some(SignatureHelp {
+            signatures: vec![signature],
+            active_signature: Some(0),
+            active_parameter: Some(argument as u32),
+        }))
+    }
+
     async fn goto_definition(
         &self,
         params: GotoDefinitionParams,
@@ -927,23 +2251,37 @@ impl LanguageServer for I18nBackend {
         let uri = params.text_document_position_params.text_document.uri;
         let position = params.text_document_position_params.position;
 
+        let Some(project) = self.project_for(&uri).await else {
+            return Ok(None);
+        };
         let docs = self.documents.read().await;
         let Some(doc) = docs.get(uri.as_str()) else {
             return Ok(None);
         };
 
         let content = doc.content.to_string();
-        let key_finder = self.key_finder.read().await;
 
-        let Some(found_key) = key_finder.find_key_at_position(
+        let Some(found_key) = project.key_finder.find_key_at_position_in(
             &content,
+            &Self::source_language(&uri, Some(doc)),
             position.line as usize,
             position.character as usize,
         ) else {
             return Ok(None);
         };
 
-        let locations = self.get_definition_locations(&found_key.key).await;
+        let locations = match &found_key.pattern {
+            Some(pattern) => project
+                .store
+                .keys_matching(pattern)
+                .iter()
+                .flat_map(|key| Self::get_definition_locations(&project, key, None))
+                .collect(),
+            None => {
+                let component = uri.to_file_path().ok();
+                Self::get_definition_locations(&project, &found_key.key, component.as_deref())
+            }
+        };
         if locations.is_empty() {
             return Ok(None);
         }
@@ -957,41 +2295,100 @@ impl LanguageServer for I18nBackend {
 
     async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
         let mut actions = Vec::new();
+        let uri = params.text_document.uri;
+        let project = self.project_for(&uri).await;
+        let project_root = project.as_ref().map(|project| project.root.clone());
+        let has_translation_provider = project
+            .as_ref()
+            .is_some_and(|project| project.config.translation_provider.is_some());
 
         for diagnostic in &params.context.diagnostics {
-            let is_missing = diagnostic
-                .code
-                .as_ref()
-                .map(|c| matches!(c, NumberOrString::String(s) if s == "missing-translation"))
-                .unwrap_or(false);
-
-            if !is_missing {
+            let code = diagnostic_code(diagnostic);
+            if !matches!(
+                code,
+                Some(MISSING_TRANSLATION | INCOMPLETE_TRANSLATION | MACHINE_TRANSLATED)
+            ) {
                 continue;
             }
 
-            let key = diagnostic
-                .message
-                .strip_prefix("Translation key '")
-                .and_then(|s| s.strip_suffix("' not found"))
-                .map(|s| s.to_string());
-
-            let Some(key) = key else {
+            let Some(data) = KeyDiagnosticData::from_diagnostic(diagnostic) else {
                 continue;
             };
 
-            let action = CodeAction {
-                title: format!("Create raw translation key '{}'", key),
-                kind: Some(CodeActionKind::QUICKFIX),
-                diagnostics: Some(vec![diagnostic.clone()]),
-                command: Some(Command {
-                    title: format!("Create raw translation key '{}'", key),
-                    command: "intl-lens.createRawTranslationKey".to_string(),
-                    arguments: Some(vec![Value::String(key)]),
-                }),
-                ..Default::default()
-            };
+            if code == Some(MACHINE_TRANSLATED) {
+                let title = format!("Mark '{}' as reviewed", data.key);
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: title.clone(),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    command: Some(Command {
+                        title,
+                        command: MARK_TRANSLATION_REVIEWED_COMMAND.to_string(),
+                        arguments: Some(vec![
+                            Value::String(data.key.clone()),
+                            Value::String(uri.to_string()),
+                        ]),
+                    }),
+                    ..Default::default()
+                }));
+                continue;
+            }
 
-            actions.push(CodeActionOrCommand::CodeAction(action));
+            if code == Some(INCOMPLETE_TRANSLATION) && has_translation_provider {
+                let title = format!(
+                    "Translate '{}' into {}",
+                    data.key,
+                    data.missing_locales.join(", ")
+                );
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: title.clone(),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    command: Some(Command {
+                        title,
+                        command: TRANSLATE_MISSING_LOCALES_COMMAND.to_string(),
+                        arguments: Some(vec![
+                            Value::String(data.key.clone()),
+                            Value::String(uri.to_string()),
+                        ]),
+                    }),
+                    ..Default::default()
+                }));
+            }
+
+            for (index, suggestion) in data.suggestions.iter().enumerate() {
+                // Under a scoped `t` the literal only holds the part after the prefix.
+                let new_text = match &data.key_prefix {
+                    Some(prefix) => match suggestion.strip_prefix(&format!("{}.", prefix)) {
+                        Some(relative) => relative.to_string(),
+                        None => continue,
+                    },
+                    None => suggestion.clone(),
+                };
+                let title = format!("Replace with '{}'", new_text);
+                let edit = TextEdit {
+                    range: diagnostic.range,
+                    new_text,
+                };
+
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(index == 0),
+                    ..Default::default()
+                }));
+            }
+
+            actions.extend(Self::create_key_actions(
+                diagnostic,
+                &data,
+                project_root.as_deref(),
+            ));
         }
 
         if actions.is_empty() {
@@ -1002,89 +2399,52 @@ impl LanguageServer for I18nBackend {
     }
 
     async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
-        if params.command != "intl-lens.createRawTranslationKey" {
-            return Ok(None);
-        }
-
-        let key = params
+        let key_argument = params
             .arguments
             .first()
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
-        let Some(key) = key else {
-            tracing::warn!("createRawTranslationKey: missing key argument");
-            return Ok(None);
-        };
-
-        let raw_value = format!("_{}_", key);
-        tracing::info!(
-            "Creating raw translation key '{}' with value '{}' in all locale files",
-            key,
-            raw_value
-        );
-
-        let translation_store = self.translation_store.read().await;
-        let Some(store) = translation_store.as_ref() else {
-            tracing::warn!("createRawTranslationKey: no translation store");
-            return Ok(None);
-        };
-
-        // Collect all locale file paths
-        let locales = store.get_locales();
-        let mut all_files: Vec<PathBuf> = Vec::new();
-        for locale in &locales {
-            for path in store.get_locale_file_paths(locale) {
-                if !all_files.contains(&path) {
-                    all_files.push(path);
-                }
+        match params.command.as_str() {
+            CREATE_RAW_TRANSLATION_KEY_COMMAND => {
+                self.create_raw_translation_key(&params.arguments).await
             }
-        }
-        drop(translation_store);
-
-        let mut files_written = 0;
-        for file_path in &all_files {
-            let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if ext != "json" {
-                tracing::debug!("Skipping non-JSON file: {:?}", file_path);
-                continue;
+            TRANSLATE_MISSING_LOCALES_COMMAND => {
+                let Some(key) = key_argument else {
+                    tracing::warn!("translateMissingLocales: missing key argument");
+                    return Ok(None);
+                };
+                let projects = self.command_projects(params.arguments.get(1)).await;
+                self.translate_missing_locales(projects, Some(&[key])).await;
             }
-
-            let file_content = match std::fs::read_to_string(file_path) {
-                Ok(content) => content,
-                Err(e) => {
-                    tracing::warn!("Failed to read {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
-
-            let result = Self::insert_key_into_json(&file_content, &key, &raw_value);
-            let Some((new_content, _, _)) = result else {
-                tracing::warn!("Failed to insert key into {:?}", file_path);
-                continue;
-            };
-
-            if let Err(e) = std::fs::write(file_path, &new_content) {
-                tracing::warn!("Failed to write {:?}: {}", file_path, e);
-                continue;
+            TRANSLATE_ALL_MISSING_LOCALES_COMMAND => {
+                let projects = self.projects.read().await.clone();
+                self.translate_missing_locales(projects, None).await;
             }
-
-            files_written += 1;
+            MARK_TRANSLATION_REVIEWED_COMMAND => {
+                let Some(key) = key_argument else {
+                    tracing::warn!("markTranslationReviewed: missing key argument");
+                    return Ok(None);
+                };
+                let projects = self.command_projects(params.arguments.get(1)).await;
+                self.mark_translation_reviewed(projects, &key).await;
+            }
+            GENERATE_PSEUDO_LOCALE_COMMAND => {
+                let styles = match key_argument {
+                    Some(name) => match PseudoStyle::from_name(&name) {
+                        Some(style) => vec![style],
+                        None => {
+                            tracing::warn!("generatePseudoLocale: unknown style '{}'", name);
+                            return Ok(None);
+                        }
+                    },
+                    None => PseudoStyle::all().to_vec(),
+                };
+                self.generate_pseudo_locales(&styles).await;
+            }
+            _ => {}
         }
 
-        tracing::info!(
-            "Inserted raw key '{}' into {}/{} locale files",
-            key,
-            files_written,
-            all_files.len()
-        );
-
-        // Reload translations so the new key is recognized immediately
-        self.reload_translations().await;
-
-        // Re-diagnose all open documents to clear stale warnings
-        self.re_diagnose_open_documents().await;
-
         Ok(None)
     }
 
@@ -1092,7 +2452,10 @@ impl LanguageServer for I18nBackend {
         let uri = params.text_document.uri;
         tracing::debug!(">>> inlay_hint: uri={}, range={:?}", uri, params.range);
 
-        let source_locale = self.config.read().await.source_locale.clone();
+        let Some(project) = self.project_for(&uri).await else {
+            return Ok(None);
+        };
+        let source_locale = &project.config.source_locale;
 
         let docs = self.documents.read().await;
         let Some(doc) = docs.get(uri.as_str()) else {
@@ -1101,14 +2464,13 @@ impl LanguageServer for I18nBackend {
         };
 
         let content = doc.content.as_str();
-        let key_finder = self.key_finder.read().await;
-        let found_keys = key_finder.find_keys(content);
+        let found_keys = project
+            .key_finder
+            .find_keys_in(content, &Self::source_language(&uri, Some(doc)));
 
-        let translation_store = self.translation_store.read().await;
-        let Some(store) = translation_store.as_ref() else {
-            return Ok(None);
-        };
+        let store = &project.store;
 
+        let component = uri.to_file_path().ok();
         let mut hints = Vec::new();
         let request_range = params.range;
         let request_is_empty = request_range.start == request_range.end;
@@ -1135,9 +2497,29 @@ impl LanguageServer for I18nBackend {
                 continue;
             }
 
-            if let Some(translation) = store.get_translation(&found_key.key, &source_locale) {
-                let display_text = truncate_string(&translation, 30);
+            let label = match &found_key.pattern {
+                Some(pattern) => match store.keys_matching(pattern).len() {
+                    0 => None,
+                    1 => Some("→ 1 key".to_string()),
+                    count => Some(format!("→ {} keys", count)),
+                },
+                None => {
+                    let translations =
+                        store.get_all_translations_in(&found_key.key, component.as_deref());
+                    store
+                        .resolve_locale(source_locale, &translations)
+                        .map(|from| {
+                            let value = truncate_string(&translations[from].value, 30);
+                            if from == source_locale {
+                                format!("= {}", value)
+                            } else {
+                                format!("= {} (from {})", value, from)
+                            }
+                        })
+                }
+            };
 
+            if let Some(label) = label {
                 let mut hint_char = found_key.end_char;
                 if let Some(line) = content.lines().nth(found_key.line) {
                     let line_bytes = line.as_bytes();
@@ -1151,7 +2533,7 @@ impl LanguageServer for I18nBackend {
                         line: found_key.line as u32,
                         character: hint_char as u32,
                     },
-                    label: InlayHintLabel::String(format!("= {}", display_text)),
+                    label: InlayHintLabel::String(label),
                     kind: Some(InlayHintKind::TYPE),
                     text_edits: None,
                     tooltip: None,
@@ -1168,6 +2550,128 @@ impl LanguageServer for I18nBackend {
 }
 
 impl I18nBackend {
+    /// Build the "create key" quick fixes for a diagnostic: one covering every missing
+    /// locale (or one per candidate file name when the target file is ambiguous), plus
+    /// per-locale variants when several locales are missing.
+    fn create_key_actions(
+        diagnostic: &Diagnostic,
+        data: &KeyDiagnosticData,
+        workspace_root: Option<&Path>,
+    ) -> Vec<CodeActionOrCommand> {
+        // Namespaced references (`auth:login.title`) are stored without the namespace
+        let key = match data.namespace {
+            Some(_) => split_namespace(&data.key).1,
+            None => data.key.as_str(),
+        };
+
+        let make_action = |title: String, files: Vec<&FileCandidate>, preferred: bool| {
+            let files: Vec<Value> = files
+                .iter()
+                .map(|candidate| Value::String(candidate.path.to_string_lossy().to_string()))
+                .collect();
+
+            CodeActionOrCommand::CodeAction(CodeAction {
+                title: title.clone(),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                command: Some(Command {
+                    title,
+                    command: CREATE_RAW_TRANSLATION_KEY_COMMAND.to_string(),
+                    arguments: Some(vec![Value::String(key.to_string()), Value::Array(files)]),
+                }),
+                is_preferred: preferred.then_some(true),
+                ..Default::default()
+            })
+        };
+
+        // Without a writable file, say only YAML ones, the command would write nothing
+        if data.file_candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let mut actions = Vec::new();
+        let all_title = if diagnostic_code(diagnostic) == Some(MISSING_TRANSLATION) {
+            format!("Create raw translation key '{}'", key)
+        } else {
+            format!(
+                "Create raw translation key '{}' in {}",
+                key,
+                data.missing_locales.join(", ")
+            )
+        };
+
+        let is_ambiguous = data
+            .missing_locales
+            .iter()
+            .any(|locale| data.files_for_locale(locale).len() > 1);
+
+        if !is_ambiguous {
+            let all_files: Vec<&FileCandidate> = data.file_candidates.iter().collect();
+            actions.push(make_action(
+                all_title,
+                all_files,
+                data.suggestions.is_empty(),
+            ));
+        } else {
+            // One action per candidate file name, applied across every locale that has
+            // it; locales with a single obvious target always get that target.
+            let mut file_names: Vec<String> = data
+                .file_candidates
+                .iter()
+                .filter(|candidate| data.files_for_locale(&candidate.locale).len() > 1)
+                .filter_map(|candidate| {
+                    Some(candidate.path.file_name()?.to_string_lossy().to_string())
+                })
+                .collect();
+            file_names.sort();
+            file_names.dedup();
+
+            for file_name in file_names {
+                let files: Vec<&FileCandidate> = data
+                    .file_candidates
+                    .iter()
+                    .filter(|candidate| {
+                        data.files_for_locale(&candidate.locale).len() == 1
+                            || candidate.path.file_name().and_then(|n| n.to_str())
+                                == Some(file_name.as_str())
+                    })
+                    .collect();
+                let title = format!("Create raw translation key '{}' in {}", key, file_name);
+                actions.push(make_action(title, files, false));
+            }
+        }
+
+        if data.missing_locales.len() > 1 {
+            for locale in &data.missing_locales {
+                let files = data.files_for_locale(locale);
+                if files.len() == 1 {
+                    let title = format!("Create raw translation key '{}' in {} only", key, locale);
+                    actions.push(make_action(title, files, false));
+                    continue;
+                }
+
+                for candidate in files {
+                    let title = format!(
+                        "Create raw translation key '{}' in {} only",
+                        key,
+                        Self::display_path(&candidate.path, workspace_root)
+                    );
+                    actions.push(make_action(title, vec![candidate], false));
+                }
+            }
+        }
+
+        actions
+    }
+
+    fn display_path(path: &Path, workspace_root: Option<&Path>) -> String {
+        workspace_root
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
     /// Insert a (possibly nested) key into a JSON string with the given value,
     /// using text-based insertion to preserve existing formatting and key order.
     /// Returns `(new_content, cursor_line, cursor_character)`.
@@ -1180,10 +2684,11 @@ impl I18nBackend {
 
         // Flat style: all top-level values are non-objects (i.e. no nesting)
         let is_flat = root_obj.values().all(|v| !v.is_object());
+        let value = serde_json::to_string(value).ok()?;
 
         if is_flat || parts.len() == 1 {
             // Insert the full dotted key before the root closing }
-            let entry = format!("{}\"{}\": \"{}\"", indent, key, value);
+            let entry = format!("{}\"{}\": {}", indent, key, value);
             let brace_offset = content.rfind('}')?;
             let (new_content, insert_line) =
                 Self::insert_text_before_offset(content, brace_offset, &entry)?;
@@ -1213,12 +2718,7 @@ impl I18nBackend {
             for (i, part) in remaining.iter().enumerate() {
                 let level = base_indent_level + i;
                 if i == remaining.len() - 1 {
-                    entry_lines.push(format!(
-                        "{}\"{}\": \"{}\"",
-                        indent.repeat(level),
-                        part,
-                        value
-                    ));
+                    entry_lines.push(format!("{}\"{}\": {}", indent.repeat(level), part, value));
                 } else {
                     entry_lines.push(format!("{}\"{}\": {{", indent.repeat(level), part));
                 }
@@ -1403,6 +2903,18 @@ impl I18nBackend {
         Some((result, insert_at))
     }
 
+    /// Byte offset of `position`, whose character is taken as a byte index within the
+    /// line like in the other position lookups.
+    fn position_offset(content: &str, position: Position) -> usize {
+        let line = content.lines().nth(position.line as usize).unwrap_or("");
+        content
+            .split_inclusive('\n')
+            .take(position.line as usize)
+            .map(str::len)
+            .sum::<usize>()
+            + (position.character as usize).min(line.len())
+    }
+
     fn extract_completion_prefix(line: &str, character: usize) -> Option<String> {
         let before_cursor = &line[..character.min(line.len())];
 
@@ -1426,6 +2938,7 @@ impl I18nBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::TempDir;
 
     #[test]
     fn test_insert_flat_key_into_flat_json() {
@@ -1611,6 +3124,290 @@ mod tests {
         assert!(range.is_none());
     }
 
+    fn action_titles(actions: &[CodeActionOrCommand]) -> Vec<String> {
+        actions
+            .iter()
+            .map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => action.title.clone(),
+                CodeActionOrCommand::Command(command) => command.title.clone(),
+            })
+            .collect()
+    }
+
+    fn candidate(locale: &str, path: &str) -> FileCandidate {
+        FileCandidate {
+            locale: locale.to_string(),
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn test_create_key_actions_offer_one_action_per_ambiguous_file() {
+        let data = KeyDiagnosticData {
+            key: "auth.title".to_string(),
+            missing_locales: vec!["en".to_string(), "vi".to_string()],
+            file_candidates: vec![
+                candidate("en", "/ws/locales/en/auth.json"),
+                candidate("en", "/ws/locales/en/common.json"),
+                candidate("vi", "/ws/locales/vi/auth.json"),
+            ],
+            ..Default::default()
+        };
+        let diagnostic = Diagnostic {
+            code: Some(NumberOrString::String(MISSING_TRANSLATION.to_string())),
+            data: data.to_value(),
+            ..Default::default()
+        };
+
+        let actions = I18nBackend::create_key_actions(&diagnostic, &data, Some(Path::new("/ws")));
+        assert_eq!(
+            action_titles(&actions),
+            vec![
+                "Create raw translation key 'auth.title' in auth.json",
+                "Create raw translation key 'auth.title' in common.json",
+                "Create raw translation key 'auth.title' in locales/en/auth.json only",
+                "Create raw translation key 'auth.title' in locales/en/common.json only",
+                "Create raw translation key 'auth.title' in vi only",
+            ]
+        );
+
+        let CodeActionOrCommand::CodeAction(action) = &actions[1] else {
+            panic!("expected a code action");
+        };
+        let arguments = action.command.as_ref().unwrap().arguments.clone().unwrap();
+        assert_eq!(arguments[0], Value::String("auth.title".to_string()));
+        assert_eq!(
+            arguments[1],
+            serde_json::json!(["/ws/locales/en/common.json", "/ws/locales/vi/auth.json"])
+        );
+    }
+
+    #[test]
+    fn test_create_key_actions_single_target_per_locale() {
+        let data = KeyDiagnosticData {
+            key: "auth.title".to_string(),
+            missing_locales: vec!["en".to_string(), "vi".to_string()],
+            file_candidates: vec![
+                candidate("en", "/ws/locales/en/auth.json"),
+                candidate("vi", "/ws/locales/vi/auth.json"),
+            ],
+            ..Default::default()
+        };
+        let diagnostic = Diagnostic {
+            code: Some(NumberOrString::String(MISSING_TRANSLATION.to_string())),
+            ..Default::default()
+        };
+
+        let actions = I18nBackend::create_key_actions(&diagnostic, &data, None);
+        assert_eq!(
+            action_titles(&actions),
+            vec![
+                "Create raw translation key 'auth.title'",
+                "Create raw translation key 'auth.title' in en only",
+                "Create raw translation key 'auth.title' in vi only",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_key_actions_strip_namespace() {
+        let data = KeyDiagnosticData {
+            key: "auth:login.title".to_string(),
+            namespace: Some("auth".to_string()),
+            missing_locales: vec!["en".to_string()],
+            file_candidates: vec![candidate("en", "/ws/locales/en/auth.json")],
+            ..Default::default()
+        };
+        let diagnostic = Diagnostic {
+            code: Some(NumberOrString::String(INCOMPLETE_TRANSLATION.to_string())),
+            ..Default::default()
+        };
+
+        let actions = I18nBackend::create_key_actions(&diagnostic, &data, None);
+        assert_eq!(
+            action_titles(&actions),
+            vec!["Create raw translation key 'login.title' in en"]
+        );
+    }
+
+    #[test]
+    fn test_create_key_action_without_candidates() {
+        let data = KeyDiagnosticData {
+            key: "auth.title".to_string(),
+            missing_locales: vec!["en".to_string()],
+            ..Default::default()
+        };
+        let diagnostic = Diagnostic {
+            code: Some(NumberOrString::String(MISSING_TRANSLATION.to_string())),
+            ..Default::default()
+        };
+
+        // Only YAML files, say: there is nowhere to create the key
+        assert!(I18nBackend::create_key_actions(&diagnostic, &data, None).is_empty());
+    }
+
+    #[test]
+    fn test_only_translation_files_are_written() {
+        let root = TempDir::new("owner");
+        std::fs::create_dir_all(root.join("locales")).unwrap();
+        std::fs::write(root.join("locales/en.json"), r#"{"hello": "Hello"}"#).unwrap();
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+
+        let projects = vec![Arc::new(Project::load(root.clone(), &Value::Null).0)];
+        let owner = |path: PathBuf| I18nBackend::translation_file_owner(&projects, &path).is_some();
+        assert!(owner(root.join("locales/en.json")));
+        assert!(!owner(root.join("package.json")));
+        assert!(!owner(root.join("locales/../package.json")));
+    }
+
+    #[test]
+    fn test_dynamic_key_diagnostics() {
+        let root = TempDir::with_files(
+            "backend-dynamic-keys",
+            &[
+                (
+                    "locales/en.json",
+                    r#"{"errors": {"404": "Not found", "500": "Server error"}}"#,
+                ),
+                (
+                    "locales/vi.json",
+                    r#"{"errors": {"404": "Không tìm thấy"}}"#,
+                ),
+            ],
+        );
+        let store = TranslationStore::new(root.to_path_buf());
+        store.scan_and_load(&["locales".to_string()]);
+        let diagnose = |template: &str| {
+            let pattern = KeyPattern::from_template(template).unwrap();
+            I18nBackend::dynamic_key_diagnostic(&store, template, &pattern, Range::default())
+        };
+
+        let incomplete = diagnose("errors.${code}").unwrap();
+        assert_eq!(diagnostic_code(&incomplete), Some(INCOMPLETE_TRANSLATION));
+        assert_eq!(
+            incomplete.message,
+            "Keys matching 'errors.${code}' missing in some locales: errors.500 (vi)"
+        );
+        assert!(incomplete.data.is_none());
+
+        let unmatched = diagnose("status.${state}").unwrap();
+        assert_eq!(diagnostic_code(&unmatched), Some(UNMATCHED_DYNAMIC_KEY));
+    }
+
+    #[test]
+    fn test_insert_escapes_value() {
+        let content = "{\n  \"hello\": \"world\"\n}\n";
+        let result = I18nBackend::insert_key_into_json(content, "quote", "Say \"hi\"\nnow");
+        let (new_content, _, _) = result.unwrap();
+        let parsed: Value = serde_json::from_str(&new_content).unwrap();
+        assert_eq!(parsed["quote"], "Say \"hi\"\nnow");
+    }
+
+    #[test]
+    fn test_collect_translation_jobs_groups_by_locale() {
+        let root = TempDir::with_files(
+            "backend-translation-jobs",
+            &[
+                (
+                    "locales/en.json",
+                    r#"{"save": "Save", "raw": "_raw_", "items": "{n, plural, one{# item} other{# items}}"}"#,
+                ),
+                ("locales/vi.json", r#"{"other": "Khác"}"#),
+                ("locales/ja.json", r#"{"save": "保存"}"#),
+            ],
+        );
+
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["locales".to_string()]);
+
+        let keys = vec!["save".to_string(), "raw".to_string(), "items".to_string()];
+        let (jobs, skipped) = I18nBackend::collect_translation_jobs(&store, "en", &keys);
+
+        assert_eq!(skipped, 1);
+        assert_eq!(jobs.keys().collect::<Vec<_>>(), vec!["vi"]);
+        assert_eq!(jobs["vi"].len(), 1);
+        assert_eq!(jobs["vi"][0].key, "save");
+        assert_eq!(jobs["vi"][0].source_value, "Save");
+        assert_eq!(jobs["vi"][0].file_path, root.join("locales/vi.json"));
+    }
+
+    #[test]
+    fn test_json_key_range() {
+        // Minified, with escapes and non-ASCII text before the key
+        let content = r#"{"é":"x","b\u00e9":"y"}"#;
+        let spans = JsonSpans::parse(content);
+        assert_eq!(
+            I18nBackend::json_key_range(content, &spans, "bé", 0),
+            Range::new(Position::new(0, 9), Position::new(0, 18))
+        );
+        assert_eq!(
+            I18nBackend::json_key_range(content, &spans, "missing", 0),
+            Range::new(Position::new(0, 0), Position::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn test_only_config_files_are_checked_as_config() {
+        let root = Path::new("/work/app");
+        assert!(I18nBackend::is_config_file(
+            root,
+            &root.join(".zed/i18n.json")
+        ));
+        assert!(I18nBackend::is_config_file(
+            root,
+            &root.join(".i18n-ally.json")
+        ));
+        assert!(!I18nBackend::is_config_file(
+            root,
+            &root.join("pubspec.yaml")
+        ));
+        assert!(!I18nBackend::is_config_file(
+            root,
+            &root.join("package.json")
+        ));
+        assert!(!I18nBackend::is_config_file(
+            root,
+            &root.join("lib/.zed/i18n.json")
+        ));
+    }
+
+    #[test]
+    fn test_file_watchers_include_config_files() {
+        let root = Path::new("/work/apps/web");
+        let watchers =
+            I18nBackend::build_file_watchers(&["locales".to_string()], Some(root), false);
+        let patterns: Vec<String> = watchers
+            .into_iter()
+            .filter_map(|watcher| match watcher.glob_pattern {
+                GlobPattern::String(pattern) => Some(pattern),
+                GlobPattern::Relative(_) => None,
+            })
+            .collect();
+
+        assert!(patterns.contains(&"/work/apps/web/locales/**/*.json".to_string()));
+        assert!(patterns.contains(&"/work/apps/web/.zed/i18n.json".to_string()));
+        assert!(patterns.contains(&"/work/apps/web/.i18n-ally.json".to_string()));
+        assert!(patterns.contains(&"/work/apps/web/l10n.yaml".to_string()));
+        assert!(patterns.contains(&"/work/apps/web/package.json".to_string()));
+        assert!(patterns.contains(&"/work/apps/web/composer.json".to_string()));
+    }
+
+    #[test]
+    fn test_project_root_watchers_cover_nested_packages() {
+        let folder = Path::new("/work");
+        let patterns: Vec<String> = I18nBackend::project_root_watchers(folder, false)
+            .into_iter()
+            .filter_map(|watcher| match watcher.glob_pattern {
+                GlobPattern::String(pattern) => Some(pattern),
+                GlobPattern::Relative(_) => None,
+            })
+            .collect();
+
+        assert!(patterns.contains(&"/work/**/package.json".to_string()));
+        assert!(patterns.contains(&"/work/**/pubspec.yaml".to_string()));
+        assert!(patterns.contains(&"/work/**/.zed/i18n.json".to_string()));
+    }
+
     #[test]
     fn test_invalid_json_returns_none() {
         let content = "not valid json";
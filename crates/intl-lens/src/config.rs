@@ -1,8 +1,45 @@
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+use crate::i18n::{canonicalize, extract_locale_from_arb_filename};
+
+/// Config files, by precedence, relative to the project root.
+pub const CONFIG_FILES: [&str; 3] = [".i18n-ally.json", "i18n-ally.config.json", ".zed/i18n.json"];
+
+/// Files besides the config files that settings are read or detected from, relative to
+/// the project root.
+pub const DETECTION_FILES: [&str; 5] = [
+    "Gemfile",
+    "composer.json",
+    "l10n.yaml",
+    "package.json",
+    "pubspec.yaml",
+];
+
+/// npm packages whose presence makes a `package.json` directory an i18n project.
+const I18N_DEPENDENCIES: [&str; 18] = [
+    "@angular/localize",
+    "@formatjs/intl",
+    "@inlang/paraglide-js",
+    "@intlify/vue-i18n",
+    "@jsverse/transloco",
+    "@lingui/core",
+    "@ngx-translate/core",
+    "@nuxtjs/i18n",
+    "i18n-js",
+    "i18next",
+    "next-i18next",
+    "next-intl",
+    "react-i18next",
+    "react-intl",
+    "svelte-i18n",
+    "sveltekit-i18n",
+    "typesafe-i18n",
+    "vue-i18n",
+];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -19,8 +56,71 @@ pub struct I18nConfig {
     #[serde(default)]
     pub namespace_enabled: bool,
 
-    #[serde(default = "default_function_patterns")]
-    pub function_patterns: Vec<String>,
+    /// Regex patterns that replace the built-in presets (unless `presets` is also set).
+    #[serde(default)]
+    pub function_patterns: Option<Vec<String>>,
+
+    /// Regex patterns added on top of the built-in presets.
+    #[serde(default)]
+    pub extra_function_patterns: Vec<String>,
+
+    /// Framework presets to recognise, by name; every built-in preset when unset.
+    #[serde(default)]
+    pub presets: Option<Vec<String>>,
+
+    /// Function patterns that only apply to documents of some languages or paths.
+    #[serde(default)]
+    pub scoped_function_patterns: Vec<ScopedFunctionPatterns>,
+
+    /// Generated Dart localization classes besides `AppLocalizations` and `S`, whose
+    /// getters are message keys. Flutter's `l10n.yaml` `output-class` is added.
+    #[serde(default)]
+    pub localization_classes: Vec<String>,
+
+    /// How locale and namespace are read from the path of a translation file relative to
+    /// its locale directory, as in i18n-ally: `{locale}/{namespace}.json`.
+    #[serde(default)]
+    pub path_matcher: Option<String>,
+
+    /// Locales a missing message is read from at runtime.
+    #[serde(default)]
+    pub fallback_locale: Option<FallbackLocale>,
+
+    /// Whether a regional locale such as `pt-BR` falls back to its language, `pt`.
+    #[serde(default = "default_language_fallback")]
+    pub language_fallback: bool,
+
+    /// How many directories deep locale directories are scanned.
+    #[serde(default = "default_scan_depth")]
+    pub scan_depth: usize,
+
+    /// Globs of the translation files to load, relative to the workspace root; every
+    /// file in the locale directories when empty.
+    #[serde(default)]
+    pub include_files: Vec<String>,
+
+    /// Globs of files in the locale directories that are not translations, relative to
+    /// the workspace root.
+    #[serde(default = "default_exclude_files")]
+    pub exclude_files: Vec<String>,
+
+    /// Whether files ignored by `.gitignore` are left out of the scan.
+    #[serde(default = "default_respect_gitignore")]
+    pub respect_gitignore: bool,
+
+    #[serde(default)]
+    pub translation_provider: Option<TranslationProviderConfig>,
+}
+
+/// Fallback locales in any of the forms i18next's `fallbackLng` and vue-i18n's
+/// `fallbackLocale` take: one locale, a list, or chains by locale where `default`
+/// applies to every locale.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FallbackLocale {
+    One(String),
+    Many(Vec<String>),
+    Chains(HashMap<String, Vec<String>>),
 }
 
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
@@ -32,6 +132,66 @@ pub enum KeyStyle {
     Auto,
 }
 
+/// Regex patterns restricted to documents matching `languageIds` or `files`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopedFunctionPatterns {
+    /// LSP language identifiers, e.g. `typescriptreact` or `dart`.
+    #[serde(default)]
+    pub language_ids: Vec<String>,
+
+    /// Globs matched against the document path relative to the workspace root.
+    #[serde(default)]
+    pub files: Vec<String>,
+
+    pub patterns: Vec<String>,
+}
+
+/// Machine translation backend used to fill missing locales.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationProviderConfig {
+    pub kind: TranslationProviderKind,
+
+    /// Base URL of the service; each kind has a public default.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// Environment variable to read the API key from, so it stays out of the repository.
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+
+    /// Extra HTTP headers sent with every request.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TranslationProviderKind {
+    Deepl,
+    Google,
+    LibreTranslate,
+    /// Generic JSON endpoint: `{"texts", "source", "target"}` in, `{"translations"}` out.
+    Http,
+}
+
+impl TranslationProviderConfig {
+    pub fn resolve_api_key(&self) -> Option<String> {
+        if let Some(key) = self.api_key.as_ref().filter(|key| !key.is_empty()) {
+            return Some(key.clone());
+        }
+
+        self.api_key_env
+            .as_ref()
+            .and_then(|name| std::env::var(name).ok())
+            .filter(|key| !key.is_empty())
+    }
+}
+
 impl Default for I18nConfig {
     fn default() -> Self {
         Self {
@@ -39,48 +199,113 @@ impl Default for I18nConfig {
             source_locale: default_source_locale(),
             key_style: default_key_style(),
             namespace_enabled: false,
-            function_patterns: default_function_patterns(),
+            function_patterns: None,
+            extra_function_patterns: Vec::new(),
+            presets: None,
+            scoped_function_patterns: Vec::new(),
+            localization_classes: Vec::new(),
+            path_matcher: None,
+            fallback_locale: None,
+            language_fallback: default_language_fallback(),
+            scan_depth: default_scan_depth(),
+            include_files: Vec::new(),
+            exclude_files: default_exclude_files(),
+            respect_gitignore: default_respect_gitignore(),
+            translation_provider: None,
         }
     }
 }
 
 impl I18nConfig {
-    pub fn load_from_workspace(root: &Path) -> Self {
-        let config_paths = [
-            root.join(".i18n-ally.json"),
-            root.join("i18n-ally.config.json"),
-            root.join(".zed/i18n.json"),
-        ];
-
-        for config_path in config_paths {
-            if let Ok(content) = std::fs::read_to_string(&config_path) {
-                let raw_config = serde_json::from_str::<Value>(&content).ok();
-
-                if let Ok(mut config) = serde_json::from_str::<I18nConfig>(&content) {
-                    let has_locale_paths = raw_config
-                        .as_ref()
-                        .and_then(|value| value.as_object())
-                        .is_some_and(|object| {
-                            object.contains_key("localePaths")
-                                || object.contains_key("locale_paths")
-                        });
-
-                    if !has_locale_paths {
-                        config.add_detected_locale_paths(root);
+    /// The config of the project at `root`, in layers: the defaults, then what is
+    /// detected from the project, then its config file, then the editor `settings`. A
+    /// layer replaces the options it sets as a whole.
+    pub fn load(root: &Path, settings: &Value) -> Self {
+        let mut raw_config = match read_config_file(root) {
+            Some((config_path, object)) => {
+                tracing::info!("Loaded config from {:?}", config_path);
+                object
+            }
+            None => {
+                tracing::info!("Using default config");
+                Map::new()
+            }
+        };
+
+        // An invalid setting is skipped on its own, so the valid ones still apply
+        if let Some(settings) = settings.as_object().filter(|object| !object.is_empty()) {
+            for (key, value) in settings {
+                match setting_error(key, value) {
+                    Some(err) => tracing::warn!("Ignoring invalid editor setting {}", err),
+                    None => {
+                        raw_config.insert(key.clone(), value.clone());
                     }
-
-                    tracing::info!("Loaded config from {:?}", config_path);
-                    return config;
                 }
             }
+            tracing::info!("Applied editor settings to {:?}", root);
+        }
+
+        let mut config: I18nConfig =
+            serde_json::from_value(Value::Object(raw_config.clone())).unwrap_or_default();
+
+        let has_locale_paths =
+            raw_config.contains_key("localePaths") || raw_config.contains_key("locale_paths");
+        if !has_locale_paths {
+            config.add_detected_locale_paths(root);
         }
 
-        tracing::info!("Using default config");
-        let mut config = Self::default();
-        config.add_detected_locale_paths(root);
+        let has_source_locale =
+            raw_config.contains_key("sourceLocale") || raw_config.contains_key("source_locale");
+        config.add_l10n_settings(root, has_source_locale);
+        config.source_locale = canonicalize(&config.source_locale);
+
         config
     }
 
+    /// Why each invalid option of the editor `settings` is skipped by `load`, such as
+    /// ``"`sourceLocale`: invalid type: integer `5`, expected a string"``.
+    pub fn invalid_settings(settings: &Value) -> Vec<String> {
+        settings
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, value)| setting_error(key, value))
+            .collect()
+    }
+
+    /// Whether `dir` is the root of a project with its own translations: it has a config
+    /// file, a `package.json` depending on an i18n library, a `pubspec.yaml` or a
+    /// `composer.json`.
+    pub fn is_project_root(dir: &Path) -> bool {
+        CONFIG_FILES.iter().any(|name| dir.join(name).is_file())
+            || dir.join("pubspec.yaml").is_file()
+            || dir.join("composer.json").is_file()
+            || read_json(&dir.join("package.json")).is_some_and(|value| {
+                I18N_DEPENDENCIES.iter().any(|dependency| {
+                    json_has_dependency(&value, dependency, &["dependencies", "devDependencies"])
+                })
+            })
+    }
+
+    /// Take the template locale and generated class name from Flutter's `l10n.yaml`.
+    fn add_l10n_settings(&mut self, root: &Path, has_source_locale: bool) {
+        let Some(l10n) = parse_l10n_yaml(root) else {
+            return;
+        };
+
+        if !has_source_locale {
+            if let Some(locale) = l10n.template_arb_file.as_deref().and_then(arb_locale) {
+                self.source_locale = locale;
+            }
+        }
+
+        if let Some(class) = l10n.output_class {
+            if !self.localization_classes.contains(&class) {
+                self.localization_classes.push(class);
+            }
+        }
+    }
+
     fn add_detected_locale_paths(&mut self, root: &Path) {
         let detected_paths = detect_framework_locale_paths(root);
         if detected_paths.is_empty() {
@@ -115,49 +340,30 @@ fn default_key_style() -> KeyStyle {
     KeyStyle::Auto
 }
 
-fn default_function_patterns() -> Vec<String> {
+fn default_language_fallback() -> bool {
+    true
+}
+
+/// Deep enough for Laravel package translations: `vendor/<package>/<locale>/<file>`.
+fn default_scan_depth() -> usize {
+    5
+}
+
+fn default_exclude_files() -> Vec<String> {
     vec![
-        // JavaScript/TypeScript patterns
-        // Match t() but not .post(), .get(), .put(), .delete(), etc.
-        r#"(?:^|[^\w.])t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"i18n\.t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"useTranslation\s*\(\s*\)\s*.*?t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"\$t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"\$tc\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"\$te\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"useI18n\s*\(\s*\)\s*.*?\.t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"formatMessage\s*\(\s*\{\s*id:\s*["']([^"']+)["']"#.to_string(),
-        // Svelte patterns (svelte-i18n)
-        r#"\$_\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"\$format\s*\(\s*["']([^"']+)["']"#.to_string(),
-        // Angular patterns
-        r#"translateService\.(?:instant|get|stream)\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"translocoService\.(?:translate|selectTranslate)\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"["']([^"']+)["']\s*\|\s*(?:translate|transloco)\b"#.to_string(),
-        // PHP/Laravel patterns
-        r#"__\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"trans(?:_choice)?\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"Lang::(?:get|choice)\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"@lang\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"@choice\s*\(\s*["']([^"']+)["']"#.to_string(),
-        // Flutter/Dart patterns - easy_localization
-        r#"['"]([^'"]+)['"]\s*\.tr\("#.to_string(),
-        r#"['"]([^'"]+)['"]\s*\.tr\(\)"#.to_string(),
-        r#"(?:^|[^\w.])tr\(\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"context\.tr\(\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"['"]([^'"]+)['"]\s*\.plural\("#.to_string(),
-        // Flutter/Dart patterns - flutter_i18n
-        r#"FlutterI18n\.translate\([^,]+,\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"FlutterI18n\.plural\([^,]+,\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"I18nText\(\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"I18nPlural\(\s*['"]([^'"]+)['"]"#.to_string(),
-        // Flutter/Dart patterns - GetX
-        r#"['"]([^'"]+)['"]\s*\.tr(?:\s|$|\)|,)"#.to_string(),
-        r#"['"]([^'"]+)['"]\s*\.trParams\("#.to_string(),
-        r#"['"]([^'"]+)['"]\s*\.trPlural\("#.to_string(),
+        "**/node_modules/**".to_string(),
+        "**/package.json".to_string(),
+        "**/package-lock.json".to_string(),
+        "**/tsconfig*.json".to_string(),
+        "**/jsconfig.json".to_string(),
+        "**/composer.json".to_string(),
     ]
 }
 
+fn default_respect_gitignore() -> bool {
+    true
+}
+
 fn detect_framework_locale_paths(root: &Path) -> Vec<String> {
     let mut paths = Vec::new();
 
@@ -170,9 +376,13 @@ fn detect_framework_locale_paths(root: &Path) -> Vec<String> {
         paths.push("lang".to_string());
     }
 
+    if is_rails_project(root) {
+        paths.push("config/locales".to_string());
+    }
+
     if is_flutter_project(root) {
         // Check l10n.yaml for custom arb-dir
-        if let Some(arb_dir) = parse_l10n_yaml(root) {
+        if let Some(arb_dir) = parse_l10n_yaml(root).and_then(|l10n| l10n.arb_dir) {
             paths.push(arb_dir);
         }
         // Default Flutter locale paths
@@ -190,6 +400,22 @@ fn detect_framework_locale_paths(root: &Path) -> Vec<String> {
         paths.push("public/locales".to_string());
     }
 
+    if is_next_intl_project(root) {
+        paths.push("messages".to_string());
+    }
+
+    if is_lingui_project(root) {
+        paths.extend(lingui_catalog_dirs(root));
+        paths.push("src/locales".to_string());
+        paths.push("src/locale".to_string());
+        paths.push("locales".to_string());
+    }
+
+    if is_paraglide_project(root) {
+        paths.extend(paraglide_message_dirs(root));
+        paths.push("messages".to_string());
+    }
+
     if is_svelte_project(root) {
         paths.push("src/lib/i18n".to_string());
         paths.push("src/lib/locales".to_string());
@@ -229,6 +455,32 @@ fn is_laravel_project(root: &Path) -> bool {
         || json_has_name(&value, "laravel/laravel")
 }
 
+/// Why `value` is not valid for the config option `key`, if it is not.
+fn setting_error(key: &str, value: &Value) -> Option<String> {
+    let mut option = Map::new();
+    option.insert(key.to_string(), value.clone());
+    serde_json::from_value::<I18nConfig>(Value::Object(option))
+        .err()
+        .map(|err| format!("`{}`: {}", key, err))
+}
+
+/// The first config file of the project at `root` that is a valid config, as a JSON
+/// object.
+fn read_config_file(root: &Path) -> Option<(PathBuf, Map<String, Value>)> {
+    CONFIG_FILES.iter().find_map(|name| {
+        let config_path = root.join(name);
+        let content = std::fs::read_to_string(&config_path).ok()?;
+        if let Err(err) = serde_json::from_str::<I18nConfig>(&content) {
+            tracing::warn!("Ignoring invalid config {:?}: {}", config_path, err);
+            return None;
+        }
+        match serde_json::from_str::<Value>(&content).ok()? {
+            Value::Object(object) => Some((config_path, object)),
+            _ => None,
+        }
+    })
+}
+
 fn read_json(path: &Path) -> Option<Value> {
     let content = std::fs::read_to_string(path).ok()?;
     serde_json::from_str::<Value>(&content).ok()
@@ -247,6 +499,11 @@ fn json_has_name(value: &Value, name: &str) -> bool {
     value.get("name").and_then(|v| v.as_str()) == Some(name)
 }
 
+fn is_rails_project(root: &Path) -> bool {
+    std::fs::read_to_string(root.join("Gemfile"))
+        .is_ok_and(|content| content.contains("\"rails\"") || content.contains("'rails'"))
+}
+
 fn is_flutter_project(root: &Path) -> bool {
     let pubspec = root.join("pubspec.yaml");
     let Ok(content) = std::fs::read_to_string(&pubspec) else {
@@ -257,15 +514,37 @@ fn is_flutter_project(root: &Path) -> bool {
     content.contains("flutter:") && content.contains("sdk: flutter")
 }
 
-fn parse_l10n_yaml(root: &Path) -> Option<String> {
+/// The settings of Flutter's `l10n.yaml` that affect where messages are and how they
+/// are used.
+#[derive(Debug, Default, PartialEq)]
+struct L10nYaml {
+    arb_dir: Option<String>,
+    template_arb_file: Option<String>,
+    output_class: Option<String>,
+}
+
+fn parse_l10n_yaml(root: &Path) -> Option<L10nYaml> {
     let l10n_yaml = root.join("l10n.yaml");
     let content = std::fs::read_to_string(&l10n_yaml).ok()?;
 
-    // Parse l10n.yaml to find arb-dir
     let yaml: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
-    yaml.get("arb-dir")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+    let field = |name: &str| {
+        yaml.get(name)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+    Some(L10nYaml {
+        arb_dir: field("arb-dir"),
+        template_arb_file: field("template-arb-file"),
+        output_class: field("output-class"),
+    })
+}
+
+/// The locale of an ARB file named like `app_en.arb` or `my_app_pt_BR.arb`, read the
+/// same way the store reads the locale of the ARB files it loads.
+fn arb_locale(file_name: &str) -> Option<String> {
+    let stem = file_name.strip_suffix(".arb").unwrap_or(file_name);
+    extract_locale_from_arb_filename(stem)
 }
 
 fn is_svelte_project(root: &Path) -> bool {
@@ -309,3 +588,212 @@ fn is_vue_project(root: &Path) -> bool {
         || root.join("vite.config.ts").exists()
         || root.join("nuxt.config.js").exists()
 }
+
+fn is_next_intl_project(root: &Path) -> bool {
+    read_json(&root.join("package.json")).is_some_and(|value| {
+        json_has_dependency(&value, "next-intl", &["dependencies", "devDependencies"])
+    })
+}
+
+fn is_lingui_project(root: &Path) -> bool {
+    let has_dependency = read_json(&root.join("package.json")).is_some_and(|value| {
+        [
+            "@lingui/core",
+            "@lingui/react",
+            "@lingui/macro",
+            "@lingui/cli",
+        ]
+        .iter()
+        .any(|dependency| {
+            json_has_dependency(&value, dependency, &["dependencies", "devDependencies"])
+        })
+    });
+
+    has_dependency || lingui_config(root).is_some()
+}
+
+fn lingui_config(root: &Path) -> Option<String> {
+    [
+        "lingui.config.js",
+        "lingui.config.ts",
+        "lingui.config.mjs",
+        ".linguirc",
+    ]
+    .iter()
+    .find_map(|name| std::fs::read_to_string(root.join(name)).ok())
+}
+
+/// Catalog directories from `catalogs[].path` in the Lingui config, such as
+/// `<rootDir>/src/locales/{locale}/messages`.
+fn lingui_catalog_dirs(root: &Path) -> Vec<String> {
+    let Some(content) = lingui_config(root) else {
+        return Vec::new();
+    };
+
+    content
+        .split(['"', '\'', '`'])
+        .filter(|part| part.contains("{locale}"))
+        .filter_map(|path| path_pattern_dir(&path.replace("<rootDir>/", ""), "{locale}"))
+        .collect()
+}
+
+fn is_paraglide_project(root: &Path) -> bool {
+    let has_dependency = read_json(&root.join("package.json")).is_some_and(|value| {
+        [
+            "@inlang/paraglide-js",
+            "@inlang/paraglide-next",
+            "@inlang/paraglide-sveltekit",
+        ]
+        .iter()
+        .any(|dependency| {
+            json_has_dependency(&value, dependency, &["dependencies", "devDependencies"])
+        })
+    });
+
+    has_dependency || root.join("project.inlang").exists()
+}
+
+/// Message directories from the inlang message format plugin's `pathPattern`, such as
+/// `./messages/{languageTag}.json`.
+fn paraglide_message_dirs(root: &Path) -> Vec<String> {
+    let Some(settings) = read_json(&root.join("project.inlang/settings.json")) else {
+        return Vec::new();
+    };
+
+    let Some(path_pattern) = settings
+        .get("plugin.inlang.messageFormat")
+        .and_then(|plugin| plugin.get("pathPattern"))
+    else {
+        return Vec::new();
+    };
+
+    let patterns: Vec<&str> = match path_pattern {
+        Value::String(pattern) => vec![pattern.as_str()],
+        Value::Array(patterns) => patterns.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+
+    patterns
+        .into_iter()
+        .filter_map(|pattern| {
+            path_pattern_dir(pattern, "{languageTag}")
+                .or_else(|| path_pattern_dir(pattern, "{locale}"))
+        })
+        .collect()
+}
+
+/// The directory holding every locale of a path pattern: the part before the
+/// placeholder, up to its last `/`.
+fn path_pattern_dir(pattern: &str, placeholder: &str) -> Option<String> {
+    let (before, _) = pattern.split_once(placeholder)?;
+    let (dir, _) = before.rsplit_once('/')?;
+    let dir = dir.trim_start_matches("./").trim_matches('/');
+    (!dir.is_empty() && dir != ".").then(|| dir.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_path_pattern_dir() {
+        assert_eq!(
+            path_pattern_dir("./messages/{languageTag}.json", "{languageTag}"),
+            Some("messages".to_string())
+        );
+        assert_eq!(
+            path_pattern_dir("src/locales/{locale}/messages", "{locale}"),
+            Some("src/locales".to_string())
+        );
+        assert_eq!(path_pattern_dir("{locale}.po", "{locale}"), None);
+        assert_eq!(path_pattern_dir("./{locale}.po", "{locale}"), None);
+    }
+
+    #[test]
+    fn test_detects_lingui_and_paraglide_locale_paths() {
+        let root = TempDir::new("config-detection");
+        std::fs::create_dir_all(root.join("project.inlang")).unwrap();
+        std::fs::create_dir_all(root.join("i18n/catalogs/en")).unwrap();
+        std::fs::create_dir_all(root.join("content/messages")).unwrap();
+        std::fs::write(
+            root.join("lingui.config.js"),
+            r#"export default { catalogs: [{ path: "<rootDir>/i18n/catalogs/{locale}/messages" }] };"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("project.inlang/settings.json"),
+            r#"{ "plugin.inlang.messageFormat": { "pathPattern": "./content/messages/{languageTag}.json" } }"#,
+        )
+        .unwrap();
+
+        let paths = detect_framework_locale_paths(&root);
+        assert!(paths.contains(&"i18n/catalogs".to_string()));
+        assert!(paths.contains(&"content/messages".to_string()));
+    }
+
+    #[test]
+    fn test_l10n_yaml_settings() {
+        let root = TempDir::new("config-l10n");
+        std::fs::create_dir_all(root.join("lib/i18n")).unwrap();
+        std::fs::write(
+            root.join("pubspec.yaml"),
+            "flutter:\n  generate: true\ndependencies:\n  flutter:\n    sdk: flutter\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("l10n.yaml"),
+            "arb-dir: lib/i18n\ntemplate-arb-file: app_de.arb\noutput-class: Strings\n",
+        )
+        .unwrap();
+
+        let config = I18nConfig::load(&root, &Value::Null);
+        assert!(config.locale_paths.contains(&"lib/i18n".to_string()));
+        assert_eq!(config.source_locale, "de");
+        assert_eq!(config.localization_classes, vec!["Strings".to_string()]);
+
+        std::fs::write(root.join(".i18n-ally.json"), r#"{ "sourceLocale": "en" }"#).unwrap();
+        assert_eq!(I18nConfig::load(&root, &Value::Null).source_locale, "en");
+    }
+
+    #[test]
+    fn test_arb_locale_with_underscored_names() {
+        assert_eq!(arb_locale("app_en.arb"), Some("en".to_string()));
+        assert_eq!(arb_locale("my_app_en.arb"), Some("en".to_string()));
+        assert_eq!(
+            arb_locale("app_localizations_en.arb"),
+            Some("en".to_string())
+        );
+        assert_eq!(arb_locale("my_app_pt_BR.arb"), Some("pt_BR".to_string()));
+        assert_eq!(arb_locale("strings.arb"), None);
+    }
+
+    #[test]
+    fn test_editor_settings_layer() {
+        let root = TempDir::new("config-settings");
+        std::fs::create_dir_all(root.join(".zed")).unwrap();
+        std::fs::write(
+            root.join(".zed/i18n.json"),
+            r#"{ "localePaths": ["lang"], "sourceLocale": "de", "keyStyle": "flat" }"#,
+        )
+        .unwrap();
+
+        let settings = serde_json::json!({ "sourceLocale": "pt_br", "scanDepth": 2 });
+        let config = I18nConfig::load(&root, &settings);
+        assert_eq!(config.locale_paths, vec!["lang".to_string()]);
+        assert_eq!(config.source_locale, "pt-BR");
+        assert_eq!(config.scan_depth, 2);
+        assert!(matches!(config.key_style, KeyStyle::Flat));
+
+        // An invalid setting is skipped without taking the valid ones with it
+        let mixed = serde_json::json!({ "sourceLocale": 5, "scanDepth": 3 });
+        let config = I18nConfig::load(&root, &mixed);
+        assert_eq!(config.source_locale, "de");
+        assert_eq!(config.scan_depth, 3);
+        assert_eq!(
+            I18nConfig::invalid_settings(&mixed),
+            vec!["`sourceLocale`: invalid type: integer `5`, expected a string".to_string()]
+        );
+        assert!(I18nConfig::invalid_settings(&settings).is_empty());
+    }
+}
@@ -0,0 +1,498 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde_json::Value;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+
+use crate::config::{
+    I18nConfig, ScopedFunctionPatterns, TranslationProviderConfig, TranslationProviderKind,
+};
+use crate::diagnostics::INVALID_CONFIG;
+
+/// Config file whose every field is the server's own, so unknown fields are mistakes.
+/// The i18n-ally files also hold i18n-ally's settings.
+pub const OWN_CONFIG_FILE: &str = ".zed/i18n.json";
+
+/// Check a config file: JSON syntax, option types, unknown fields when `strict`, and the
+/// function patterns, which must compile and capture the key in group 1.
+pub fn check_config(content: &str, strict: bool) -> Vec<Diagnostic> {
+    let value = match serde_json::from_str::<Value>(content) {
+        Ok(value) => value,
+        Err(err) => {
+            let message = format!("Invalid JSON: {}", error_message(&err));
+            return vec![error_at(content, &err, message)];
+        }
+    };
+    let spans = JsonSpans::parse(content);
+    let mut diagnostics = Vec::new();
+
+    if let Err(err) = serde_json::from_str::<I18nConfig>(content) {
+        let message = format!(
+            "{}; the config file is ignored",
+            capitalize(&error_message(&err))
+        );
+        diagnostics.push(error_at(content, &err, message));
+    }
+
+    if strict {
+        check_fields(content, &value, &spans, &mut diagnostics);
+    }
+
+    for (pointer, pattern) in function_patterns(&value) {
+        let Some(&(start, end)) = spans.strings.get(&pointer) else {
+            continue;
+        };
+        diagnostics.extend(check_pattern(content, start, end, &pattern));
+    }
+
+    diagnostics
+}
+
+/// Warn about fields the config does not have, suggesting the one meant when only case
+/// or underscores differ.
+fn check_fields(content: &str, value: &Value, spans: &JsonSpans, out: &mut Vec<Diagnostic>) {
+    let scoped = ScopedFunctionPatterns {
+        language_ids: Vec::new(),
+        files: Vec::new(),
+        patterns: Vec::new(),
+    };
+    let provider = TranslationProviderConfig {
+        kind: TranslationProviderKind::Http,
+        endpoint: None,
+        api_key: None,
+        api_key_env: None,
+        headers: HashMap::new(),
+    };
+
+    let mut objects = vec![(String::new(), value, field_names(&I18nConfig::default()))];
+    if let Some(Value::Array(items)) = value.get("scopedFunctionPatterns") {
+        for (index, item) in items.iter().enumerate() {
+            let pointer = format!("/scopedFunctionPatterns/{}", index);
+            objects.push((pointer, item, field_names(&scoped)));
+        }
+    }
+    if let Some(item) = value.get("translationProvider") {
+        let pointer = "/translationProvider".to_string();
+        objects.push((pointer, item, field_names(&provider)));
+    }
+
+    for (pointer, object, known) in objects {
+        let Value::Object(object) = object else {
+            continue;
+        };
+        for field in object.keys() {
+            if known.contains(field) || (pointer.is_empty() && field == "$schema") {
+                continue;
+            }
+            let Some(&(start, end)) = spans.keys.get(&format!("{}/{}", pointer, field)) else {
+                continue;
+            };
+            let normalized = normalize_field(field);
+            let message = match known
+                .iter()
+                .find(|name| normalize_field(name) == normalized)
+            {
+                Some(name) => format!("Unknown field '{}'; did you mean '{}'?", field, name),
+                None => format!("Unknown field '{}'", field),
+            };
+            let range = range_of(content, start, end);
+            out.push(diagnostic(range, DiagnosticSeverity::WARNING, message));
+        }
+    }
+}
+
+fn field_names(value: &impl serde::Serialize) -> Vec<String> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(object)) => object.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn normalize_field(field: &str) -> String {
+    field.replace(['_', '-'], "").to_lowercase()
+}
+
+/// The function patterns of a config with the JSON pointer of each.
+fn function_patterns(value: &Value) -> Vec<(String, String)> {
+    let mut patterns = Vec::new();
+    let mut add = |pointer: String, list: Option<&Value>| {
+        let Some(Value::Array(items)) = list else {
+            return;
+        };
+        for (index, item) in items.iter().enumerate() {
+            if let Value::String(pattern) = item {
+                patterns.push((format!("{}/{}", pointer, index), pattern.clone()));
+            }
+        }
+    };
+
+    add(
+        "/functionPatterns".to_string(),
+        value.get("functionPatterns"),
+    );
+    add(
+        "/extraFunctionPatterns".to_string(),
+        value.get("extraFunctionPatterns"),
+    );
+    if let Some(Value::Array(scoped)) = value.get("scopedFunctionPatterns") {
+        for (index, item) in scoped.iter().enumerate() {
+            let pointer = format!("/scopedFunctionPatterns/{}/patterns", index);
+            add(pointer, item.get("patterns"));
+        }
+    }
+    patterns
+}
+
+/// The problem with the pattern in the string literal at `start..end` of the source:
+/// a syntax error, underlined where the regex parser stopped, or a missing group 1.
+fn check_pattern(content: &str, start: usize, end: usize, pattern: &str) -> Option<Diagnostic> {
+    let literal = &content[start + 1..end - 1];
+    let whole = range_of(content, start, end);
+
+    if let Err(err) = regex_syntax::Parser::new().parse(pattern) {
+        let located = match &err {
+            regex_syntax::Error::Parse(err) => Some((err.kind().to_string(), *err.span())),
+            regex_syntax::Error::Translate(err) => Some((err.kind().to_string(), *err.span())),
+            _ => None,
+        };
+        let (message, range) = match located {
+            Some((kind, span)) => {
+                let from = start + 1 + source_offset(literal, span.start.offset);
+                let to = start + 1 + source_offset(literal, span.end.offset);
+                let to = if to > from {
+                    to
+                } else {
+                    next_char_boundary(content, from).min(end - 1)
+                };
+                (kind, range_of(content, from, to))
+            }
+            None => (err.to_string(), whole),
+        };
+        let message = format!("Invalid regex: {}", message);
+        return Some(diagnostic(range, DiagnosticSeverity::ERROR, message));
+    }
+
+    match Regex::new(pattern) {
+        Err(err) => {
+            let message = format!("Invalid regex: {}", err);
+            Some(diagnostic(whole, DiagnosticSeverity::ERROR, message))
+        }
+        Ok(regex) if regex.captures_len() < 2 => {
+            let message =
+                "Pattern has no capture group 1, so it never finds a key; capture the key with (...)"
+                    .to_string();
+            Some(diagnostic(whole, DiagnosticSeverity::WARNING, message))
+        }
+        Ok(_) => None,
+    }
+}
+
+/// Offset in the raw JSON string literal `raw` of byte `offset` of its decoded value.
+fn source_offset(raw: &str, offset: usize) -> usize {
+    let bytes = raw.as_bytes();
+    let mut index = 0;
+    let mut decoded = 0;
+    while index < bytes.len() && decoded < offset {
+        if bytes[index] != b'\\' {
+            let len = raw[index..].chars().next().map_or(1, char::len_utf8);
+            index += len;
+            decoded += len;
+            continue;
+        }
+        if bytes.get(index + 1) != Some(&b'u') {
+            index += 2;
+            decoded += 1;
+            continue;
+        }
+        let unit = raw
+            .get(index + 2..index + 6)
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .unwrap_or(0);
+        let is_pair = (0xD800..0xDC00).contains(&unit) && raw[index + 6..].starts_with("\\u");
+        if is_pair {
+            index += 12;
+            decoded += 4;
+        } else {
+            index += 6;
+            decoded += char::from_u32(unit).map_or(3, char::len_utf8);
+        }
+    }
+    index.min(raw.len())
+}
+
+/// Source spans of the keys and string values of a JSON document, by JSON pointer.
+#[derive(Debug, Default)]
+pub(crate) struct JsonSpans {
+    pub keys: HashMap<String, (usize, usize)>,
+    pub strings: HashMap<String, (usize, usize)>,
+}
+
+impl JsonSpans {
+    /// Walk a document already known to be valid JSON.
+    pub fn parse(content: &str) -> Self {
+        let mut spans = Self::default();
+        let mut walker = Walker {
+            bytes: content.as_bytes(),
+            index: 0,
+        };
+        walker.value(String::new(), &mut spans);
+        spans
+    }
+}
+
+struct Walker<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl Walker<'_> {
+    fn value(&mut self, pointer: String, spans: &mut JsonSpans) {
+        self.skip_whitespace();
+        match self.bytes.get(self.index) {
+            Some(b'{') => {
+                self.index += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.bytes.get(self.index) {
+                        Some(b'"') => {}
+                        Some(b',') => {
+                            self.index += 1;
+                            continue;
+                        }
+                        _ => {
+                            self.index += 1;
+                            break;
+                        }
+                    }
+                    let (start, end) = self.string();
+                    let key = std::str::from_utf8(&self.bytes[start..end])
+                        .ok()
+                        .and_then(|literal| serde_json::from_str::<String>(literal).ok())
+                        .unwrap_or_default();
+                    let child = format!("{}/{}", pointer, key);
+                    spans.keys.insert(child.clone(), (start, end));
+                    self.skip_whitespace();
+                    self.index += 1;
+                    self.value(child, spans);
+                }
+            }
+            Some(b'[') => {
+                self.index += 1;
+                let mut position = 0;
+                loop {
+                    self.skip_whitespace();
+                    match self.bytes.get(self.index) {
+                        Some(b']') | None => {
+                            self.index += 1;
+                            break;
+                        }
+                        Some(b',') => self.index += 1,
+                        _ => {
+                            self.value(format!("{}/{}", pointer, position), spans);
+                            position += 1;
+                        }
+                    }
+                }
+            }
+            Some(b'"') => {
+                let span = self.string();
+                spans.strings.insert(pointer, span);
+            }
+            _ => {
+                while self
+                    .bytes
+                    .get(self.index)
+                    .is_some_and(|b| !matches!(b, b',' | b'}' | b']') && !b.is_ascii_whitespace())
+                {
+                    self.index += 1;
+                }
+            }
+        }
+    }
+
+    /// Skip a string literal, returning its span including the quotes.
+    fn string(&mut self) -> (usize, usize) {
+        let start = self.index;
+        self.index += 1;
+        while let Some(&byte) = self.bytes.get(self.index) {
+            self.index += if byte == b'\\' { 2 } else { 1 };
+            if byte == b'"' {
+                break;
+            }
+        }
+        (start, self.index.min(self.bytes.len()))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.index)
+            .is_some_and(|b| b.is_ascii_whitespace())
+        {
+            self.index += 1;
+        }
+    }
+}
+
+/// A serde_json error message without the position, which the range shows.
+fn error_message(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    let suffix = format!(" at line {} column {}", err.line(), err.column());
+    message
+        .strip_suffix(&suffix)
+        .unwrap_or(&message)
+        .to_string()
+}
+
+fn capitalize(message: &str) -> String {
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// An error diagnostic on the character serde_json stopped at.
+fn error_at(content: &str, err: &serde_json::Error, message: String) -> Diagnostic {
+    let line_start: usize = content
+        .split_inclusive('\n')
+        .take(err.line().saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let line_end = content[line_start..]
+        .find('\n')
+        .map_or(content.len(), |end| line_start + end);
+    let offset = (line_start + err.column().saturating_sub(1)).min(line_end);
+    let offset = floor_char_boundary(content, offset);
+    let end = next_char_boundary(content, offset).min(line_end);
+    diagnostic(
+        range_of(content, offset, end),
+        DiagnosticSeverity::ERROR,
+        message,
+    )
+}
+
+fn diagnostic(range: Range, severity: DiagnosticSeverity, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(INVALID_CONFIG.to_string())),
+        source: Some("i18n".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// LSP range of the byte range `start..end`.
+pub(crate) fn range_of(content: &str, start: usize, end: usize) -> Range {
+    Range {
+        start: position_of(content, start),
+        end: position_of(content, end),
+    }
+}
+
+/// LSP position, in UTF-16 code units, of a byte offset.
+fn position_of(content: &str, offset: usize) -> Position {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].encode_utf16().count() as u32,
+    }
+}
+
+fn floor_char_boundary(content: &str, mut offset: usize) -> usize {
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+fn next_char_boundary(content: &str, offset: usize) -> usize {
+    content[offset..]
+        .chars()
+        .next()
+        .map_or(offset, |c| offset + c.len_utf8())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_check_config() {
+        let content = r#"{
+  "$schema": "./schema.json",
+  "locale_paths": ["lang"],
+  "functionPatterns": ["t\\(['\"]([^'\"]+", "i18n\\.t\\("],
+  "scopedFunctionPatterns": [{"languageIds": ["dart"], "pattern": [], "patterns": ["tr\\((\\w+)\\)"]}]
+}"#;
+        let diagnostics = check_config(content, true);
+        assert_eq!(
+            messages(&diagnostics),
+            vec![
+                "Unknown field 'locale_paths'; did you mean 'localePaths'?",
+                "Unknown field 'pattern'",
+                "Invalid regex: unclosed group",
+                "Pattern has no capture group 1, so it never finds a key; capture the key with (...)",
+            ]
+        );
+
+        // The unclosed group is underlined from its `(` in the source, past the escapes
+        let unclosed = &diagnostics[2];
+        assert_eq!(unclosed.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(unclosed.range.start, Position::new(3, 33));
+        assert_eq!(unclosed.range.end, Position::new(3, 34));
+        let missing_group = &diagnostics[3];
+        assert_eq!(missing_group.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(missing_group.range.start, Position::new(3, 44));
+        assert_eq!(missing_group.range.end, Position::new(3, 57));
+
+        // i18n-ally's files hold its own settings too
+        assert_eq!(check_config(content, false).len(), 2);
+    }
+
+    #[test]
+    fn test_check_config_errors() {
+        let diagnostics = check_config("{\n  \"localePaths\": [\"lang\",]\n}", true);
+        assert_eq!(messages(&diagnostics), vec!["Invalid JSON: trailing comma"]);
+        assert_eq!(diagnostics[0].range.start, Position::new(1, 25));
+
+        let diagnostics = check_config("{\n  \"scanDepth\": \"deep\"\n}", true);
+        assert_eq!(
+            messages(&diagnostics),
+            vec!["Invalid type: string \"deep\", expected usize; the config file is ignored"]
+        );
+        assert_eq!(diagnostics[0].range.start.line, 1);
+
+        assert!(check_config(r#"{"functionPatterns": ["t\\((\\w+)\\)"]}"#, true).is_empty());
+    }
+
+    #[test]
+    fn test_schema_covers_config() {
+        let schema: Value = serde_json::from_str(include_str!(
+            "../../intl-lens-extension/schemas/i18n.schema.json"
+        ))
+        .unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        for field in field_names(&I18nConfig::default()) {
+            assert!(
+                properties.contains_key(&field),
+                "'{}' is not in the schema",
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn test_source_offset() {
+        assert_eq!(source_offset(r"a\\(b", 2), 3);
+        assert_eq!(source_offset(r#"\"x"#, 1), 2);
+        assert_eq!(source_offset(r"\u00e9(", 2), 6);
+        assert_eq!(source_offset(r"\ud83d\ude00(", 4), 12);
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tower_lsp::lsp_types::{Diagnostic, NumberOrString};
+
+pub const MISSING_TRANSLATION: &str = "missing-translation";
+pub const RAW_TRANSLATION: &str = "raw-translation";
+pub const INCOMPLETE_TRANSLATION: &str = "incomplete-translation";
+pub const MACHINE_TRANSLATED: &str = "machine-translated";
+pub const UNMATCHED_DYNAMIC_KEY: &str = "unmatched-dynamic-key";
+pub const ARB_NOT_IN_TEMPLATE: &str = "arb-not-in-template";
+pub const ARB_PLACEHOLDER: &str = "arb-placeholder";
+pub const INVALID_CONFIG: &str = "invalid-config";
+
+/// Structured payload attached to key diagnostics via `Diagnostic::data`, so code
+/// actions never have to recover the key from the human-readable message.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyDiagnosticData {
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_locales: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_candidates: Vec<FileCandidate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+    /// Prefix of the scoped translation function the key was written under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_prefix: Option<String>,
+}
+
+/// A writable locale file the key could be created in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileCandidate {
+    pub locale: String,
+    pub path: PathBuf,
+}
+
+impl KeyDiagnosticData {
+    pub fn to_value(&self) -> Option<Value> {
+        serde_json::to_value(self).ok()
+    }
+
+    pub fn from_diagnostic(diagnostic: &Diagnostic) -> Option<Self> {
+        let data = diagnostic.data.clone()?;
+        serde_json::from_value(data).ok()
+    }
+
+    /// Candidate files that belong to `locale`.
+    pub fn files_for_locale(&self, locale: &str) -> Vec<&FileCandidate> {
+        self.file_candidates
+            .iter()
+            .filter(|candidate| candidate.locale == locale)
+            .collect()
+    }
+}
+
+pub fn diagnostic_code(diagnostic: &Diagnostic) -> Option<&str> {
+    match diagnostic.code.as_ref()? {
+        NumberOrString::String(code) => Some(code.as_str()),
+        NumberOrString::Number(_) => None,
+    }
+}
+
+/// Split an i18next-style `namespace:key` reference into its parts.
+pub fn split_namespace(key: &str) -> (Option<&str>, &str) {
+    match key.split_once(':') {
+        Some((namespace, rest)) if !namespace.is_empty() && !rest.is_empty() => {
+            (Some(namespace), rest)
+        }
+        _ => (None, key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_round_trips_through_diagnostic() {
+        let data = KeyDiagnosticData {
+            key: "common.save".to_string(),
+            namespace: Some("common".to_string()),
+            missing_locales: vec!["vi".to_string()],
+            file_candidates: vec![FileCandidate {
+                locale: "vi".to_string(),
+                path: PathBuf::from("/locales/vi/common.json"),
+            }],
+            suggestions: vec![],
+            key_prefix: Some("settings".to_string()),
+        };
+
+        let diagnostic = Diagnostic {
+            data: data.to_value(),
+            ..Default::default()
+        };
+
+        assert_eq!(KeyDiagnosticData::from_diagnostic(&diagnostic), Some(data));
+    }
+
+    #[test]
+    fn test_from_diagnostic_without_data() {
+        let diagnostic = Diagnostic::default();
+        assert_eq!(KeyDiagnosticData::from_diagnostic(&diagnostic), None);
+    }
+
+    #[test]
+    fn test_split_namespace() {
+        assert_eq!(
+            split_namespace("auth:login.title"),
+            (Some("auth"), "login.title")
+        );
+        assert_eq!(split_namespace("login.title"), (None, "login.title"));
+        assert_eq!(split_namespace(":title"), (None, ":title"));
+    }
+}
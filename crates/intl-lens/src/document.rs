@@ -7,6 +7,8 @@ pub struct DocumentStore {
 pub struct Document {
     pub content: String,
     pub version: i32,
+    /// `languageId` reported by the client at `didOpen`.
+    pub language_id: String,
 }
 
 impl DocumentStore {
@@ -16,8 +18,15 @@ impl DocumentStore {
         }
     }
 
-    pub fn open(&mut self, uri: String, content: String, version: i32) {
-        self.documents.insert(uri, Document { content, version });
+    pub fn open(&mut self, uri: String, content: String, version: i32, language_id: String) {
+        self.documents.insert(
+            uri,
+            Document {
+                content,
+                version,
+                language_id,
+            },
+        );
     }
 
     pub fn update(&mut self, uri: &str, content: String, version: i32) {
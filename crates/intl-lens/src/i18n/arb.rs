@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+
+/// The `@key` metadata of an ARB message.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArbMetadata {
+    pub description: Option<String>,
+    /// Declared placeholders, in declaration order (the order of the generated
+    /// method's parameters).
+    pub placeholders: Vec<ArbPlaceholder>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArbPlaceholder {
+    pub name: String,
+    /// Dart type of the generated parameter, `Object` when not declared.
+    pub kind: Option<String>,
+    pub example: Option<String>,
+}
+
+impl ArbPlaceholder {
+    pub fn parameter(&self) -> String {
+        format!("{} {}", self.kind.as_deref().unwrap_or("Object"), self.name)
+    }
+}
+
+/// What an ARB file says about itself besides its messages.
+#[derive(Debug, Clone, Default)]
+pub struct ArbFile {
+    /// The `@@locale` entry.
+    pub locale: Option<String>,
+    pub metadata: HashMap<String, ArbMetadata>,
+}
+
+pub fn parse_arb_file(content: &str) -> Result<ArbFile> {
+    let value: JsonValue = serde_json::from_str(content)?;
+    let mut file = ArbFile::default();
+
+    let JsonValue::Object(map) = value else {
+        return Ok(file);
+    };
+
+    file.locale = map
+        .get("@@locale")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    for (name, entry) in &map {
+        let Some(key) = name.strip_prefix('@').filter(|key| !key.starts_with('@')) else {
+            continue;
+        };
+        let description = entry
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        let placeholders = entry
+            .get("placeholders")
+            .and_then(|v| v.as_object())
+            .map(|placeholders| {
+                placeholders
+                    .iter()
+                    .map(|(name, declaration)| ArbPlaceholder {
+                        name: name.clone(),
+                        kind: declaration
+                            .get("type")
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string),
+                        example: declaration.get("example").and_then(|v| match v {
+                            JsonValue::String(s) => Some(s.clone()),
+                            JsonValue::Null => None,
+                            other => Some(other.to_string()),
+                        }),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        file.metadata.insert(
+            key.to_string(),
+            ArbMetadata {
+                description,
+                placeholders,
+            },
+        );
+    }
+
+    Ok(file)
+}
+
+/// Names of the ICU arguments a message uses: `{name}`, and the selector of
+/// `{count, plural, ...}` together with the arguments inside its branches.
+pub fn message_arguments(message: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    collect_arguments(message, &mut arguments);
+    arguments
+}
+
+fn collect_arguments(message: &str, arguments: &mut Vec<String>) {
+    let mut rest = message;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = matching_brace(&rest[open..]).map(|close| open + close) else {
+            return;
+        };
+        let inner = &rest[open + 1..close];
+        let mut parts = inner.splitn(3, ',');
+        let name = parts.next().unwrap_or_default().trim();
+
+        let is_identifier = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if is_identifier && !arguments.iter().any(|argument| argument == name) {
+            arguments.push(name.to_string());
+        }
+
+        let kind = parts.next().map(str::trim);
+        if let (Some("plural" | "select" | "selectordinal"), Some(branches)) = (kind, parts.next())
+        {
+            let mut branches = branches;
+            while let Some(start) = branches.find('{') {
+                let Some(end) = matching_brace(&branches[start..]).map(|end| start + end) else {
+                    break;
+                };
+                collect_arguments(&branches[start + 1..end], arguments);
+                branches = &branches[end + 1..];
+            }
+        }
+
+        rest = &rest[close + 1..];
+    }
+}
+
+/// Byte offset of the `}` closing the `{` that `text` starts with.
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, ch) in text.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A problem with one message of an ARB file, compared with the template ARB.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArbIssue {
+    /// The key does not exist in the template, so no getter is generated for it.
+    NotInTemplate,
+    /// The message uses an argument the template does not declare.
+    UndeclaredPlaceholder(String),
+    /// The template declares a placeholder its message never uses.
+    UnusedPlaceholder(String),
+}
+
+/// Check every message of an ARB file against the template ARB's keys and declared
+/// placeholders. Pass `None` as the template when checking the template itself.
+/// Returns the key, its zero-based line, and the issue.
+pub fn check_arb(content: &str, template: Option<&str>) -> Vec<(String, usize, ArbIssue)> {
+    let Ok(JsonValue::Object(messages)) = serde_json::from_str::<JsonValue>(content) else {
+        return Vec::new();
+    };
+    let Ok(file) = parse_arb_file(template.unwrap_or(content)) else {
+        return Vec::new();
+    };
+    let template_keys: Option<Vec<String>> = match template {
+        Some(template) => match serde_json::from_str::<JsonValue>(template) {
+            Ok(JsonValue::Object(map)) => Some(map.keys().cloned().collect()),
+            _ => return Vec::new(),
+        },
+        None => None,
+    };
+
+    let mut issues = Vec::new();
+    for (key, value) in &messages {
+        let JsonValue::String(message) = value else {
+            continue;
+        };
+        if key.starts_with('@') {
+            continue;
+        }
+        let line = key_line(content, key);
+
+        if template_keys
+            .as_ref()
+            .is_some_and(|keys| !keys.contains(key))
+        {
+            issues.push((key.clone(), line, ArbIssue::NotInTemplate));
+            continue;
+        }
+
+        // Without declarations gen_l10n infers the placeholders, so there is nothing to check
+        let Some(declared) = file
+            .metadata
+            .get(key)
+            .filter(|metadata| !metadata.placeholders.is_empty())
+        else {
+            continue;
+        };
+        let used = message_arguments(message);
+
+        for argument in &used {
+            if !declared.placeholders.iter().any(|p| &p.name == argument) {
+                let issue = ArbIssue::UndeclaredPlaceholder(argument.clone());
+                issues.push((key.clone(), line, issue));
+            }
+        }
+        if template.is_none() {
+            for placeholder in &declared.placeholders {
+                if !used.contains(&placeholder.name) {
+                    let issue = ArbIssue::UnusedPlaceholder(placeholder.name.clone());
+                    issues.push((key.clone(), line, issue));
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+/// Zero-based line of the `"key":` entry.
+pub fn key_line(content: &str, key: &str) -> usize {
+    let quoted = format!("\"{}\"", key);
+    content
+        .lines()
+        .position(|line| {
+            line.trim_start()
+                .strip_prefix(&quoted)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"{
+  "@@locale": "en",
+  "greeting": "Hello {name}",
+  "@greeting": {
+    "description": "Greets the user",
+    "placeholders": {
+      "name": { "type": "String", "example": "Bob" }
+    }
+  },
+  "items": "{count, plural, =0{No items} other{{count} items for {owner}}}",
+  "@items": {
+    "placeholders": { "count": { "type": "int" }, "owner": {}, "unused": {} }
+  }
+}"#;
+
+    #[test]
+    fn test_parse_arb_metadata() {
+        let file = parse_arb_file(TEMPLATE).unwrap();
+        assert_eq!(file.locale.as_deref(), Some("en"));
+
+        let greeting = &file.metadata["greeting"];
+        assert_eq!(greeting.description.as_deref(), Some("Greets the user"));
+        assert_eq!(greeting.placeholders[0].parameter(), "String name");
+        assert_eq!(greeting.placeholders[0].example.as_deref(), Some("Bob"));
+
+        let names: Vec<_> = file.metadata["items"]
+            .placeholders
+            .iter()
+            .map(|p| p.parameter())
+            .collect();
+        assert_eq!(names, vec!["int count", "Object owner", "Object unused"]);
+    }
+
+    #[test]
+    fn test_message_arguments() {
+        assert_eq!(
+            message_arguments("{count, plural, =0{No items} other{{count} items for {owner}}}"),
+            vec!["count", "owner"]
+        );
+        assert_eq!(
+            message_arguments("{gender, select, male{He} other{They}} left"),
+            vec!["gender"]
+        );
+        assert!(message_arguments("No arguments").is_empty());
+    }
+
+    #[test]
+    fn test_check_arb_against_template() {
+        assert_eq!(
+            check_arb(TEMPLATE, None),
+            vec![(
+                "items".to_string(),
+                9,
+                ArbIssue::UnusedPlaceholder("unused".to_string())
+            )]
+        );
+
+        let translation = r#"{
+  "@@locale": "fr",
+  "greeting": "Bonjour {nom}",
+  "farewell": "Au revoir"
+}"#;
+        assert_eq!(
+            check_arb(translation, Some(TEMPLATE)),
+            vec![
+                (
+                    "greeting".to_string(),
+                    2,
+                    ArbIssue::UndeclaredPlaceholder("nom".to_string())
+                ),
+                ("farewell".to_string(), 3, ArbIssue::NotInTemplate),
+            ]
+        );
+    }
+}
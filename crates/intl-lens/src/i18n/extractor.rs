@@ -0,0 +1,985 @@
+use std::ops::Range;
+
+use super::key_pattern::{template_segments, KeyPattern};
+use super::language::LanguageFamily;
+use super::lexer::{SourceScan, Token, TokenKind};
+use super::parser::PO_CONTEXT_SEPARATOR;
+use super::scope::{self, Binding, PrefixSource, ScopeHook};
+
+/// A translation call recognised by its callee chain, e.g. `i18n.t` or `Lang::get`.
+struct CallRule {
+    callee: &'static str,
+    /// Index of the argument holding the key.
+    arg: usize,
+    /// Only match when called without a receiver, so `t(...)` matches but
+    /// `client.t(...)` does not.
+    bare: bool,
+}
+
+const fn call(callee: &'static str) -> CallRule {
+    CallRule {
+        callee,
+        arg: 0,
+        bare: false,
+    }
+}
+
+const fn bare(callee: &'static str) -> CallRule {
+    CallRule {
+        callee,
+        arg: 0,
+        bare: true,
+    }
+}
+
+/// A compiled messages module whose members are the messages, e.g. Paraglide's
+/// `import * as m from "./paraglide/messages"` and `m.some_key()`.
+struct MessageModule {
+    object: &'static str,
+    /// Text the import path must contain for `object` to be treated as the module.
+    path: &'static str,
+}
+
+/// A framework's translation APIs and the language families it is used from.
+pub struct Preset {
+    pub name: &'static str,
+    families: &'static [LanguageFamily],
+    calls: &'static [CallRule],
+    /// `intl.formatMessage({ id: "key" })`
+    object_id_callees: &'static [&'static str],
+    /// `"key" | translate` in Angular templates.
+    pipes: &'static [&'static str],
+    /// `'key'.tr()`, `'key'.tr` and friends.
+    postfix_members: &'static [&'static str],
+    /// `<Trans i18nKey="key">` in JSX, `<i18n-t keypath="key">` in templates.
+    attributes: &'static [&'static str],
+    /// Attributes that only hold a key on one element, e.g. Lingui's `<Trans id="key">`.
+    element_attributes: &'static [(&'static str, &'static str)],
+    /// Tagged templates whose text is the message id: Lingui's `` t`Hello` ``.
+    tagged_templates: &'static [&'static str],
+    message_modules: &'static [MessageModule],
+    /// Hooks returning a translation function bound to a key prefix.
+    pub(crate) scope_hooks: &'static [ScopeHook],
+    /// Transloco's `*transloco="let t; read: 'scope'"` template bindings.
+    pub(crate) template_scopes: bool,
+    /// Methods of a scoped translation function that take a key too, like `t.rich`.
+    scoped_members: &'static [&'static str],
+    /// Call option holding the scope the key is looked up in, e.g. Rails'
+    /// `t(:title, scope: [:users, :index])`.
+    scope_option: Option<&'static str>,
+    /// Keys starting with `.` are relative to the file they are used in (Rails lazy lookup).
+    pub(crate) lazy_lookup: bool,
+    /// Generated Dart classes whose getters are the messages: `AppLocalizations.of(context)!.key`.
+    localization_classes: &'static [&'static str],
+    /// Extension getters returning such a class, like `context.l10n`.
+    localization_getters: &'static [&'static str],
+}
+
+impl Preset {
+    pub fn applies_to(&self, family: Option<LanguageFamily>) -> bool {
+        family.map_or(true, |family| self.families.contains(&family))
+    }
+}
+
+const EMPTY: Preset = Preset {
+    name: "",
+    families: &[],
+    calls: &[],
+    object_id_callees: &[],
+    pipes: &[],
+    postfix_members: &[],
+    attributes: &[],
+    element_attributes: &[],
+    tagged_templates: &[],
+    message_modules: &[],
+    scope_hooks: &[],
+    template_scopes: false,
+    scoped_members: &[],
+    scope_option: None,
+    lazy_lookup: false,
+    localization_classes: &[],
+    localization_getters: &[],
+};
+
+/// The registry of built-in framework presets, addressable by name from `presets`.
+pub const PRESETS: &[Preset] = &[
+    // react-i18next / i18next: `t("key")`, `i18n.t("key")`, `<Trans i18nKey="key">`.
+    Preset {
+        name: "react-i18next",
+        families: &[LanguageFamily::JavaScript],
+        calls: &[bare("t"), call("i18n.t"), call("i18next.t")],
+        attributes: &["i18nKey"],
+        scope_hooks: &[
+            ScopeHook {
+                callee: "useTranslation",
+                prefix: PrefixSource::Field(1, "keyPrefix"),
+            },
+            ScopeHook {
+                callee: "getFixedT",
+                prefix: PrefixSource::Arg(2),
+            },
+        ],
+        ..EMPTY
+    },
+    // react-intl / FormatJS: `formatMessage({ id: "key" })`.
+    Preset {
+        name: "react-intl",
+        families: &[LanguageFamily::JavaScript],
+        object_id_callees: &["formatMessage"],
+        ..EMPTY
+    },
+    // vue-i18n: `$t`, `$tc`, `$te`, composition `t`, `i18n.global.t`, `<i18n-t keypath>`.
+    Preset {
+        name: "vue-i18n",
+        families: &[LanguageFamily::Vue, LanguageFamily::JavaScript],
+        calls: &[
+            bare("t"),
+            call("$t"),
+            call("$tc"),
+            call("$te"),
+            call("i18n.global.t"),
+        ],
+        attributes: &["keypath"],
+        scope_hooks: &[ScopeHook {
+            callee: "useI18n",
+            prefix: PrefixSource::None,
+        }],
+        ..EMPTY
+    },
+    // ngx-translate: `translateService.instant/get/stream("key")` and `| translate`.
+    Preset {
+        name: "ngx-translate",
+        families: &[LanguageFamily::JavaScript, LanguageFamily::Html],
+        calls: &[
+            call("translateService.instant"),
+            call("translateService.get"),
+            call("translateService.stream"),
+        ],
+        pipes: &["translate"],
+        ..EMPTY
+    },
+    // Transloco: `translocoService.translate/selectTranslate("key")` and `| transloco`.
+    Preset {
+        name: "transloco",
+        families: &[LanguageFamily::JavaScript, LanguageFamily::Html],
+        calls: &[
+            call("translocoService.translate"),
+            call("translocoService.selectTranslate"),
+        ],
+        pipes: &["transloco"],
+        template_scopes: true,
+        ..EMPTY
+    },
+    // next-intl: `useTranslations("Settings")`, `getTranslations("Settings")`, `t.rich`.
+    Preset {
+        name: "next-intl",
+        families: &[LanguageFamily::JavaScript],
+        scope_hooks: &[
+            ScopeHook {
+                callee: "useTranslations",
+                prefix: PrefixSource::Arg(0),
+            },
+            ScopeHook {
+                callee: "getTranslations",
+                prefix: PrefixSource::Arg(0),
+            },
+        ],
+        scoped_members: &["rich", "markup", "raw", "has"],
+        ..EMPTY
+    },
+    // Lingui: `` t`message` ``, `t({ id })`, `msg`, `defineMessage`, `i18n._`, `<Trans id>`.
+    Preset {
+        name: "lingui",
+        families: &[LanguageFamily::JavaScript],
+        calls: &[call("i18n._"), call("i18n.t")],
+        object_id_callees: &["t", "msg", "defineMessage"],
+        element_attributes: &[("Trans", "id")],
+        tagged_templates: &["t", "msg"],
+        ..EMPTY
+    },
+    // inlang Paraglide: `m.some_key()` on the compiled messages module.
+    Preset {
+        name: "paraglide",
+        families: &[
+            LanguageFamily::JavaScript,
+            LanguageFamily::Svelte,
+            LanguageFamily::Vue,
+        ],
+        message_modules: &[MessageModule {
+            object: "m",
+            path: "paraglide",
+        }],
+        ..EMPTY
+    },
+    // Laravel: `__()`, `trans()`, `trans_choice()`, `Lang::get/choice()`, `@lang`, `@choice`.
+    Preset {
+        name: "laravel",
+        families: &[LanguageFamily::Php, LanguageFamily::Blade],
+        calls: &[
+            call("__"),
+            call("trans"),
+            call("trans_choice"),
+            call("Lang.get"),
+            call("Lang.choice"),
+            call("@lang"),
+            call("@choice"),
+        ],
+        ..EMPTY
+    },
+    // Rails: `t(".relative")`, `I18n.t("key")`, `t(:key, scope: [:a, :b])`.
+    Preset {
+        name: "rails",
+        families: &[LanguageFamily::Ruby, LanguageFamily::Erb],
+        calls: &[
+            bare("t"),
+            bare("translate"),
+            call("I18n.t"),
+            call("I18n.translate"),
+        ],
+        scope_option: Some("scope"),
+        lazy_lookup: true,
+        ..EMPTY
+    },
+    // easy_localization: `'key'.tr()`, `tr('key')`, `context.tr('key')`, `'key'.plural()`.
+    Preset {
+        name: "easy_localization",
+        families: &[LanguageFamily::Dart],
+        calls: &[bare("tr"), call("context.tr")],
+        postfix_members: &["tr", "plural"],
+        ..EMPTY
+    },
+    // flutter_i18n: `FlutterI18n.translate(context, 'key')`, `I18nText('key')`.
+    Preset {
+        name: "flutter_i18n",
+        families: &[LanguageFamily::Dart],
+        calls: &[
+            CallRule {
+                callee: "FlutterI18n.translate",
+                arg: 1,
+                bare: false,
+            },
+            CallRule {
+                callee: "FlutterI18n.plural",
+                arg: 1,
+                bare: false,
+            },
+            call("I18nText"),
+            call("I18nPlural"),
+        ],
+        ..EMPTY
+    },
+    // Flutter gen_l10n and intl_utils: `AppLocalizations.of(context)!.key`, `S.of(context).key`,
+    // `S.current.key`, `context.l10n.key`, `l10n.greeting(name)`.
+    Preset {
+        name: "gen_l10n",
+        families: &[LanguageFamily::Dart],
+        localization_classes: &["AppLocalizations", "S"],
+        localization_getters: &["l10n"],
+        ..EMPTY
+    },
+    // GetX: `'key'.tr`, `'key'.trParams({})`, `'key'.trPlural()`.
+    Preset {
+        name: "getx",
+        families: &[LanguageFamily::Dart],
+        postfix_members: &["tr", "trParams", "trPlural"],
+        ..EMPTY
+    },
+    // svelte-i18n / sveltekit-i18n: `$_`, `$t`, `$format`, `t`.
+    Preset {
+        name: "svelte-i18n",
+        families: &[LanguageFamily::Svelte, LanguageFamily::JavaScript],
+        calls: &[bare("t"), call("$t"), call("$_"), call("$format")],
+        ..EMPTY
+    },
+];
+
+pub fn preset(name: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|preset| preset.name == name)
+}
+
+/// A key reference found in the source. Static keys cover the string contents;
+/// dynamic keys cover the whole argument expression and carry its pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedKey {
+    pub range: Range<usize>,
+    /// The key when it is not the source text itself, such as the message id of a
+    /// Lingui tagged template with placeholders.
+    pub key: Option<String>,
+    pub pattern: Option<KeyPattern>,
+    /// Key prefix of the scoped translation function the key was passed to.
+    pub prefix: Option<String>,
+}
+
+impl From<Range<usize>> for ExtractedKey {
+    fn from(range: Range<usize>) -> Self {
+        Self {
+            range,
+            key: None,
+            pattern: None,
+            prefix: None,
+        }
+    }
+}
+
+/// Every key passed to a translation API of one of the given presets.
+pub fn extract_keys(content: &str, scan: &SourceScan, presets: &[&Preset]) -> Vec<ExtractedKey> {
+    let tokens = &scan.tokens;
+    let has_attribute = |name: &str| presets.iter().any(|p| p.attributes.contains(&name));
+
+    let bindings = scope::collect_bindings(content, tokens, presets);
+    let message_objects: Vec<&str> = presets
+        .iter()
+        .flat_map(|preset| preset.message_modules)
+        .filter(|module| imports_module(tokens, module))
+        .map(|module| module.object)
+        .collect();
+
+    let mut keys: Vec<ExtractedKey> = scan
+        .attributes
+        .iter()
+        .filter(|(name, _)| has_attribute(name))
+        .map(|(_, range)| range.clone().into())
+        .collect();
+
+    for (index, token) in tokens.iter().enumerate() {
+        match &token.kind {
+            TokenKind::Punct('(') => {
+                let key = match bound_callee(tokens, index, &bindings, presets) {
+                    Some(binding) => {
+                        argument_key(content, tokens, index + 1).map(|key| ExtractedKey {
+                            prefix: binding.prefix.clone(),
+                            ..key
+                        })
+                    }
+                    None => call_key(content, tokens, index, presets),
+                };
+                keys.extend(key);
+            }
+            TokenKind::Ident(name)
+                if has_attribute(name) || is_element_attribute(tokens, index, presets) =>
+            {
+                if let (Some(eq), Some(value)) = (tokens.get(index + 1), tokens.get(index + 2)) {
+                    if eq.is_punct('=') && value.static_string().is_some() {
+                        keys.push(value.inner_range(content).into());
+                    }
+                }
+            }
+            TokenKind::Ident(name) if message_objects.contains(&name.as_str()) => {
+                keys.extend(message_member(tokens, index));
+            }
+            TokenKind::Template { raw, dynamic } if is_tagged(tokens, index, presets) => {
+                keys.push(ExtractedKey {
+                    key: dynamic.then(|| message_id(raw)),
+                    ..token.inner_range(content).into()
+                });
+            }
+            _ if token.static_string().is_some()
+                && (is_piped(tokens, index, presets)
+                    || has_postfix_member(tokens, index, presets)) =>
+            {
+                keys.push(token.inner_range(content).into());
+            }
+            _ => {}
+        }
+    }
+
+    keys
+}
+
+/// Getters and methods of generated Dart localizations, which are named after the ARB
+/// keys: `AppLocalizations.of(context)!.title`, `S.current.title`, `context.l10n.title`
+/// and `l10n.greeting(name)` through a variable holding any of these. `classes` adds
+/// generated class names to the presets' own.
+pub fn localization_members(
+    tokens: &[Token],
+    presets: &[&Preset],
+    classes: &[String],
+) -> Vec<ExtractedKey> {
+    let classes: Vec<&str> = presets
+        .iter()
+        .flat_map(|preset| preset.localization_classes)
+        .copied()
+        .chain(classes.iter().map(String::as_str))
+        .collect();
+    let getters: Vec<&str> = presets
+        .iter()
+        .flat_map(|preset| preset.localization_getters)
+        .copied()
+        .collect();
+    if classes.is_empty() && getters.is_empty() {
+        return Vec::new();
+    }
+
+    // Index just past an expression evaluating to the localizations that starts at `index`
+    let accessor_end = |index: usize, variables: &[&str]| -> Option<usize> {
+        let name = tokens[index].ident()?;
+        let has_receiver = separator_before(tokens, index) > 0;
+
+        if classes.contains(&name) && !has_receiver && tokens.get(index + 1)?.is_punct('.') {
+            return match tokens.get(index + 2)?.ident()? {
+                "of" if tokens.get(index + 3)?.is_punct('(') => {
+                    closing_paren(tokens, index + 3).map(|close| close + 1)
+                }
+                "current" => Some(index + 3),
+                _ => None,
+            };
+        }
+        if (getters.contains(&name) && has_receiver) || (variables.contains(&name) && !has_receiver)
+        {
+            return Some(index + 1);
+        }
+        None
+    };
+
+    // `final l10n = AppLocalizations.of(context)!;`
+    let mut variables = Vec::new();
+    for index in 0..tokens.len().saturating_sub(2) {
+        let (Some(name), true) = (tokens[index].ident(), tokens[index + 1].is_punct('=')) else {
+            continue;
+        };
+        // The accessor may hang off a receiver: `final l10n = context.l10n;`
+        let Some(end) =
+            (index + 2..(index + 6).min(tokens.len())).find_map(|start| accessor_end(start, &[]))
+        else {
+            continue;
+        };
+        let end = match tokens.get(end) {
+            Some(token) if token.is_punct('!') => end + 1,
+            _ => end,
+        };
+        if tokens.get(end).is_some_and(|t| t.is_punct(';')) {
+            variables.push(name);
+        }
+    }
+
+    let mut keys = Vec::new();
+    for index in 0..tokens.len() {
+        let Some(end) = accessor_end(index, &variables) else {
+            continue;
+        };
+        let member = (end + 1..=end + 2).find(|&i| {
+            tokens.get(i).is_some_and(|t| t.ident().is_some())
+                && i - separator_before(tokens, i) == end
+        });
+        if let Some(member) = member.map(|i| &tokens[i]) {
+            // `localeName` is the one getter that is not a message
+            if member.ident() != Some("localeName") {
+                keys.push((member.start..member.end).into());
+            }
+        }
+    }
+
+    keys
+}
+
+/// Index of the `)` closing the `(` at `open`.
+fn closing_paren(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate().skip(open) {
+        if token.is_punct('(') {
+            depth += 1;
+        } else if token.is_punct(')') {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+/// The binding a bare call like `tp("title")` or `t.rich("title")` at `paren` goes
+/// through, if any.
+fn bound_callee<'a>(
+    tokens: &[Token],
+    paren: usize,
+    bindings: &'a [Binding],
+    presets: &[&Preset],
+) -> Option<&'a Binding> {
+    let (chain, has_receiver) = callee_chain(tokens, paren)?;
+    if has_receiver {
+        return None;
+    }
+    match chain.as_slice() {
+        [name] => scope::binding_at(bindings, name, paren),
+        [name, member] if presets.iter().any(|p| p.scoped_members.contains(member)) => {
+            scope::binding_at(bindings, name, paren)
+        }
+        _ => None,
+    }
+}
+
+/// Key prefix of the scoped translation call whose argument list contains `offset`,
+/// used to complete keys relative to the scope.
+pub fn prefix_at(
+    content: &str,
+    scan: &SourceScan,
+    presets: &[&Preset],
+    offset: usize,
+) -> Option<String> {
+    let tokens = &scan.tokens;
+    let bindings = scope::collect_bindings(content, tokens, presets);
+
+    let before = tokens
+        .iter()
+        .take_while(|token| token.start < offset)
+        .count();
+    let paren = (0..before)
+        .rev()
+        .take_while(|&index| !tokens[index].is_punct(')'))
+        .find(|&index| tokens[index].is_punct('('))?;
+
+    bound_callee(tokens, paren, &bindings, presets)?
+        .prefix
+        .clone()
+}
+
+/// Whether a messages module is imported under its object name, as in
+/// `import * as m from "$lib/paraglide/messages"` or `import { m } from "..."`.
+fn imports_module(tokens: &[Token], module: &MessageModule) -> bool {
+    tokens.iter().enumerate().any(|(index, token)| {
+        if token.ident() != Some("import") {
+            return false;
+        }
+        let Some(from) = (index + 1..(index + 12).min(tokens.len()))
+            .find(|&i| tokens[i].ident() == Some("from"))
+        else {
+            return false;
+        };
+        tokens[index + 1..from]
+            .iter()
+            .any(|token| token.ident() == Some(module.object))
+            && tokens
+                .get(from + 1)
+                .and_then(Token::static_string)
+                .is_some_and(|path| path.contains(module.path))
+    })
+}
+
+/// The `some_key` of `m.some_key(...)` where `m` is at `index`.
+fn message_member(tokens: &[Token], index: usize) -> Option<ExtractedKey> {
+    if index > 0 && separator_before(tokens, index) > 0 {
+        return None;
+    }
+    let (dot, member, paren) = (
+        tokens.get(index + 1)?,
+        tokens.get(index + 2)?,
+        tokens.get(index + 3)?,
+    );
+    if !dot.is_punct('.') || member.ident().is_none() || !paren.is_punct('(') {
+        return None;
+    }
+    Some((member.start..member.end).into())
+}
+
+/// Whether the template at `index` is tagged with a bare message tag like `` t`...` ``.
+fn is_tagged(tokens: &[Token], index: usize, presets: &[&Preset]) -> bool {
+    let Some(tag) = index.checked_sub(1).and_then(|i| tokens[i].ident()) else {
+        return false;
+    };
+    separator_before(tokens, index - 1) == 0
+        && presets.iter().any(|p| p.tagged_templates.contains(&tag))
+}
+
+/// The message id Lingui generates for a template: identifiers become named
+/// placeholders (`{name}`) and other expressions positional ones (`{0}`).
+fn message_id(raw: &str) -> String {
+    let mut id = String::new();
+    let mut positional = 0;
+    let mut rest = raw;
+
+    while let Some(start) = rest.find("${") {
+        id.push_str(&rest[..start]);
+
+        let mut depth = 0usize;
+        let mut end = rest.len();
+        for (offset, ch) in rest[start + 1..].char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = start + 1 + offset + 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let expression = rest[start + 2..end].trim_end_matches('}').trim();
+        let is_identifier = expression
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+            && expression
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+        if is_identifier {
+            id.push_str(&format!("{{{}}}", expression));
+        } else {
+            id.push_str(&format!("{{{}}}", positional));
+            positional += 1;
+        }
+
+        rest = &rest[end..];
+    }
+    id.push_str(rest);
+
+    id
+}
+
+/// Whether the identifier at `index` is an attribute of an element that only takes
+/// a key there, e.g. the `id` in `<Trans id="key">`.
+fn is_element_attribute(tokens: &[Token], index: usize, presets: &[&Preset]) -> bool {
+    let Some(name) = tokens[index].ident() else {
+        return false;
+    };
+    let mut elements = presets
+        .iter()
+        .flat_map(|preset| preset.element_attributes)
+        .filter(|(_, attribute)| *attribute == name)
+        .peekable();
+    if elements.peek().is_none() {
+        return false;
+    }
+
+    let mut depth = 0usize;
+    for i in (index.saturating_sub(64)..index).rev() {
+        match tokens[i].kind {
+            TokenKind::Punct('}') => depth += 1,
+            TokenKind::Punct('{') => depth = depth.saturating_sub(1),
+            TokenKind::Punct('>') if depth == 0 => return false,
+            TokenKind::Punct('<') if depth == 0 => {
+                let tag = tokens.get(i + 1).and_then(Token::ident);
+                return elements.any(|(element, _)| Some(*element) == tag);
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+fn is_piped(tokens: &[Token], index: usize, presets: &[&Preset]) -> bool {
+    let (Some(pipe), Some(name)) = (tokens.get(index + 1), tokens.get(index + 2)) else {
+        return false;
+    };
+    pipe.is_punct('|')
+        && name
+            .ident()
+            .is_some_and(|name| presets.iter().any(|p| p.pipes.contains(&name)))
+}
+
+fn has_postfix_member(tokens: &[Token], index: usize, presets: &[&Preset]) -> bool {
+    let (Some(dot), Some(name)) = (tokens.get(index + 1), tokens.get(index + 2)) else {
+        return false;
+    };
+    dot.is_punct('.')
+        && name
+            .ident()
+            .is_some_and(|name| presets.iter().any(|p| p.postfix_members.contains(&name)))
+}
+
+/// The callee chain ending just before the `(` at `paren`, e.g. `["this", "$t"]`.
+/// The flag is set when the chain hangs off a non-identifier receiver such as
+/// `foo().t(...)` or `items[0].t(...)`.
+pub(crate) fn callee_chain(tokens: &[Token], paren: usize) -> Option<(Vec<&str>, bool)> {
+    let mut index = paren.checked_sub(1)?;
+    let mut chain = vec![tokens[index].ident()?];
+
+    loop {
+        let separator_len = separator_before(tokens, index);
+        if separator_len == 0 {
+            return Some((chain.into_iter().rev().collect(), false));
+        }
+        let Some(previous) = index
+            .checked_sub(separator_len + 1)
+            .and_then(|i| tokens[i].ident())
+        else {
+            return Some((chain.into_iter().rev().collect(), true));
+        };
+        chain.push(previous);
+        index -= separator_len + 1;
+    }
+}
+
+/// Length in tokens of a member-access separator (`.`, `?.`, `!.`, `::`, `->`)
+/// immediately before `index`, or 0.
+fn separator_before(tokens: &[Token], index: usize) -> usize {
+    let punct = |offset: usize| {
+        index
+            .checked_sub(offset)
+            .and_then(|i| tokens.get(i))
+            .and_then(|t| match t.kind {
+                TokenKind::Punct(c) => Some(c),
+                _ => None,
+            })
+    };
+
+    match (punct(2), punct(1)) {
+        (Some('?' | '!'), Some('.')) | (Some(':'), Some(':')) | (Some('-'), Some('>')) => 2,
+        (_, Some('.')) => 1,
+        _ => 0,
+    }
+}
+
+fn rule_matches(rule: &CallRule, chain: &[&str], has_receiver: bool) -> bool {
+    let callee: Vec<&str> = rule.callee.split('.').collect();
+    if rule.bare {
+        return !has_receiver && chain == callee.as_slice();
+    }
+    chain.ends_with(&callee)
+}
+
+fn call_key(
+    content: &str,
+    tokens: &[Token],
+    paren: usize,
+    presets: &[&Preset],
+) -> Option<ExtractedKey> {
+    let (chain, has_receiver) = callee_chain(tokens, paren)?;
+
+    let key = presets.iter().find_map(|preset| {
+        preset
+            .calls
+            .iter()
+            .filter(|rule| rule_matches(rule, &chain, has_receiver))
+            .find_map(|rule| {
+                let arg = argument_start(tokens, paren, rule.arg)?;
+                let key = argument_key(content, tokens, arg)?;
+                let prefix = preset
+                    .scope_option
+                    .and_then(|option| scope_option(tokens, paren, option));
+                Some(ExtractedKey { prefix, ..key })
+            })
+    });
+    if key.is_some() {
+        return key;
+    }
+
+    let callee = chain.last()?;
+    if presets
+        .iter()
+        .any(|preset| preset.object_id_callees.contains(callee))
+    {
+        let id = object_field(content, tokens, paren + 1, "id")?;
+        // A Lingui message with a context is keyed like its gettext entry
+        let key = object_field(content, tokens, paren + 1, "context").map(|context| {
+            format!(
+                "{}{}{}",
+                &content[context],
+                PO_CONTEXT_SEPARATOR,
+                &content[id.clone()]
+            )
+        });
+        return Some(ExtractedKey { key, ..id.into() });
+    }
+
+    None
+}
+
+/// The key passed as the argument starting at `arg`: a plain string, a template literal
+/// with interpolations, or a `+` concatenation with at least one static part.
+fn argument_key(content: &str, tokens: &[Token], arg: usize) -> Option<ExtractedKey> {
+    let ends_argument = |token: Option<&Token>| {
+        token.is_some_and(|token| token.is_punct(',') || token.is_punct(')'))
+    };
+
+    let first = tokens.get(arg)?;
+    if first.static_string().is_some() && ends_argument(tokens.get(arg + 1)) {
+        return Some(first.inner_range(content).into());
+    }
+
+    if let TokenKind::Template { raw, dynamic: true } = &first.kind {
+        if ends_argument(tokens.get(arg + 1)) {
+            return Some(ExtractedKey {
+                range: first.inner_range(content),
+                key: None,
+                pattern: Some(KeyPattern::from_template(raw)?),
+                prefix: None,
+            });
+        }
+    }
+
+    // Concatenation: operands separated by top-level `+`
+    let mut segments = Vec::new();
+    let mut index = arg;
+    let mut concatenated = false;
+    let mut last = first;
+
+    loop {
+        let token = tokens.get(index)?;
+        let operand_end = tokens.get(index + 1);
+        let single = operand_end.is_some_and(|t| t.is_punct('+')) || ends_argument(operand_end);
+
+        match (&token.kind, single) {
+            (TokenKind::Template { raw, dynamic: true }, true) => {
+                segments.extend(template_segments(raw));
+                index += 1;
+            }
+            _ if single && token.static_string().is_some() => {
+                segments.push(token.static_string().map(str::to_string));
+                index += 1;
+            }
+            _ => {
+                segments.push(None);
+                let mut depth = 0usize;
+                while let Some(token) = tokens.get(index) {
+                    match token.kind {
+                        TokenKind::Punct('(' | '[' | '{') => depth += 1,
+                        TokenKind::Punct(')' | ']' | '}') if depth > 0 => depth -= 1,
+                        TokenKind::Punct('+' | ',' | ')') if depth == 0 => break,
+                        _ => {}
+                    }
+                    index += 1;
+                }
+            }
+        }
+
+        last = tokens.get(index.checked_sub(1)?).unwrap_or(last);
+        match tokens.get(index) {
+            Some(token) if token.is_punct('+') => {
+                concatenated = true;
+                index += 1;
+            }
+            _ => break,
+        }
+    }
+
+    if !concatenated {
+        return None;
+    }
+
+    let pattern = KeyPattern::from_segments(segments)?;
+    let mut range = first.start..last.end;
+    if content[range.clone()].contains('\n') {
+        range = first.start..first.end;
+    }
+
+    Some(ExtractedKey {
+        range,
+        key: None,
+        pattern: Some(pattern),
+        prefix: None,
+    })
+}
+
+/// The value of a `scope: "a.b"`, `scope: :a` or `scope: [:a, :b]` option (or its
+/// `:scope => ...` spelling) among the arguments of the call at `paren`, dot-joined.
+fn scope_option(tokens: &[Token], paren: usize, option: &str) -> Option<String> {
+    let mut depth = 0usize;
+    let mut value = None;
+
+    for (index, token) in tokens.iter().enumerate().skip(paren + 1) {
+        match token.kind {
+            TokenKind::Punct('(' | '[' | '{') => depth += 1,
+            TokenKind::Punct(')' | ']' | '}') => {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            _ if depth == 0 => {
+                let named = token.ident() == Some(option)
+                    && tokens.get(index + 1).is_some_and(|t| t.is_punct(':'));
+                let rocket = token.static_string() == Some(option)
+                    && tokens.get(index + 1).is_some_and(|t| t.is_punct('='))
+                    && tokens.get(index + 2).is_some_and(|t| t.is_punct('>'));
+                if named {
+                    value = Some(index + 2);
+                } else if rocket {
+                    value = Some(index + 3);
+                }
+                if value.is_some() {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let start = tokens.get(value?)?;
+    if let Some(scope) = start.static_string() {
+        return Some(scope.to_string());
+    }
+    if !start.is_punct('[') {
+        return None;
+    }
+
+    let mut parts = Vec::new();
+    for token in &tokens[value? + 1..] {
+        match token.kind {
+            TokenKind::Punct(']') => return Some(parts.join(".")),
+            TokenKind::Punct(',') => {}
+            _ => parts.push(token.static_string()?),
+        }
+    }
+    None
+}
+
+/// Index of the first token of argument `arg` of the call whose `(` is at `paren`.
+pub(crate) fn argument_start(tokens: &[Token], paren: usize, arg: usize) -> Option<usize> {
+    if arg == 0 {
+        return (paren + 1 < tokens.len()).then_some(paren + 1);
+    }
+
+    let mut depth = 0usize;
+    let mut seen = 0;
+    for (index, token) in tokens.iter().enumerate().skip(paren + 1) {
+        match token.kind {
+            TokenKind::Punct('(' | '[' | '{') => depth += 1,
+            TokenKind::Punct(')' | ']' | '}') => {
+                if depth == 0 {
+                    return None;
+                }
+                depth -= 1;
+            }
+            TokenKind::Punct(',') if depth == 0 => {
+                seen += 1;
+                if seen == arg {
+                    return (index + 1 < tokens.len()).then_some(index + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// The string value of `field` in an object literal starting at `brace`, such as
+/// `{ id: "key", ... }`.
+pub(crate) fn object_field(
+    content: &str,
+    tokens: &[Token],
+    brace: usize,
+    field: &str,
+) -> Option<Range<usize>> {
+    if !tokens.get(brace)?.is_punct('{') {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate().skip(brace) {
+        match token.kind {
+            TokenKind::Punct('{' | '(' | '[') => depth += 1,
+            TokenKind::Punct('}' | ')' | ']') => {
+                depth -= 1;
+                if depth == 0 {
+                    return None;
+                }
+            }
+            _ if depth == 1 && token.ident() == Some(field) => {
+                let colon = tokens.get(index + 1)?;
+                let value = tokens.get(index + 2)?;
+                if colon.is_punct(':') && value.static_string().is_some() {
+                    return Some(value.inner_range(content));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
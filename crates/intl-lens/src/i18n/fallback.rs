@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use super::locale::canonicalize;
+use crate::config::{FallbackLocale, I18nConfig};
+
+/// Where a locale reads a message it lacks, as i18next, vue-i18n and Laravel resolve it
+/// at runtime: the parents of a regional locale (`zh-Hant-TW`, then `zh-Hant`, then
+/// `zh`), then the configured chain of the locale or of one of its parents, then the
+/// default fallback locales.
+#[derive(Debug, Clone, Default)]
+pub struct LocaleFallback {
+    chains: HashMap<String, Vec<String>>,
+    default: Vec<String>,
+    language: bool,
+}
+
+impl LocaleFallback {
+    pub fn from_config(config: &I18nConfig) -> Self {
+        let mut chains = HashMap::new();
+        let mut default = Vec::new();
+
+        match &config.fallback_locale {
+            Some(FallbackLocale::One(locale)) => default.push(locale.clone()),
+            Some(FallbackLocale::Many(locales)) => default.clone_from(locales),
+            Some(FallbackLocale::Chains(map)) => {
+                for (locale, chain) in map {
+                    if locale == "default" {
+                        default.clone_from(chain);
+                    } else {
+                        chains.insert(canonicalize(locale), chain.clone());
+                    }
+                }
+            }
+            None => {}
+        }
+
+        Self {
+            chains,
+            default,
+            language: config.language_fallback,
+        }
+    }
+
+    /// Every locale `locale` falls back to, in order, without `locale` itself. Locales
+    /// are canonical.
+    pub fn chain(&self, locale: &str) -> Vec<String> {
+        let locale = &canonicalize(locale);
+        let mut chain = self.variant_chain(locale);
+        for fallback in &self.default {
+            push_unique(&mut chain, locale, fallback);
+        }
+        chain
+    }
+
+    /// The fallbacks that make `locale` a variant of other locales: its parents and its
+    /// configured chain, but not the default fallback every locale ends with. A message
+    /// these provide is not missing in `locale`.
+    pub fn variant_chain(&self, locale: &str) -> Vec<String> {
+        let locale = &canonicalize(locale);
+        let parents = parents(locale);
+        let mut chain = Vec::new();
+
+        if self.language {
+            for parent in &parents {
+                push_unique(&mut chain, locale, parent);
+            }
+        }
+
+        let configured = std::iter::once(locale)
+            .chain(&parents)
+            .find_map(|code| self.chains.get(code));
+        for fallback in configured.into_iter().flatten() {
+            push_unique(&mut chain, locale, fallback);
+        }
+
+        chain
+    }
+}
+
+/// The shorter codes of a canonical locale, longest first: `zh-Hant-TW` gives `zh-Hant`
+/// and `zh`.
+fn parents(locale: &str) -> Vec<String> {
+    let mut parents = Vec::new();
+    let mut code = locale;
+    while let Some(index) = code.rfind('-') {
+        code = &code[..index];
+        parents.push(code.to_string());
+    }
+    parents
+}
+
+fn push_unique(chain: &mut Vec<String>, locale: &str, fallback: &str) {
+    let fallback = canonicalize(fallback);
+    if fallback != locale && !chain.contains(&fallback) {
+        chain.push(fallback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_json(json: &str) -> LocaleFallback {
+        let config: I18nConfig = serde_json::from_str(json).unwrap();
+        LocaleFallback::from_config(&config)
+    }
+
+    #[test]
+    fn test_language_and_default_fallback() {
+        let fallback = from_json(r#"{"fallbackLocale": "en"}"#);
+        assert_eq!(fallback.chain("pt-BR"), vec!["pt", "en"]);
+        assert_eq!(fallback.chain("zh_Hant_TW"), vec!["zh-Hant", "zh", "en"]);
+        assert_eq!(fallback.variant_chain("pt-BR"), vec!["pt"]);
+        assert!(fallback.chain("en").is_empty());
+
+        let strict = from_json(r#"{"fallbackLocale": ["en", "de"], "languageFallback": false}"#);
+        assert_eq!(strict.chain("pt-BR"), vec!["en", "de"]);
+        assert!(strict.variant_chain("pt-BR").is_empty());
+    }
+
+    #[test]
+    fn test_configured_chains() {
+        let fallback = from_json(
+            r#"{"fallbackLocale": {"de-CH": ["fr", "it"], "es": ["pt"], "default": ["en"]}}"#,
+        );
+        assert_eq!(fallback.chain("de_CH"), vec!["de", "fr", "it", "en"]);
+        assert_eq!(fallback.chain("es-MX"), vec!["es", "pt", "en"]);
+        assert_eq!(fallback.variant_chain("es-MX"), vec!["es", "pt"]);
+        assert_eq!(fallback.chain("fr"), vec!["en"]);
+    }
+}
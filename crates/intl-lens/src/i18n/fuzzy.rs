@@ -0,0 +1,148 @@
+/// Rank `candidates` by similarity to `query` and return at most `limit` near-miss keys.
+///
+/// Keys with the same number of segments are compared segment by segment, so a typo in
+/// one segment (`comon.save`) does not get drowned out by the rest of the key. Every
+/// differing segment must stay within a small edit budget relative to its length,
+/// which keeps unrelated siblings such as `common.name` out of the results.
+pub fn find_similar_keys<'a, I>(query: &str, candidates: I, limit: usize) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let mut scored: Vec<(usize, usize, &String)> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.as_str() != query)
+        .filter_map(|candidate| {
+            let score = key_distance(query, candidate)?;
+            let length_delta = query.len().abs_diff(candidate.len());
+            Some((score, length_delta, candidate))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(b.2)));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, _, candidate)| candidate.clone())
+        .collect()
+}
+
+fn key_distance(query: &str, candidate: &str) -> Option<usize> {
+    let query_segments: Vec<&str> = query.split('.').collect();
+    let candidate_segments: Vec<&str> = candidate.split('.').collect();
+
+    if query_segments.len() == candidate_segments.len() {
+        let mut total = 0;
+        for (a, b) in query_segments.iter().zip(&candidate_segments) {
+            let distance = edit_distance(a, b);
+            if distance > segment_budget(a) {
+                return None;
+            }
+            total += distance;
+        }
+        return Some(total);
+    }
+
+    // A misplaced or missing separator (`common.buttonsave`) changes the segment count,
+    // so fall back to the whole key and charge a penalty for the structural mismatch.
+    let distance = edit_distance(query, candidate);
+    let budget = (query.chars().count() / 6).clamp(1, 3);
+    (distance <= budget).then_some(distance + 1)
+}
+
+fn segment_budget(segment: &str) -> usize {
+    (segment.chars().count() / 3).clamp(1, 3)
+}
+
+/// Optimal string alignment distance: Levenshtein plus adjacent transpositions,
+/// so `saev` → `save` costs one edit instead of two.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() {
+        return b.len();
+    }
+    if b.is_empty() {
+        return a.len();
+    }
+
+    let width = b.len() + 1;
+    let mut matrix = vec![0usize; (a.len() + 1) * width];
+    for (i, row) in matrix.chunks_mut(width).enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in matrix.iter_mut().take(width).enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut value = (matrix[(i - 1) * width + j] + 1)
+                .min(matrix[i * width + j - 1] + 1)
+                .min(matrix[(i - 1) * width + j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                value = value.min(matrix[(i - 2) * width + j - 2] + 1);
+            }
+
+            matrix[i * width + j] = value;
+        }
+    }
+
+    matrix[a.len() * width + b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_suggests_key_with_typo_in_first_segment() {
+        let candidates = keys(&["common.save", "common.cancel", "auth.login"]);
+        let result = find_similar_keys("comon.save", &candidates, 3);
+        assert_eq!(result, vec!["common.save".to_string()]);
+    }
+
+    #[test]
+    fn test_suggests_key_with_transposed_letters() {
+        let candidates = keys(&["common.save", "common.cancel"]);
+        let result = find_similar_keys("common.saev", &candidates, 3);
+        assert_eq!(result, vec!["common.save".to_string()]);
+    }
+
+    #[test]
+    fn test_does_not_suggest_unrelated_sibling() {
+        let candidates = keys(&["common.name", "common.title"]);
+        let result = find_similar_keys("common.save", &candidates, 3);
+        assert!(result.is_empty(), "got {:?}", result);
+    }
+
+    #[test]
+    fn test_handles_missing_separator() {
+        let candidates = keys(&["common.buttons.save"]);
+        let result = find_similar_keys("common.buttonssave", &candidates, 3);
+        assert_eq!(result, vec!["common.buttons.save".to_string()]);
+    }
+
+    #[test]
+    fn test_orders_by_distance_and_respects_limit() {
+        let candidates = keys(&["common.saving", "common.save", "common.says", "common.saw"]);
+        let result = find_similar_keys("common.sav", &candidates, 2);
+        assert_eq!(
+            result,
+            vec!["common.saw".to_string(), "common.save".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance_counts_transposition_once() {
+        assert_eq!(edit_distance("save", "saev"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
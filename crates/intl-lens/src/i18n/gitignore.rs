@@ -0,0 +1,129 @@
+use std::path::{Path, PathBuf};
+
+use globset::{GlobBuilder, GlobMatcher};
+
+/// The rules of the `.gitignore` files seen so far. Each applies to the paths below the
+/// directory of its file, and the last matching rule decides, so a `!pattern` can
+/// re-include what an earlier rule ignored.
+#[derive(Debug, Default)]
+pub struct Gitignore {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    base: PathBuf,
+    glob: GlobMatcher,
+    negated: bool,
+    dir_only: bool,
+}
+
+impl Gitignore {
+    /// The rules of the `.gitignore` files in `dir` and its parents up to `root`.
+    pub fn for_dir(root: &Path, dir: &Path) -> Self {
+        let mut gitignore = Self::default();
+        let mut dirs: Vec<&Path> = dir
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(root))
+            .collect();
+        dirs.reverse();
+        for dir in dirs {
+            gitignore.add_dir(dir);
+        }
+        gitignore
+    }
+
+    /// Add the rules of the `.gitignore` file in `dir`, if there is one.
+    pub fn add_dir(&mut self, dir: &Path) {
+        let Ok(content) = std::fs::read_to_string(dir.join(".gitignore")) else {
+            return;
+        };
+        self.rules
+            .extend(content.lines().filter_map(|line| Rule::parse(dir, line)));
+    }
+
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&rule.base) else {
+                continue;
+            };
+            if rule.glob.is_match(relative) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+impl Rule {
+    fn parse(base: &Path, line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, pattern) = match line.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, line),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+        // A pattern with a slash is relative to the `.gitignore`; others match at any depth
+        let pattern = match pattern.strip_prefix('/') {
+            Some(anchored) => anchored.to_string(),
+            None if pattern.contains('/') => pattern.to_string(),
+            None => format!("**/{}", pattern),
+        };
+
+        let glob = GlobBuilder::new(&pattern)
+            .literal_separator(true)
+            .build()
+            .ok()?
+            .compile_matcher();
+        Some(Self {
+            base: base.to_path_buf(),
+            glob,
+            negated,
+            dir_only,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_gitignore_rules() {
+        let root = TempDir::new("gitignore");
+        std::fs::create_dir_all(root.join("locales")).unwrap();
+        std::fs::write(
+            root.join(".gitignore"),
+            "# build output\nnode_modules/\n*.tmp.json\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("locales/.gitignore"),
+            "/generated\n!keep.tmp.json\n",
+        )
+        .unwrap();
+
+        let gitignore = Gitignore::for_dir(&root, &root.join("locales"));
+        let ignored = |path: &str, is_dir: bool| gitignore.is_ignored(&root.join(path), is_dir);
+
+        assert!(ignored("locales/node_modules", true));
+        assert!(!ignored("locales/node_modules", false));
+        assert!(ignored("locales/en/draft.tmp.json", false));
+        assert!(!ignored("locales/keep.tmp.json", false));
+        assert!(ignored("locales/generated", true));
+        assert!(!ignored("locales/en/generated", true));
+        assert!(!ignored("locales/en.json", false));
+    }
+}
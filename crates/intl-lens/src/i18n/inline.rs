@@ -0,0 +1,459 @@
+use super::extractor::argument_start;
+use super::language::LanguageFamily;
+use super::lexer::{self, SourceScan, Token, TokenKind};
+use super::parser::TranslationParser;
+
+/// A message defined in a source file rather than a locale file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InlineMessage {
+    pub locale: String,
+    pub key: String,
+    pub value: String,
+    /// Zero-based line of the message in the source file.
+    pub line: usize,
+    /// The note for translators, such as a react-intl `description`.
+    pub description: Option<String>,
+}
+
+/// Messages from vue-i18n `<i18n>` custom blocks of a single-file component. Blocks
+/// are JSON (the default, JSON5 is read the same way) or YAML, keyed by locale unless
+/// the block names its `locale`. Blocks loading an external `src` are skipped.
+pub fn component_messages(content: &str) -> Vec<InlineMessage> {
+    let mut messages = Vec::new();
+    let mut from = 0;
+
+    while let Some(open) = find_block_start(content, from) {
+        let Some(tag_end) = content[open..].find('>').map(|pos| open + pos) else {
+            break;
+        };
+        let body_start = tag_end + 1;
+        let body_end = content[body_start..]
+            .find("</i18n>")
+            .map_or(content.len(), |pos| body_start + pos);
+        from = body_end;
+
+        let tag = &content[open..tag_end];
+        if tag_attribute(tag, "src").is_some() {
+            continue;
+        }
+        let locale = tag_attribute(tag, "locale");
+
+        match tag_attribute(tag, "lang").as_deref() {
+            Some("yaml" | "yml") => {
+                yaml_block(content, body_start..body_end, locale, &mut messages)
+            }
+            _ => json_block(content, body_start..body_end, locale, &mut messages),
+        }
+    }
+
+    messages
+}
+
+/// Messages registered with svelte-i18n's `addMessages("en", { ... })` object literals.
+pub fn registered_messages(content: &str, family: Option<LanguageFamily>) -> Vec<InlineMessage> {
+    let scan = lexer::scan(content, family);
+    let tokens = &scan.tokens;
+    let mut messages = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        if token.ident() != Some("addMessages")
+            || !tokens.get(index + 1).is_some_and(|t| t.is_punct('('))
+        {
+            continue;
+        }
+        let paren = index + 1;
+        let Some(locale) = argument_start(tokens, paren, 0)
+            .and_then(|arg| tokens[arg].static_string())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let Some(object) = argument_start(tokens, paren, 1) else {
+            continue;
+        };
+
+        let mut entries = Vec::new();
+        object_entries(tokens, object, "", &mut entries);
+        messages.extend(
+            entries
+                .into_iter()
+                .map(|(key, value, offset)| InlineMessage {
+                    locale: locale.clone(),
+                    key,
+                    value,
+                    line: line_at(content, offset),
+                    description: None,
+                }),
+        );
+    }
+
+    messages
+}
+
+/// react-intl message descriptors from `defineMessages({ key: { id, defaultMessage } })`
+/// and `defineMessage({ id, defaultMessage })`, as `locale` messages keyed by `id`.
+pub fn default_messages(
+    content: &str,
+    family: Option<LanguageFamily>,
+    locale: &str,
+) -> Vec<InlineMessage> {
+    let scan = lexer::scan(content, family);
+    let tokens = &scan.tokens;
+    let mut messages = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        if !matches!(token.ident(), Some("defineMessages" | "defineMessage"))
+            || !tokens.get(index + 1).is_some_and(|t| t.is_punct('('))
+        {
+            continue;
+        }
+        let Some(object) = argument_start(tokens, index + 1, 0) else {
+            continue;
+        };
+
+        let mut entries = Vec::new();
+        object_entries(tokens, object, "", &mut entries);
+
+        // Pair up the `id`, `defaultMessage` and `description` fields of each descriptor
+        type Descriptor<'a> = (
+            &'a str,
+            Option<&'a str>,
+            Option<(&'a str, usize)>,
+            Option<&'a str>,
+        );
+        let mut descriptors: Vec<Descriptor> = Vec::new();
+        for (key, value, offset) in &entries {
+            let (descriptor, field) = key.rsplit_once('.').unwrap_or(("", key));
+            let position = match descriptors.iter().position(|(d, ..)| *d == descriptor) {
+                Some(position) => position,
+                None => {
+                    descriptors.push((descriptor, None, None, None));
+                    descriptors.len() - 1
+                }
+            };
+            match field {
+                "id" => descriptors[position].1 = Some(value),
+                "defaultMessage" => descriptors[position].2 = Some((value, *offset)),
+                "description" => descriptors[position].3 = Some(value),
+                _ => {}
+            }
+        }
+
+        for (_, id, default, description) in descriptors {
+            if let (Some(id), Some((value, offset))) = (id, default) {
+                messages.push(InlineMessage {
+                    locale: locale.to_string(),
+                    key: id.to_string(),
+                    value: value.to_string(),
+                    line: line_at(content, offset),
+                    description: description.map(str::to_string),
+                });
+            }
+        }
+    }
+
+    messages
+}
+
+fn find_block_start(content: &str, from: usize) -> Option<usize> {
+    let mut search = from;
+    while let Some(pos) = content.get(search..)?.find("<i18n") {
+        let open = search + pos;
+        let next = content[open + 5..].chars().next();
+        if next.is_some_and(|c| c.is_whitespace() || c == '>') {
+            return Some(open);
+        }
+        search = open + 5;
+    }
+    None
+}
+
+fn tag_attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(pos) = rest.find(name) {
+        let before = rest[..pos].chars().next_back();
+        let after = rest[pos + name.len()..].trim_start();
+        rest = &rest[pos + name.len()..];
+        if !before.is_some_and(char::is_whitespace) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)?;
+        return Some(value[1..1 + end].to_string());
+    }
+    None
+}
+
+fn json_block(
+    content: &str,
+    body: std::ops::Range<usize>,
+    locale: Option<String>,
+    messages: &mut Vec<InlineMessage>,
+) {
+    let mut scan = SourceScan::default();
+    lexer::tokenize(content, body, LanguageFamily::JavaScript, &mut scan);
+    let tokens = &scan.tokens;
+    if !tokens.first().is_some_and(|t| t.is_punct('{')) {
+        return;
+    }
+
+    let mut entries = Vec::new();
+    object_entries(tokens, 0, "", &mut entries);
+
+    for (key, value, offset) in entries {
+        let (locale, key) = match &locale {
+            Some(locale) => (locale.clone(), key),
+            None => match key.split_once('.') {
+                Some((locale, key)) => (locale.to_string(), key.to_string()),
+                None => continue,
+            },
+        };
+        messages.push(InlineMessage {
+            locale,
+            key,
+            value,
+            line: line_at(content, offset),
+            description: None,
+        });
+    }
+}
+
+fn yaml_block(
+    content: &str,
+    body: std::ops::Range<usize>,
+    locale: Option<String>,
+    messages: &mut Vec<InlineMessage>,
+) {
+    let text = &content[body.clone()];
+    let first_line = line_at(content, body.start);
+
+    let single_locale = locale.is_some();
+    let roots = match locale {
+        Some(locale) => {
+            TranslationParser::parse_yaml(text).map(|translations| vec![(locale, translations)])
+        }
+        None => TranslationParser::parse_yaml_roots(text),
+    };
+    let Ok(roots) = roots else {
+        return;
+    };
+
+    for (locale, translations) in roots {
+        // With several locales in the block, look for keys below the locale's own line
+        let section = if single_locale {
+            0
+        } else {
+            locale_line(text, &locale).unwrap_or(0)
+        };
+        let mut entries: Vec<_> = translations.into_iter().collect();
+        entries.sort();
+        for (key, value) in entries {
+            let last = key.rsplit('.').next().unwrap_or(&key);
+            let line = text
+                .lines()
+                .enumerate()
+                .skip(section)
+                .find(|(_, line)| {
+                    let line = line.trim_start();
+                    line.starts_with(&format!("{}:", last))
+                        || line.starts_with(&format!("\"{}\":", last))
+                        || line.starts_with(&format!("'{}':", last))
+                })
+                .map_or(section, |(line, _)| line);
+            messages.push(InlineMessage {
+                locale: locale.clone(),
+                key,
+                value,
+                line: first_line + line,
+                description: None,
+            });
+        }
+    }
+}
+
+/// The unindented `en:` line starting a locale's section.
+fn locale_line(text: &str, locale: &str) -> Option<usize> {
+    text.lines().position(|line| {
+        !line.starts_with(char::is_whitespace)
+            && line
+                .trim_end()
+                .trim_end_matches(':')
+                .trim_matches(['"', '\''])
+                == locale
+    })
+}
+
+/// Flatten the object literal whose `{` is at `brace` into `(key, value, offset)`
+/// entries for its string values. Returns the index after the closing brace.
+fn object_entries(
+    tokens: &[Token],
+    brace: usize,
+    prefix: &str,
+    entries: &mut Vec<(String, String, usize)>,
+) -> usize {
+    if !tokens.get(brace).is_some_and(|t| t.is_punct('{')) {
+        return brace + 1;
+    }
+
+    let mut index = brace + 1;
+    while let Some(token) = tokens.get(index) {
+        if token.is_punct('}') {
+            return index + 1;
+        }
+        if token.is_punct(',') {
+            index += 1;
+            continue;
+        }
+
+        let name = match &token.kind {
+            TokenKind::Ident(name) => Some(name.as_str()),
+            _ => token.static_string(),
+        };
+        let has_colon = tokens.get(index + 1).is_some_and(|t| t.is_punct(':'));
+        let (Some(name), true) = (name, has_colon) else {
+            index = skip_value(tokens, index + 1);
+            continue;
+        };
+
+        let key = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        let value = index + 2;
+        let ends_value = tokens
+            .get(value + 1)
+            .is_some_and(|t| t.is_punct(',') || t.is_punct('}'));
+
+        match tokens.get(value) {
+            Some(token) if token.is_punct('{') => {
+                index = object_entries(tokens, value, &key, entries);
+            }
+            Some(token) if ends_value && token.static_string().is_some() => {
+                let text = token.static_string().unwrap_or_default().to_string();
+                entries.push((key, text, token.start));
+                index = value + 1;
+            }
+            _ => index = skip_value(tokens, value),
+        }
+    }
+
+    tokens.len()
+}
+
+/// Index of the `,` or `}` ending the value starting at `index`.
+pub(super) fn skip_value(tokens: &[Token], index: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(index) {
+        match token.kind {
+            TokenKind::Punct('(' | '[' | '{') => depth += 1,
+            TokenKind::Punct('}') if depth == 0 => return i,
+            TokenKind::Punct(')' | ']' | '}') => depth = depth.saturating_sub(1),
+            TokenKind::Punct(',') if depth == 0 => return i,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(messages: Vec<InlineMessage>) -> Vec<(String, String, String, usize)> {
+        messages
+            .into_iter()
+            .map(|m| (m.locale, m.key, m.value, m.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_vue_json_block() {
+        let content = r#"<template><p>{{ t('hello') }}</p></template>
+<i18n>
+{
+  "en": { "hello": "Hello", "nav": { "home": "Home" } },
+  "fr": { "hello": "Bonjour" }
+}
+</i18n>
+<i18n locale="de" lang="json5">
+{ hello: 'Hallo', }
+</i18n>
+<i18n src="./messages.json"></i18n>"#;
+        assert_eq!(
+            entries(component_messages(content)),
+            vec![
+                ("en".into(), "hello".into(), "Hello".into(), 3),
+                ("en".into(), "nav.home".into(), "Home".into(), 3),
+                ("fr".into(), "hello".into(), "Bonjour".into(), 4),
+                ("de".into(), "hello".into(), "Hallo".into(), 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vue_yaml_block() {
+        let content = "<template></template>\n<i18n lang=\"yaml\">\nen:\n  hello: Hello\nfr:\n  hello: Bonjour\n</i18n>\n";
+        assert_eq!(
+            entries(component_messages(content)),
+            vec![
+                ("en".into(), "hello".into(), "Hello".into(), 3),
+                ("fr".into(), "hello".into(), "Bonjour".into(), 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_react_intl_default_messages() {
+        let content = r#"import { defineMessage, defineMessages } from "react-intl";
+const messages = defineMessages({
+  greeting: {
+    id: "app.greeting",
+    defaultMessage: "Hello",
+    description: "Home page greeting",
+  },
+  missing: { id: "app.missing" },
+});
+const title = defineMessage({ id: "app.title", defaultMessage: `Title` });"#;
+        let messages = default_messages(content, Some(LanguageFamily::JavaScript), "en");
+        assert_eq!(
+            messages[0].description.as_deref(),
+            Some("Home page greeting")
+        );
+        assert_eq!(
+            entries(messages),
+            vec![
+                ("en".into(), "app.greeting".into(), "Hello".into(), 4),
+                ("en".into(), "app.title".into(), "Title".into(), 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_svelte_add_messages() {
+        let content = r#"import { addMessages } from "svelte-i18n";
+addMessages("en", {
+  page: { title: "Home", subtitle: `Welcome` },
+  count: computeCount(),
+});
+addMessages("fr", fr);"#;
+        assert_eq!(
+            entries(registered_messages(
+                content,
+                Some(LanguageFamily::JavaScript)
+            )),
+            vec![
+                ("en".into(), "page.title".into(), "Home".into(), 2),
+                ("en".into(), "page.subtitle".into(), "Welcome".into(), 2),
+            ]
+        );
+    }
+}
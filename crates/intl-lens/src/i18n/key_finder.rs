@@ -1,64 +1,303 @@
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use regex::Regex;
 
+use super::extractor::{self, ExtractedKey, Preset};
+use super::key_pattern::KeyPattern;
+use super::language::{LanguageFamily, SourceLanguage};
+use super::lexer::TokenKind;
+use super::{lazy_lookup, lexer};
+use crate::config::I18nConfig;
+
 #[derive(Debug, Clone)]
 pub struct FoundKey {
+    /// The key, or for dynamic keys its pattern with `*` wildcards (`errors.*`).
     pub key: String,
+    /// Set for keys assembled at runtime from template literals or concatenation.
+    pub pattern: Option<KeyPattern>,
+    /// Prefix contributed by a scoped translation function (`keyPrefix`, next-intl
+    /// namespaces, Transloco `read`); already included in `key`.
+    pub key_prefix: Option<String>,
     pub start_offset: usize,
     pub line: usize,
     pub start_char: usize,
     pub end_char: usize,
 }
 
+/// Regexes that only run for documents matching a language id or path glob.
+struct ScopedPatterns {
+    language_ids: Vec<String>,
+    files: GlobSet,
+    patterns: Vec<Regex>,
+}
+
+impl ScopedPatterns {
+    fn applies_to(&self, source: &SourceLanguage, root: Option<&Path>) -> bool {
+        let language_matches = source
+            .language_id
+            .as_ref()
+            .is_some_and(|id| self.language_ids.contains(id));
+
+        let path_matches = source.path.as_deref().is_some_and(|path| {
+            let relative = root
+                .and_then(|root| path.strip_prefix(root).ok())
+                .unwrap_or(path);
+            self.files.is_match(relative)
+        });
+
+        language_matches || path_matches
+    }
+}
+
 pub struct KeyFinder {
+    /// Framework presets whose APIs the syntax-aware extractor recognises.
+    presets: Vec<&'static Preset>,
+    /// User regexes from `functionPatterns` and `extraFunctionPatterns`, run over the
+    /// raw text and skipping matches inside comments.
     patterns: Vec<Regex>,
+    scoped_patterns: Vec<ScopedPatterns>,
+    /// Generated Dart localization classes beyond the presets' own, from `l10n.yaml`.
+    localization_classes: Vec<String>,
+    root: Option<PathBuf>,
 }
 
 impl KeyFinder {
+    /// A finder with every built-in preset plus the given regex patterns.
     pub fn new(patterns: &[String]) -> Self {
-        let compiled_patterns: Vec<Regex> =
-            patterns.iter().filter_map(|p| Regex::new(p).ok()).collect();
-
         Self {
-            patterns: compiled_patterns,
+            presets: extractor::PRESETS.iter().collect(),
+            patterns: compile_patterns(patterns),
+            scoped_patterns: Vec::new(),
+            localization_classes: Vec::new(),
+            root: None,
         }
     }
 
+    /// Build the finder described by the config. `functionPatterns` replaces the
+    /// built-in presets unless `presets` names them explicitly, while
+    /// `extraFunctionPatterns` always adds to whatever presets are active.
+    pub fn from_config(config: &I18nConfig, root: &Path) -> Self {
+        let patterns: Vec<String> = config
+            .function_patterns
+            .iter()
+            .flatten()
+            .chain(&config.extra_function_patterns)
+            .cloned()
+            .collect();
+        let mut finder = Self::new(&patterns);
+        finder.root = Some(root.to_path_buf());
+        finder.localization_classes = config.localization_classes.clone();
+
+        if config.presets.is_none() && config.function_patterns.is_some() {
+            finder.presets.clear();
+        }
+
+        if let Some(names) = &config.presets {
+            finder.presets = names
+                .iter()
+                .filter_map(|name| {
+                    let preset = extractor::preset(name);
+                    if preset.is_none() {
+                        tracing::warn!("Unknown framework preset '{}'", name);
+                    }
+                    preset
+                })
+                .collect();
+        }
+
+        finder.scoped_patterns = config
+            .scoped_function_patterns
+            .iter()
+            .map(|scoped| {
+                let mut files = GlobSetBuilder::new();
+                for pattern in &scoped.files {
+                    match Glob::new(pattern) {
+                        Ok(glob) => {
+                            files.add(glob);
+                        }
+                        Err(err) => tracing::warn!("Invalid glob '{}': {}", pattern, err),
+                    }
+                }
+
+                ScopedPatterns {
+                    language_ids: scoped.language_ids.clone(),
+                    files: files.build().unwrap_or_else(|_| GlobSet::empty()),
+                    patterns: compile_patterns(&scoped.patterns),
+                }
+            })
+            .collect();
+
+        finder
+    }
+
+    /// Find keys treating the document as JavaScript-like code.
+    #[cfg(test)]
     pub fn find_keys(&self, content: &str) -> Vec<FoundKey> {
-        let mut found_keys = Vec::new();
+        self.find_keys_in(content, &SourceLanguage::default())
+    }
 
-        for pattern in &self.patterns {
+    pub fn find_keys_in(&self, content: &str, source: &SourceLanguage) -> Vec<FoundKey> {
+        let family = source.family();
+        let scan = lexer::scan(content, family);
+
+        let presets = self.presets_for(family);
+        let mut extracted = extractor::extract_keys(content, &scan, &presets);
+        extracted.extend(extractor::localization_members(
+            &scan.tokens,
+            &presets,
+            &self.localization_classes,
+        ));
+        let lazy_lookup = presets.iter().any(|preset| preset.lazy_lookup);
+
+        let scoped = self
+            .scoped_patterns
+            .iter()
+            .filter(|scoped| scoped.applies_to(source, self.root.as_deref()))
+            .flat_map(|scoped| &scoped.patterns);
+
+        for pattern in self.patterns.iter().chain(scoped) {
             for cap in pattern.captures_iter(content) {
                 if let Some(key_match) = cap.get(1) {
-                    let key = key_match.as_str().to_string();
-                    let start_offset = key_match.start();
-                    let end_offset = key_match.end();
+                    if !scan.in_comment(key_match.start()) {
+                        extracted.push(key_match.range().into());
+                    }
+                }
+            }
+        }
+
+        let mut found_keys: Vec<FoundKey> = extracted
+            .into_iter()
+            .filter(|extracted| !extracted.range.is_empty())
+            .map(
+                |ExtractedKey {
+                     mut range,
+                     key,
+                     pattern,
+                     prefix,
+                 }| {
+                    let mut text = key.unwrap_or_else(|| content[range.clone()].to_string());
+                    let relative = pattern.as_ref().map_or(text.starts_with('.'), |p| {
+                        p.static_prefix().starts_with('.')
+                    });
+                    let prefix = match (prefix, &source.path) {
+                        (None, Some(path)) if lazy_lookup && relative => {
+                            let scope = lazy_lookup::scope_for(path, content, range.start);
+                            // The leading dot stays in the source, outside the key range
+                            if scope.is_some() {
+                                text.remove(0);
+                                range.start += 1;
+                            }
+                            scope
+                        }
+                        (prefix, _) => prefix,
+                    };
 
                     let (line, start_char, end_char) =
-                        Self::offset_to_position(content, start_offset, end_offset);
+                        Self::offset_to_position(content, range.start, range.end);
+
+                    let pattern = match (pattern, &prefix) {
+                        (Some(pattern), Some(prefix)) => Some(pattern.with_prefix(prefix)),
+                        (pattern, _) => pattern,
+                    };
+                    let key = match (&pattern, &prefix) {
+                        (Some(pattern), _) => pattern.to_string(),
+                        (None, Some(prefix)) => format!("{}.{}", prefix, text),
+                        (None, None) => text,
+                    };
 
-                    found_keys.push(FoundKey {
+                    FoundKey {
                         key,
-                        start_offset,
+                        pattern,
+                        key_prefix: prefix,
+                        start_offset: range.start,
                         line,
                         start_char,
                         end_char,
-                    });
-                }
-            }
-        }
+                    }
+                },
+            )
+            .collect();
 
         found_keys.sort_by_key(|k| k.start_offset);
         found_keys.dedup_by(|a, b| a.start_offset == b.start_offset);
         found_keys
     }
 
+    /// Key prefix of the scoped translation call around `offset`, if any.
+    pub fn prefix_at(
+        &self,
+        content: &str,
+        source: &SourceLanguage,
+        offset: usize,
+    ) -> Option<String> {
+        let family = source.family();
+        let scan = lexer::scan(content, family);
+        let presets = self.presets_for(family);
+        extractor::prefix_at(content, &scan, &presets, offset)
+    }
+
+    /// The key called as a message method whose argument list surrounds `offset`, such
+    /// as `l10n.greeting(name, |)`, with the index of the argument at `offset`.
+    pub fn call_at(
+        &self,
+        content: &str,
+        source: &SourceLanguage,
+        offset: usize,
+    ) -> Option<(FoundKey, usize)> {
+        let scan = lexer::scan(content, source.family());
+        let tokens = &scan.tokens;
+
+        let mut keys = self.find_keys_in(content, source);
+        keys.retain(|key| key.start_offset < offset);
+        keys.into_iter().rev().find_map(|key| {
+            let member = tokens.iter().position(|t| t.start == key.start_offset)?;
+            let paren = tokens.get(member + 1).filter(|t| t.is_punct('('))?;
+            if paren.end > offset {
+                return None;
+            }
+
+            let mut depth = 0usize;
+            let mut argument = 0;
+            for token in tokens[member + 2..].iter().take_while(|t| t.start < offset) {
+                match token.kind {
+                    TokenKind::Punct('(' | '[' | '{') => depth += 1,
+                    TokenKind::Punct(')' | ']' | '}') if depth == 0 => return None,
+                    TokenKind::Punct(')' | ']' | '}') => depth -= 1,
+                    TokenKind::Punct(',') if depth == 0 => argument += 1,
+                    _ => {}
+                }
+            }
+            Some((key, argument))
+        })
+    }
+
+    fn presets_for(&self, family: Option<LanguageFamily>) -> Vec<&'static Preset> {
+        self.presets
+            .iter()
+            .copied()
+            .filter(|preset| preset.applies_to(family))
+            .collect()
+    }
+
+    #[cfg(test)]
     pub fn find_key_at_position(
         &self,
         content: &str,
         line: usize,
         character: usize,
     ) -> Option<FoundKey> {
-        let keys = self.find_keys(content);
+        self.find_key_at_position_in(content, &SourceLanguage::default(), line, character)
+    }
+
+    pub fn find_key_at_position_in(
+        &self,
+        content: &str,
+        source: &SourceLanguage,
+        line: usize,
+        character: usize,
+    ) -> Option<FoundKey> {
+        let keys = self.find_keys_in(content, source);
 
         keys.into_iter()
             .find(|k| k.line == line && character >= k.start_char && character <= k.end_char)
@@ -89,49 +328,33 @@ impl KeyFinder {
     }
 }
 
+fn compile_patterns(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter_map(|pattern| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(err) => {
+                tracing::warn!("Invalid function pattern '{}': {}", pattern, err);
+                None
+            }
+        })
+        .collect()
+}
+
 impl Default for KeyFinder {
     fn default() -> Self {
-        Self::new(&default_patterns())
+        Self::new(&[])
     }
 }
 
-fn default_patterns() -> Vec<String> {
-    vec![
-        // JavaScript/TypeScript patterns
-        // Match t() but not .post(), .get(), .put(), .delete(), etc.
-        r#"(?:^|[^\w.])t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"i18n\.t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"\$t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"\$tc\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"\$te\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"useI18n\s*\(\s*\)\s*.*?\.t\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"formatMessage\s*\(\s*\{\s*id:\s*["']([^"']+)["']"#.to_string(),
-        r#"<Trans\s+i18nKey\s*=\s*["']([^"']+)["']"#.to_string(),
-        // Svelte patterns (svelte-i18n)
-        r#"\$_\s*\(\s*["']([^"']+)["']"#.to_string(),
-        r#"\$format\s*\(\s*["']([^"']+)["']"#.to_string(),
-        // Flutter/Dart patterns - easy_localization
-        r#"['"]([^'"]+)['"]\s*\.tr\("#.to_string(),
-        r#"['"]([^'"]+)['"]\s*\.tr\(\)"#.to_string(),
-        r#"(?:^|[^\w.])tr\(\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"context\.tr\(\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"['"]([^'"]+)['"]\s*\.plural\("#.to_string(),
-        // Flutter/Dart patterns - flutter_i18n
-        r#"FlutterI18n\.translate\([^,]+,\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"FlutterI18n\.plural\([^,]+,\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"I18nText\(\s*['"]([^'"]+)['"]"#.to_string(),
-        r#"I18nPlural\(\s*['"]([^'"]+)['"]"#.to_string(),
-        // Flutter/Dart patterns - GetX
-        r#"['"]([^'"]+)['"]\s*\.tr(?:\s|$|\)|,)"#.to_string(),
-        r#"['"]([^'"]+)['"]\s*\.trParams\("#.to_string(),
-        r#"['"]([^'"]+)['"]\s*\.trPlural\("#.to_string(),
-    ]
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn in_file(name: &str) -> SourceLanguage {
+        SourceLanguage::new(None, Some(PathBuf::from(name)))
+    }
+
     #[test]
     fn test_find_t_function() {
         let finder = KeyFinder::default();
@@ -380,4 +603,461 @@ mod tests {
         assert_eq!(keys.len(), 1);
         assert_eq!(keys[0].key, "welcome.message");
     }
+
+    #[test]
+    fn test_skips_keys_in_comments() {
+        let finder = KeyFinder::default();
+        let content = r#"
+            // t("commented.out")
+            /* const old = t("old.key"); */
+            const msg = t("live.key");
+        "#;
+        let keys = finder.find_keys(content);
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "live.key");
+    }
+
+    #[test]
+    fn test_find_multiline_call_and_template_literal() {
+        let finder = KeyFinder::default();
+        let content = "t(\n  \"multi.line\"\n)\nt(`template.key`)\nt(`dynamic.${x}`)";
+        let keys = finder.find_keys(content);
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys[0].key, "multi.line");
+        assert_eq!(keys[0].line, 1);
+        assert_eq!(keys[0].start_char, 3);
+        assert_eq!(keys[1].key, "template.key");
+        assert!(keys[1].pattern.is_none());
+        assert_eq!(keys[2].key, "dynamic.*");
+        assert!(keys[2].pattern.is_some());
+    }
+
+    #[test]
+    fn test_find_keys_in_vue_sfc() {
+        let finder = KeyFinder::default();
+        let content = r#"<template>
+  <p>Don't {{ $t('template.key') }}</p>
+  <!-- {{ $t('commented.key') }} -->
+  <button :title="t('attr.key')">x</button>
+  <i18n-t keypath="component.key" />
+</template>
+<script setup>
+const msg = t('script.key');
+</script>"#;
+        let keys: Vec<_> = finder
+            .find_keys_in(content, &in_file("App.vue"))
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec!["template.key", "attr.key", "component.key", "script.key"]
+        );
+    }
+
+    #[test]
+    fn test_find_keys_in_blade_and_php() {
+        let finder = KeyFinder::default();
+        let blade = "{{-- @lang('old.key') --}}\n@lang('auth.failed') {{ __('auth.throttle') }}";
+        let keys: Vec<_> = finder
+            .find_keys_in(blade, &in_file("welcome.blade.php"))
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["auth.failed", "auth.throttle"]);
+
+        let php =
+            "<?php\n# __('old.key')\nLang::get('messages.a'); $translator->trans('messages.b');";
+        let keys: Vec<_> = finder
+            .find_keys_in(php, &in_file("routes.php"))
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["messages.a", "messages.b"]);
+    }
+
+    #[test]
+    fn test_find_angular_pipe_and_service() {
+        let finder = KeyFinder::default();
+        let content =
+            r#"<h1 [title]="'page.title' | translate">{{ 'page.heading' | translate }}</h1>"#;
+        let keys: Vec<_> = finder
+            .find_keys_in(content, &in_file("app.component.html"))
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["page.title", "page.heading"]);
+
+        let keys = finder.find_keys("this.translateService.instant('app.name')");
+        assert_eq!(keys[0].key, "app.name");
+    }
+
+    #[test]
+    fn test_custom_patterns_are_a_fallback() {
+        let patterns = vec![r#"myTranslate\(\s*["']([^"']+)["']"#.to_string()];
+        let finder = KeyFinder::new(&patterns);
+
+        let content = "// myTranslate('skipped')\nmyTranslate('custom.key'); t('builtin.key');";
+        let keys: Vec<_> = finder
+            .find_keys(content)
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["custom.key", "builtin.key"]);
+    }
+
+    #[test]
+    fn test_presets_are_scoped_by_language() {
+        let finder = KeyFinder::default();
+        let content = "const a = 'label'.tr; const b = __('auth.failed'); t('app.title');";
+
+        let keys: Vec<_> = finder
+            .find_keys_in(
+                content,
+                &SourceLanguage::new(Some("typescript".into()), None),
+            )
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["app.title"]);
+
+        let dart = "Text('hello'.tr); t('not.dart');";
+        let keys: Vec<_> = finder
+            .find_keys_in(dart, &in_file("lib/main.dart"))
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["hello"]);
+    }
+
+    #[test]
+    fn test_presets_enabled_by_name() {
+        let config = I18nConfig {
+            presets: Some(vec!["ngx-translate".to_string()]),
+            ..I18nConfig::default()
+        };
+        let finder = KeyFinder::from_config(&config, Path::new("/project"));
+
+        let content = "t('skipped'); this.translateService.instant('kept');";
+        let keys: Vec<_> = finder
+            .find_keys_in(content, &in_file("/project/src/app.ts"))
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["kept"]);
+    }
+
+    #[test]
+    fn test_scoped_function_patterns() {
+        let config = I18nConfig {
+            scoped_function_patterns: vec![crate::config::ScopedFunctionPatterns {
+                language_ids: vec!["typescriptreact".to_string()],
+                files: vec!["legacy/**".to_string()],
+                patterns: vec![r#"loc\(\s*["']([^"']+)["']"#.to_string()],
+            }],
+            ..I18nConfig::default()
+        };
+        let finder = KeyFinder::from_config(&config, Path::new("/project"));
+        let content = "loc('scoped.key')";
+
+        let by_language = SourceLanguage::new(
+            Some("typescriptreact".into()),
+            Some(PathBuf::from("/project/src/App.tsx")),
+        );
+        assert_eq!(finder.find_keys_in(content, &by_language).len(), 1);
+        assert_eq!(
+            finder
+                .find_keys_in(content, &in_file("/project/legacy/old.js"))
+                .len(),
+            1
+        );
+        assert!(finder
+            .find_keys_in(content, &in_file("/project/src/app.js"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_function_patterns_replace_and_extra_patterns_extend() {
+        let custom = vec![r#"loc\(\s*["']([^"']+)["']"#.to_string()];
+        let content = "loc('custom.key'); t('builtin.key');";
+        let keys = |config: &I18nConfig| -> Vec<String> {
+            KeyFinder::from_config(config, Path::new("/project"))
+                .find_keys(content)
+                .into_iter()
+                .map(|k| k.key)
+                .collect()
+        };
+
+        let replaced = I18nConfig {
+            function_patterns: Some(custom.clone()),
+            ..I18nConfig::default()
+        };
+        assert_eq!(keys(&replaced), vec!["custom.key"]);
+
+        let extended = I18nConfig {
+            extra_function_patterns: custom.clone(),
+            ..I18nConfig::default()
+        };
+        assert_eq!(keys(&extended), vec!["custom.key", "builtin.key"]);
+
+        let replaced_with_presets = I18nConfig {
+            function_patterns: Some(custom),
+            presets: Some(vec!["react-i18next".to_string()]),
+            ..I18nConfig::default()
+        };
+        assert_eq!(
+            keys(&replaced_with_presets),
+            vec!["custom.key", "builtin.key"]
+        );
+    }
+
+    #[test]
+    fn test_find_concatenated_keys() {
+        let finder = KeyFinder::default();
+        let content = r#"
+            t("status." + state);
+            t("status." + state + ".label", { count });
+            t(prefix + ".title");
+            t(key);
+            t(`${a}${b}`);
+        "#;
+        let keys: Vec<_> = finder
+            .find_keys(content)
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["status.*", "status.*.label", "*.title"]);
+
+        let found = finder.find_keys(r#"t("errors." + code)"#);
+        let pattern = found[0].pattern.as_ref().unwrap();
+        assert!(pattern.matches("errors.not_found"));
+        assert_eq!(found[0].start_char, 2);
+        assert_eq!(found[0].end_char, 18);
+    }
+
+    #[test]
+    fn test_scoped_translation_functions() {
+        let finder = KeyFinder::default();
+        let content = r#"
+            function Profile() {
+                const { t } = useTranslation("account", { keyPrefix: "settings.profile" });
+                t("title");
+                t(`fields.${name}`);
+            }
+            function Page() {
+                const t = useTranslations("Home");
+                t("hero");
+            }
+            t("global.key");
+        "#;
+        let found = finder.find_keys(content);
+        let keys: Vec<_> = found.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "settings.profile.title",
+                "settings.profile.fields.*",
+                "Home.hero",
+                "global.key"
+            ]
+        );
+        assert_eq!(found[0].key_prefix.as_deref(), Some("settings.profile"));
+        assert_eq!(&content[found[0].start_offset..][..5], "title");
+        assert!(found[3].key_prefix.is_none());
+
+        let offset = content.find("hero").unwrap();
+        let source = SourceLanguage::default();
+        assert_eq!(
+            finder.prefix_at(content, &source, offset).as_deref(),
+            Some("Home")
+        );
+    }
+
+    #[test]
+    fn test_transloco_read_scope() {
+        let finder = KeyFinder::default();
+        let content =
+            r#"<div *transloco="let t; read: 'dashboard'"><h1>{{ t('title') }}</h1></div>"#;
+        let source = SourceLanguage::new(Some("html".to_string()), None);
+        let keys: Vec<_> = finder
+            .find_keys_in(content, &source)
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["dashboard.title"]);
+    }
+
+    #[test]
+    fn test_find_lingui_messages() {
+        let finder = KeyFinder::default();
+        let content = r#"
+            const greeting = t`Hello ${name}, you have ${items.length} items`;
+            const title = t({ id: "page.title", message: "Welcome" });
+            const open = t({ id: "Open", context: "verb" });
+            const label = msg`Save`;
+            i18n._("checkout.submit");
+            <Trans id="cart.empty">Your cart is empty</Trans>;
+            <div id="main" />;
+        "#;
+        let keys: Vec<_> = finder
+            .find_keys(content)
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "Hello {name}, you have {0} items",
+                "page.title",
+                "verb\u{4}Open",
+                "Save",
+                "checkout.submit",
+                "cart.empty"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_paraglide_messages() {
+        let finder = KeyFinder::default();
+        let content = r#"
+            import * as m from "$lib/paraglide/messages.js";
+            m.hello_world();
+            m.greeting({ name });
+            const cache = new Map();
+        "#;
+        let found = finder.find_keys(content);
+        let keys: Vec<_> = found.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(keys, vec!["hello_world", "greeting"]);
+        assert_eq!(&content[found[0].start_offset..][..11], "hello_world");
+
+        // Without the import `m` is just a local
+        let keys = finder.find_keys("const m = new Map(); m.get(key);");
+        assert!(keys.is_empty());
+    }
+
+    #[test]
+    fn test_next_intl_rich_and_markup() {
+        let finder = KeyFinder::default();
+        let content = r#"
+            const t = useTranslations("About");
+            t.rich("description", { b: (chunks) => chunks });
+            t.has("optional");
+            other.rich("ignored");
+        "#;
+        let keys: Vec<_> = finder
+            .find_keys(content)
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(keys, vec!["About.description", "About.optional"]);
+    }
+
+    #[test]
+    fn test_find_rails_keys() {
+        let finder = KeyFinder::default();
+        let source = SourceLanguage::new(
+            Some("ruby".to_string()),
+            Some(PathBuf::from(
+                "/app/app/controllers/admin/users_controller.rb",
+            )),
+        );
+        let content = r#"
+            class Admin::UsersController < ApplicationController
+              def create
+                # t("commented.out")
+                flash[:notice] = t(".created")
+                I18n.t("users.welcome", name: user.name)
+                t(:title, scope: [:admin, :users])
+                t("title", :scope => "admin.users")
+                t(:missing, scope: :errors)
+              end
+            end
+        "#;
+        let keys: Vec<_> = finder
+            .find_keys_in(content, &source)
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "admin.users.create.created",
+                "users.welcome",
+                "admin.users.title",
+                "admin.users.title",
+                "errors.missing"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_flutter_localization_getters() {
+        let config = I18nConfig {
+            localization_classes: vec!["Strings".to_string()],
+            ..I18nConfig::default()
+        };
+        let finder = KeyFinder::from_config(&config, Path::new("/project"));
+        let content = r#"
+            final l10n = AppLocalizations.of(context)!;
+            final loc = context.l10n;
+            Text(AppLocalizations.of(context)!.appTitle);
+            Text(S.of(context).pageTitle);
+            Text(S.current.retry);
+            Text(context.l10n.greeting(user.name));
+            Text(l10n.itemCount(items.length));
+            Text(loc.farewell);
+            Text(Strings.of(context)?.custom);
+            Text(AppLocalizations.of(context)!.localeName);
+            Text(other.appTitle);
+        "#;
+        let keys: Vec<_> = finder
+            .find_keys_in(content, &in_file("/project/lib/home.dart"))
+            .into_iter()
+            .map(|k| k.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "appTitle",
+                "pageTitle",
+                "retry",
+                "greeting",
+                "itemCount",
+                "farewell",
+                "custom"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_message_call_at_offset() {
+        let finder = KeyFinder::default();
+        let source = in_file("lib/home.dart");
+        let content = "final l10n = AppLocalizations.of(context)!;\n\
+                       Text(l10n.greeting(user.name, items(a, b), ));";
+        let at = |needle: &str| content.find(needle).unwrap();
+
+        let (key, argument) = finder.call_at(content, &source, at("user")).unwrap();
+        assert_eq!((key.key.as_str(), argument), ("greeting", 0));
+        assert_eq!(finder.call_at(content, &source, at("b)")).unwrap().1, 1);
+        assert_eq!(finder.call_at(content, &source, at(" ))")).unwrap().1, 2);
+        assert!(finder.call_at(content, &source, at("context")).is_none());
+        assert!(finder.call_at(content, &source, content.len()).is_none());
+    }
+
+    #[test]
+    fn test_find_erb_lazy_lookup() {
+        let finder = KeyFinder::default();
+        let source =
+            SourceLanguage::new(None, Some(PathBuf::from("app/views/users/_form.html.erb")));
+        let content = r#"<h1><%= t(".heading") %></h1>
+<%# t(".ignored") %>
+<p title="<%= t('users.hint') %>">Don't <%= t "no.parens" %></p>"#;
+        let found = finder.find_keys_in(content, &source);
+        let keys: Vec<_> = found.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(keys, vec!["users.form.heading", "users.hint"]);
+        assert_eq!(found[0].key_prefix.as_deref(), Some("users.form"));
+        assert_eq!(&content[found[0].start_offset..][..8], "heading\"");
+    }
 }
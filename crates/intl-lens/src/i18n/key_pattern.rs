@@ -0,0 +1,148 @@
+use std::fmt;
+
+/// A key assembled at runtime, such as ``t(`errors.${code}`)`` or `t("status." + state)`.
+/// The static parts must appear in order; anything can stand between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPattern {
+    /// Static text around each dynamic segment; always one more than the segments.
+    parts: Vec<String>,
+}
+
+impl KeyPattern {
+    /// Build a pattern from alternating static text, where `None` marks a dynamic
+    /// segment. Adjacent static texts are joined and adjacent dynamic segments merge.
+    /// Returns `None` when the key has no dynamic segment or no static text at all.
+    pub fn from_segments<I>(segments: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Option<String>>,
+    {
+        let mut parts = vec![String::new()];
+        let mut dynamic = false;
+
+        for segment in segments {
+            match segment {
+                Some(text) => parts.last_mut().unwrap().push_str(&text),
+                None => {
+                    if !dynamic || !parts.last().unwrap().is_empty() {
+                        parts.push(String::new());
+                    }
+                    dynamic = true;
+                }
+            }
+        }
+
+        if parts.len() < 2 || parts.iter().all(|part| part.is_empty()) {
+            return None;
+        }
+
+        Some(Self { parts })
+    }
+
+    /// Parse a template literal body such as `errors.${code}.title`.
+    pub fn from_template(raw: &str) -> Option<Self> {
+        Self::from_segments(template_segments(raw))
+    }
+
+    /// The pattern under a scoped translation function's key prefix. A relative
+    /// pattern (`.status.*`) already starts with the separator.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        let first = self.parts[0].strip_prefix('.').unwrap_or(&self.parts[0]);
+        self.parts[0] = format!("{}.{}", prefix, first);
+        self
+    }
+
+    /// The text every matching key starts with.
+    pub fn static_prefix(&self) -> &str {
+        &self.parts[0]
+    }
+
+    pub fn matches(&self, key: &str) -> bool {
+        let (first, rest) = self.parts.split_first().expect("at least two parts");
+        let (last, middle) = rest.split_last().expect("at least two parts");
+
+        let Some(mut remaining) = key.strip_prefix(first.as_str()) else {
+            return false;
+        };
+
+        for part in middle {
+            match remaining.find(part.as_str()) {
+                Some(index) => remaining = &remaining[index + part.len()..],
+                None => return false,
+            }
+        }
+
+        remaining.len() >= last.len() && remaining.ends_with(last.as_str())
+    }
+}
+
+/// Split a template literal body into static text and `None` for each `${...}`.
+pub fn template_segments(raw: &str) -> Vec<Option<String>> {
+    let mut segments = Vec::new();
+    let mut rest = raw;
+
+    while let Some(start) = rest.find("${") {
+        segments.push(Some(rest[..start].to_string()));
+        segments.push(None);
+
+        let mut depth = 0usize;
+        let mut end = rest.len();
+        for (offset, ch) in rest[start + 1..].char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = start + 1 + offset + 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        rest = &rest[end..];
+    }
+    segments.push(Some(rest.to_string()));
+
+    segments
+}
+
+impl fmt::Display for KeyPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.parts.join("*"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_pattern() {
+        let pattern = KeyPattern::from_template("errors.${code}.title").unwrap();
+        assert_eq!(pattern.to_string(), "errors.*.title");
+        assert_eq!(pattern.static_prefix(), "errors.");
+        assert!(pattern.matches("errors.404.title"));
+        assert!(!pattern.matches("errors.404.body"));
+        assert!(!pattern.matches("status.404.title"));
+    }
+
+    #[test]
+    fn test_nested_braces_in_template() {
+        let pattern = KeyPattern::from_template("a.${fn({ x })}").unwrap();
+        assert_eq!(pattern.to_string(), "a.*");
+    }
+
+    #[test]
+    fn test_segments_merge() {
+        let pattern = KeyPattern::from_segments([
+            Some("status.".to_string()),
+            None,
+            None,
+            Some(".label".to_string()),
+        ])
+        .unwrap();
+        assert_eq!(pattern.to_string(), "status.*.label");
+        assert!(KeyPattern::from_segments([None]).is_none());
+        assert!(KeyPattern::from_segments([Some("static".to_string())]).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+/// Source language families the key extractor knows how to tokenize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LanguageFamily {
+    /// JavaScript, TypeScript and their JSX variants.
+    JavaScript,
+    /// Vue single-file components: `<script>` blocks plus the template markup.
+    Vue,
+    /// Svelte components: `<script>` blocks plus `{expression}` markup.
+    Svelte,
+    Php,
+    /// Blade templates: markup with `{{ }}`, `{!! !!}` and `@directive(...)` code.
+    Blade,
+    Dart,
+    /// Plain HTML and Angular templates.
+    Html,
+    Ruby,
+    /// ERB templates: markup with `<% %>` and `<%= %>` Ruby code.
+    Erb,
+}
+
+impl LanguageFamily {
+    /// Map an LSP `languageId` to its family.
+    pub fn from_language_id(language_id: &str) -> Option<Self> {
+        match language_id {
+            "javascript" | "javascriptreact" | "typescript" | "typescriptreact" | "tsx" | "jsx" => {
+                Some(LanguageFamily::JavaScript)
+            }
+            "vue" => Some(LanguageFamily::Vue),
+            "svelte" => Some(LanguageFamily::Svelte),
+            "php" => Some(LanguageFamily::Php),
+            "blade" => Some(LanguageFamily::Blade),
+            "dart" => Some(LanguageFamily::Dart),
+            "html" | "angular" => Some(LanguageFamily::Html),
+            "ruby" => Some(LanguageFamily::Ruby),
+            "erb" | "html+erb" | "eruby" => Some(LanguageFamily::Erb),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        if file_name.ends_with(".blade.php") {
+            return Some(LanguageFamily::Blade);
+        }
+        if file_name.ends_with(".erb") {
+            return Some(LanguageFamily::Erb);
+        }
+
+        match path.extension()?.to_str()? {
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => {
+                Some(LanguageFamily::JavaScript)
+            }
+            "vue" => Some(LanguageFamily::Vue),
+            "svelte" => Some(LanguageFamily::Svelte),
+            "php" => Some(LanguageFamily::Php),
+            "dart" => Some(LanguageFamily::Dart),
+            "html" | "htm" => Some(LanguageFamily::Html),
+            "rb" | "rake" => Some(LanguageFamily::Ruby),
+            _ => None,
+        }
+    }
+
+    /// Whether the document is markup with embedded code regions rather than plain code.
+    pub fn is_markup(&self) -> bool {
+        matches!(
+            self,
+            LanguageFamily::Vue
+                | LanguageFamily::Svelte
+                | LanguageFamily::Blade
+                | LanguageFamily::Html
+                | LanguageFamily::Erb
+        )
+    }
+}
+
+/// What is known about a document's language: the `languageId` recorded at
+/// `did_open` and its path.
+#[derive(Debug, Clone, Default)]
+pub struct SourceLanguage {
+    pub language_id: Option<String>,
+    pub path: Option<PathBuf>,
+}
+
+impl SourceLanguage {
+    pub fn new(language_id: Option<String>, path: Option<PathBuf>) -> Self {
+        Self { language_id, path }
+    }
+
+    /// The language family, preferring the editor's `languageId`. Blade views are
+    /// often reported as plain `php`, so the path refines that case.
+    pub fn family(&self) -> Option<LanguageFamily> {
+        let from_path = self.path.as_deref().and_then(LanguageFamily::from_path);
+        match self
+            .language_id
+            .as_deref()
+            .and_then(LanguageFamily::from_language_id)
+        {
+            Some(LanguageFamily::Php) if from_path == Some(LanguageFamily::Blade) => from_path,
+            Some(family) => Some(family),
+            None => from_path,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// The scope Rails resolves a lazy key like `t(".title")` in, from the file it is used in:
+/// views use their template path (`app/views/users/_form.html.erb` → `users.form`),
+/// controllers and mailers their path plus the enclosing action
+/// (`app/controllers/admin/users_controller.rb` → `admin.users.create`).
+pub fn scope_for(path: &Path, content: &str, offset: usize) -> Option<String> {
+    let path = path.to_string_lossy().replace('\\', "/");
+
+    if let Some((_, template)) = path.rsplit_once("app/views/") {
+        let (dir, file) = template.rsplit_once('/').unwrap_or(("", template));
+        let name = file.split('.').next()?;
+        let name = name.strip_prefix('_').unwrap_or(name);
+        return Some(join_scope(dir, name));
+    }
+
+    let (dir, file, suffix) = if let Some((_, rest)) = path.rsplit_once("app/controllers/") {
+        let (dir, file) = rest.rsplit_once('/').unwrap_or(("", rest));
+        (dir, file, "_controller")
+    } else if let Some((_, rest)) = path.rsplit_once("app/mailers/") {
+        let (dir, file) = rest.rsplit_once('/').unwrap_or(("", rest));
+        (dir, file, "")
+    } else {
+        return None;
+    };
+
+    let name = file.strip_suffix(".rb")?;
+    let name = name.strip_suffix(suffix).unwrap_or(name);
+    let action = enclosing_method(content, offset)?;
+    Some(format!("{}.{}", join_scope(dir, name), action))
+}
+
+fn join_scope(dir: &str, name: &str) -> String {
+    dir.split('/')
+        .filter(|part| !part.is_empty())
+        .chain(std::iter::once(name))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Name of the last `def` before `offset`.
+fn enclosing_method(content: &str, offset: usize) -> Option<String> {
+    static DEF: OnceLock<Regex> = OnceLock::new();
+    let def = DEF.get_or_init(|| {
+        Regex::new(r"(?m)^\s*def\s+(?:self\.)?([A-Za-z_][A-Za-z0-9_]*[?!]?)").unwrap()
+    });
+
+    def.captures_iter(&content[..offset.min(content.len())])
+        .last()
+        .map(|captures| captures[1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_scope() {
+        let scope = |path: &str| scope_for(Path::new(path), "", 0);
+        assert_eq!(
+            scope("/app/app/views/users/index.html.erb"),
+            Some("users.index".to_string())
+        );
+        assert_eq!(
+            scope("app/views/admin/users/_form.html.erb"),
+            Some("admin.users.form".to_string())
+        );
+        assert_eq!(scope("lib/tasks/seed.rb"), None);
+    }
+
+    #[test]
+    fn test_controller_scope_uses_action() {
+        let content = "class Admin::UsersController\n  def create\n    t('.created')\n  end\nend\n";
+        let offset = content.find(".created").unwrap();
+        assert_eq!(
+            scope_for(
+                Path::new("app/controllers/admin/users_controller.rb"),
+                content,
+                offset
+            ),
+            Some("admin.users.create".to_string())
+        );
+    }
+}
@@ -0,0 +1,642 @@
+use std::ops::Range;
+
+use super::language::LanguageFamily;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Ident(String),
+    /// A quoted string literal with escapes resolved.
+    Str(String),
+    /// A template literal; `dynamic` is set when it interpolates `${...}` expressions.
+    Template {
+        raw: String,
+        dynamic: bool,
+    },
+    Punct(char),
+}
+
+/// A token with byte offsets into the original document. For strings and templates
+/// the range includes the delimiters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Token {
+    pub fn ident(&self) -> Option<&str> {
+        match &self.kind {
+            TokenKind::Ident(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn is_punct(&self, c: char) -> bool {
+        self.kind == TokenKind::Punct(c)
+    }
+
+    /// The literal value of a string or interpolation-free template.
+    pub fn static_string(&self) -> Option<&str> {
+        match &self.kind {
+            TokenKind::Str(value) => Some(value),
+            TokenKind::Template {
+                raw,
+                dynamic: false,
+            } => Some(raw),
+            _ => None,
+        }
+    }
+
+    /// Byte range of the literal's contents, without the delimiters.
+    pub fn inner_range(&self, content: &str) -> Range<usize> {
+        let literal = &content[self.start..self.end];
+        let quote = &literal[..1];
+        // Ruby symbols only have the leading colon
+        if quote == ":" {
+            return self.start + 1..self.end;
+        }
+        let quote_len = if literal.len() >= 6 && literal.starts_with(&quote.repeat(3)) {
+            3
+        } else {
+            1
+        };
+        // Unterminated templates run to the end of their region, with no closing quote
+        let closed = literal.len() >= 2 * quote_len && literal.ends_with(&literal[..quote_len]);
+        let close_len = if closed { quote_len } else { 0 };
+
+        self.start + quote_len..(self.end - close_len).max(self.start + quote_len)
+    }
+}
+
+/// Tokens of every code region in a document, plus the byte ranges of its comments.
+#[derive(Debug, Default)]
+pub struct SourceScan {
+    pub tokens: Vec<Token>,
+    pub comments: Vec<Range<usize>>,
+    /// Quoted markup attributes and the byte range of their values, so presets can
+    /// pick out keys such as `<i18n-t keypath="...">`.
+    pub attributes: Vec<(String, Range<usize>)>,
+}
+
+impl SourceScan {
+    pub fn in_comment(&self, offset: usize) -> bool {
+        self.comments.iter().any(|range| range.contains(&offset))
+    }
+}
+
+/// Tokenize `content`. Markup families are split into their embedded code regions
+/// first, so text such as `Don't` between tags never opens a string literal. Without
+/// a family the whole document is treated as JavaScript-like code.
+pub fn scan(content: &str, family: Option<LanguageFamily>) -> SourceScan {
+    let mut scan = SourceScan::default();
+    let family_or_default = family.unwrap_or(LanguageFamily::JavaScript);
+
+    if family_or_default.is_markup() {
+        scan_markup(content, family_or_default, &mut scan);
+    } else {
+        tokenize(content, 0..content.len(), family_or_default, &mut scan);
+    }
+
+    scan
+}
+
+/// Tokenize a code region of `content`.
+pub fn tokenize(content: &str, range: Range<usize>, family: LanguageFamily, scan: &mut SourceScan) {
+    let bytes = content.as_bytes();
+    let end = range.end.min(content.len());
+    let mut i = range.start;
+
+    while i < end {
+        let c = bytes[i];
+
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c == b'/' && bytes.get(i + 1) == Some(&b'/') || is_hash_comment(bytes, i, family) {
+            let close = find_from(content, i, "\n", end).unwrap_or(end);
+            scan.comments.push(i..close);
+            i = close;
+            continue;
+        }
+
+        if c == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            let close = find_from(content, i + 2, "*/", end).map_or(end, |pos| pos + 2);
+            scan.comments.push(i..close);
+            i = close;
+            continue;
+        }
+
+        if c == b'\'' || c == b'"' {
+            let raw_prefix = family == LanguageFamily::Dart
+                && i > range.start
+                && bytes[i - 1] == b'r'
+                && !bytes
+                    .get(i.wrapping_sub(2))
+                    .is_some_and(|b| is_ident_byte(*b));
+            match read_string(content, i, end, family, raw_prefix) {
+                Some((value, close)) => {
+                    // Ruby interpolation reads like a template literal
+                    let kind =
+                        if family == LanguageFamily::Ruby && c == b'"' && value.contains("#{") {
+                            TokenKind::Template {
+                                raw: value.replace("#{", "${"),
+                                dynamic: true,
+                            }
+                        } else {
+                            TokenKind::Str(value)
+                        };
+                    scan.tokens.push(Token {
+                        kind,
+                        start: i,
+                        end: close,
+                    });
+                    i = close;
+                }
+                None => {
+                    // Unterminated on this line: a stray apostrophe in JSX text or a
+                    // regex literal. Treat it as punctuation and resynchronize.
+                    scan.tokens.push(Token {
+                        kind: TokenKind::Punct(c as char),
+                        start: i,
+                        end: i + 1,
+                    });
+                    i += 1;
+                }
+            }
+            continue;
+        }
+
+        if c == b'`' && (family == LanguageFamily::JavaScript || family.is_markup()) {
+            let (raw, dynamic, close) = read_template(content, i, end);
+            scan.tokens.push(Token {
+                kind: TokenKind::Template { raw, dynamic },
+                start: i,
+                end: close,
+            });
+            i = close;
+            continue;
+        }
+
+        if family == LanguageFamily::Ruby && c == b':' && is_symbol_start(bytes, i, range.start) {
+            let start = i;
+            i += 1;
+            while i < end && (is_ident_byte(bytes[i]) || matches!(bytes[i], b'?' | b'!')) {
+                i += 1;
+            }
+            scan.tokens.push(Token {
+                kind: TokenKind::Str(content[start + 1..i].to_string()),
+                start,
+                end: i,
+            });
+            continue;
+        }
+
+        if is_ident_start(c) {
+            let start = i;
+            i += 1;
+            while i < end && is_ident_byte(bytes[i]) {
+                i += 1;
+            }
+            scan.tokens.push(Token {
+                kind: TokenKind::Ident(content[start..i].to_string()),
+                start,
+                end: i,
+            });
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            while i < end && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                i += 1;
+            }
+            continue;
+        }
+
+        let ch = content[i..].chars().next().unwrap_or(' ');
+        scan.tokens.push(Token {
+            kind: TokenKind::Punct(ch),
+            start: i,
+            end: i + ch.len_utf8(),
+        });
+        i += ch.len_utf8();
+    }
+}
+
+fn is_ident_start(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'_' || c == b'$' || c == b'@' || c >= 0x80
+}
+
+fn is_ident_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$' || c >= 0x80
+}
+
+fn is_hash_comment(bytes: &[u8], i: usize, family: LanguageFamily) -> bool {
+    match family {
+        LanguageFamily::Php | LanguageFamily::Blade => {
+            bytes[i] == b'#' && bytes.get(i + 1) != Some(&b'[')
+        }
+        LanguageFamily::Ruby => bytes[i] == b'#',
+        _ => false,
+    }
+}
+
+/// A Ruby symbol such as `:title`, as opposed to `::`, `a ? b : c` or `key: value`.
+fn is_symbol_start(bytes: &[u8], i: usize, region_start: usize) -> bool {
+    let next_starts_name = bytes
+        .get(i + 1)
+        .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_');
+    let previous = (i > region_start).then(|| bytes[i - 1]);
+    next_starts_name && !previous.is_some_and(|b| b == b':' || is_ident_byte(b))
+}
+
+fn find_from(content: &str, from: usize, needle: &str, end: usize) -> Option<usize> {
+    content.get(from..end)?.find(needle).map(|pos| from + pos)
+}
+
+/// Read a quoted string starting at `start`. Returns the decoded value and the offset
+/// just past the closing quote. JavaScript and Dart single-line strings that hit a
+/// newline are reported as unterminated.
+fn read_string(
+    content: &str,
+    start: usize,
+    end: usize,
+    family: LanguageFamily,
+    raw: bool,
+) -> Option<(String, usize)> {
+    let bytes = content.as_bytes();
+    let quote = bytes[start];
+
+    let triple = family == LanguageFamily::Dart
+        && bytes.get(start + 1) == Some(&quote)
+        && bytes.get(start + 2) == Some(&quote);
+    if triple {
+        let delimiter = &content[start..start + 3];
+        let close = find_from(content, start + 3, delimiter, end)?;
+        return Some((content[start + 3..close].to_string(), close + 3));
+    }
+
+    let multiline = family == LanguageFamily::Php;
+    let mut value = String::new();
+    let mut chars = content[start + 1..end].char_indices();
+
+    while let Some((offset, ch)) = chars.next() {
+        match ch {
+            c if c as u32 == quote as u32 => return Some((value, start + 1 + offset + 1)),
+            '\n' if !multiline => return None,
+            '\\' if !raw => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, 'r')) => value.push('\r'),
+                Some((_, '\n')) => {}
+                Some((_, escaped)) => value.push(escaped),
+                None => return None,
+            },
+            c => value.push(c),
+        }
+    }
+
+    None
+}
+
+/// Read a template literal starting at the opening backtick, skipping over nested
+/// `${...}` expressions. Unterminated templates run to the end of the region.
+fn read_template(content: &str, start: usize, end: usize) -> (String, bool, usize) {
+    let bytes = content.as_bytes();
+    let mut i = start + 1;
+    let mut dynamic = false;
+
+    while i < end {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'`' => return (content[start + 1..i].to_string(), dynamic, i + 1),
+            b'$' if bytes.get(i + 1) == Some(&b'{') => {
+                dynamic = true;
+                i = skip_balanced(content, i + 1, end, b'{', b'}');
+            }
+            _ => i += 1,
+        }
+    }
+
+    (content[start + 1..end].to_string(), dynamic, end)
+}
+
+/// Given `open` at `start`, return the offset just past its matching `close`, skipping
+/// over quoted strings. Returns `end` when the region is unbalanced.
+fn skip_balanced(content: &str, start: usize, end: usize, open: u8, close: u8) -> usize {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut i = start;
+
+    while i < end {
+        let c = bytes[i];
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth = depth.saturating_sub(1);
+            if depth == 0 {
+                return i + 1;
+            }
+        } else if matches!(c, b'"' | b'\'' | b'`') {
+            let line_end = find_from(content, i + 1, "\n", end).unwrap_or(end);
+            let mut j = i + 1;
+            while j < line_end && bytes[j] != c {
+                j += if bytes[j] == b'\\' { 2 } else { 1 };
+            }
+            if j < line_end {
+                i = j;
+            }
+        }
+        i += 1;
+    }
+
+    end
+}
+
+fn scan_markup(content: &str, family: LanguageFamily, scan: &mut SourceScan) {
+    let bytes = content.as_bytes();
+    let end = content.len();
+    let mustache = matches!(
+        family,
+        LanguageFamily::Vue | LanguageFamily::Html | LanguageFamily::Blade
+    );
+    let mut i = 0;
+
+    while i < end {
+        let rest = &content[i..];
+
+        if rest.starts_with("<!--") {
+            let close = find_from(content, i + 4, "-->", end).map_or(end, |pos| pos + 3);
+            scan.comments.push(i..close);
+            i = close;
+            continue;
+        }
+
+        if family == LanguageFamily::Erb && rest.starts_with("<%") {
+            let close = find_from(content, i + 2, "%>", end).unwrap_or(end);
+            if rest.starts_with("<%#") {
+                scan.comments.push(i..(close + 2).min(end));
+            } else {
+                let code_start = (i + 2..close)
+                    .find(|&j| !matches!(bytes[j], b'=' | b'-'))
+                    .unwrap_or(close);
+                tokenize(content, code_start..close, LanguageFamily::Ruby, scan);
+            }
+            i = (close + 2).min(end);
+            continue;
+        }
+
+        if family == LanguageFamily::Blade {
+            if rest.starts_with("{{--") {
+                let close = find_from(content, i + 4, "--}}", end).map_or(end, |pos| pos + 4);
+                scan.comments.push(i..close);
+                i = close;
+                continue;
+            }
+            if rest.starts_with("{!!") {
+                let close = find_from(content, i + 3, "!!}", end).unwrap_or(end);
+                tokenize(content, i + 3..close, family, scan);
+                i = (close + 3).min(end);
+                continue;
+            }
+            if rest.starts_with("@php") && !rest[4..].starts_with(|c: char| c.is_alphanumeric()) {
+                let close = find_from(content, i + 4, "@endphp", end).unwrap_or(end);
+                tokenize(content, i + 4..close, LanguageFamily::Php, scan);
+                i = close;
+                continue;
+            }
+            if bytes[i] == b'@' {
+                if let Some(paren) = directive_paren(content, i) {
+                    let close = skip_balanced(content, paren, end, b'(', b')');
+                    tokenize(content, i..close, LanguageFamily::Php, scan);
+                    i = close;
+                    continue;
+                }
+            }
+        }
+
+        if mustache && rest.starts_with("{{") {
+            let close = find_from(content, i + 2, "}}", end).unwrap_or(end);
+            tokenize(content, i + 2..close, family, scan);
+            i = (close + 2).min(end);
+            continue;
+        }
+
+        if family == LanguageFamily::Svelte && bytes[i] == b'{' {
+            let close = skip_balanced(content, i, end, b'{', b'}');
+            tokenize(content, brace_inner(content, i, close), family, scan);
+            i = close;
+            continue;
+        }
+
+        if bytes[i] == b'<' && bytes.get(i + 1).is_some_and(|b| b.is_ascii_alphabetic()) {
+            i = scan_tag(content, i, family, scan);
+            continue;
+        }
+
+        i += rest.chars().next().map_or(1, |c| c.len_utf8());
+    }
+}
+
+/// The inside of the `{...}` opening at `open` that `skip_balanced` closed at `close`.
+/// An unbalanced one runs to `close`, which then is no closing brace to leave out.
+fn brace_inner(content: &str, open: usize, close: usize) -> Range<usize> {
+    let closed = close > open + 1 && content.as_bytes()[close - 1] == b'}';
+    open + 1..if closed { close - 1 } else { close }
+}
+
+/// `@lang(`, `@choice(`... returns the offset of the opening parenthesis.
+fn directive_paren(content: &str, at: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut i = at + 1;
+    while i < bytes.len() && bytes[i].is_ascii_alphanumeric() {
+        i += 1;
+    }
+    if i == at + 1 {
+        return None;
+    }
+    while i < bytes.len() && bytes[i] == b' ' {
+        i += 1;
+    }
+    (bytes.get(i) == Some(&b'(')).then_some(i)
+}
+
+/// Scan an opening tag starting at `<`, tokenizing attribute values as code. `<script>`
+/// bodies are tokenized whole and `<style>` bodies skipped. Returns the offset after
+/// the construct.
+fn scan_tag(content: &str, start: usize, family: LanguageFamily, scan: &mut SourceScan) -> usize {
+    let bytes = content.as_bytes();
+    let end = content.len();
+    let mut i = start + 1;
+    while i < end && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-' || bytes[i] == b':') {
+        i += 1;
+    }
+    let tag = content[start + 1..i].to_ascii_lowercase();
+    let mut attribute = String::new();
+
+    while i < end && bytes[i] != b'>' {
+        match bytes[i] {
+            b'"' | b'\'' => {
+                let quote = bytes[i];
+                let close = content[i + 1..]
+                    .find(quote as char)
+                    .map_or(end, |pos| i + 1 + pos);
+                scan.attributes
+                    .push((std::mem::take(&mut attribute), i + 1..close));
+                tokenize(content, i + 1..close, family, scan);
+                i = (close + 1).min(end);
+            }
+            b'{' if family == LanguageFamily::Svelte => {
+                let close = skip_balanced(content, i, end, b'{', b'}');
+                tokenize(content, brace_inner(content, i, close), family, scan);
+                attribute.clear();
+                i = close;
+            }
+            b'=' => i += 1,
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+            }
+            _ => {
+                let name_start = i;
+                while i < end && !bytes[i].is_ascii_whitespace() && !matches!(bytes[i], b'=' | b'>')
+                {
+                    i += 1;
+                }
+                if i == name_start {
+                    i += 1;
+                }
+                attribute = content[name_start..i].to_string();
+            }
+        }
+    }
+    let body_start = (i + 1).min(end);
+
+    match tag.as_str() {
+        "script" => {
+            let close = find_from(content, body_start, "</script", end).unwrap_or(end);
+            let code_family = if family == LanguageFamily::Html {
+                LanguageFamily::JavaScript
+            } else {
+                family
+            };
+            tokenize(content, body_start..close, code_family, scan);
+            close
+        }
+        "style" => find_from(content, body_start, "</style", end).unwrap_or(end),
+        _ => body_start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(content: &str, family: LanguageFamily) -> Vec<TokenKind> {
+        scan(content, Some(family))
+            .tokens
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_skips_comments() {
+        let content = "// t('a')\n/* t('b') */ t('c')";
+        let scan = scan(content, Some(LanguageFamily::JavaScript));
+        let strings: Vec<_> = scan
+            .tokens
+            .iter()
+            .filter_map(|t| t.static_string())
+            .collect();
+        assert_eq!(strings, vec!["c"]);
+        assert_eq!(scan.comments.len(), 2);
+    }
+
+    #[test]
+    fn test_template_literals() {
+        assert_eq!(
+            kinds("`a.b` `x.${y}`", LanguageFamily::JavaScript),
+            vec![
+                TokenKind::Template {
+                    raw: "a.b".to_string(),
+                    dynamic: false
+                },
+                TokenKind::Template {
+                    raw: "x.${y}".to_string(),
+                    dynamic: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unterminated_quote_resynchronizes() {
+        let content = "<p>Don't</p>\nt('key')";
+        let strings: Vec<_> = scan(content, Some(LanguageFamily::JavaScript))
+            .tokens
+            .into_iter()
+            .filter_map(|t| t.static_string().map(str::to_string))
+            .collect();
+        assert_eq!(strings, vec!["key"]);
+    }
+
+    #[test]
+    fn test_markup_text_is_not_code() {
+        let content = "<template><p>It's {{ $t('a') }}</p><!-- {{ $t('b') }} --></template>";
+        let strings: Vec<_> = scan(content, Some(LanguageFamily::Vue))
+            .tokens
+            .into_iter()
+            .filter_map(|t| t.static_string().map(str::to_string))
+            .collect();
+        assert_eq!(strings, vec!["a"]);
+    }
+
+    #[test]
+    fn test_blade_directives_and_comments() {
+        let content = "{{-- @lang('a') --}}\n@lang('b') <p>{{ __('c') }}</p>";
+        let strings: Vec<_> = scan(content, Some(LanguageFamily::Blade))
+            .tokens
+            .into_iter()
+            .filter_map(|t| t.static_string().map(str::to_string))
+            .collect();
+        assert_eq!(strings, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_inner_range() {
+        let content = "x = \"key\"";
+        let scan = scan(content, Some(LanguageFamily::JavaScript));
+        let token = scan.tokens.last().unwrap();
+        assert_eq!(&content[token.inner_range(content)], "key");
+
+        // An unterminated template has no closing backtick to leave out
+        for (content, inner) in [("t`ab", "ab"), ("t`é", "é"), ("t`", "")] {
+            let tokens = super::scan(content, Some(LanguageFamily::JavaScript)).tokens;
+            let token = tokens.last().unwrap();
+            assert_eq!(&content[token.inner_range(content)], inner);
+        }
+    }
+
+    #[test]
+    fn test_unterminated_svelte_expressions() {
+        assert_eq!(
+            kinds("<p>{é", LanguageFamily::Svelte),
+            vec![TokenKind::Ident("é".to_string())]
+        );
+        assert_eq!(
+            kinds("<p a={é", LanguageFamily::Svelte),
+            vec![TokenKind::Ident("é".to_string())]
+        );
+        assert_eq!(
+            kinds("<p>{$_('a')", LanguageFamily::Svelte),
+            vec![
+                TokenKind::Ident("$_".to_string()),
+                TokenKind::Punct('('),
+                TokenKind::Str("a".to_string()),
+                TokenKind::Punct(')'),
+            ]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+/// A BCP 47 language tag: a language with an optional script, region and variants, as
+/// in `zh-Hant-TW`, `es-419` or `ca-ES-valencia`. Extensions and private use subtags
+/// are not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageTag {
+    pub language: String,
+    pub script: Option<String>,
+    pub region: Option<String>,
+    pub variants: Vec<String>,
+}
+
+/// Three-letter languages with locale data in CLDR that projects ship translations for.
+/// Other three-letter names in paths are far more likely to be words (`app`, `src`).
+const THREE_LETTER_LANGUAGES: [&str; 40] = [
+    "arn", "ast", "bem", "brx", "ceb", "chr", "ckb", "dsb", "fil", "fon", "fur", "gsw", "haw",
+    "hsb", "kab", "kea", "kln", "kok", "lkt", "lrc", "mai", "mni", "moh", "mzn", "nds", "nqo",
+    "pcm", "quc", "sah", "sat", "shi", "smn", "sms", "szl", "tzm", "vai", "vec", "yrl", "yue",
+    "zgh",
+];
+
+impl LanguageTag {
+    /// Parse a tag written with `-` or `_` separators, in any case.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut subtags = tag.split(&['-', '_'][..]).peekable();
+
+        let language = subtags.next()?;
+        if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic())
+        {
+            return None;
+        }
+
+        let script = subtags
+            .next_if(|s| s.len() == 4 && s.chars().all(|c| c.is_ascii_alphabetic()))
+            .map(|s| {
+                let (first, rest) = s.split_at(1);
+                first.to_ascii_uppercase() + &rest.to_ascii_lowercase()
+            });
+        let region = subtags
+            .next_if(|s| {
+                s.len() == 2 && s.chars().all(|c| c.is_ascii_alphabetic())
+                    || s.len() == 3 && s.chars().all(|c| c.is_ascii_digit())
+            })
+            .map(|s| s.to_ascii_uppercase());
+
+        let mut variants = Vec::new();
+        for subtag in subtags {
+            let alphanumeric = subtag.chars().all(|c| c.is_ascii_alphanumeric());
+            let is_variant = alphanumeric
+                && ((5..=8).contains(&subtag.len())
+                    || subtag.len() == 4 && subtag.starts_with(|c: char| c.is_ascii_digit()));
+            if !is_variant {
+                return None;
+            }
+            variants.push(subtag.to_ascii_lowercase());
+        }
+
+        Some(Self {
+            language: language.to_ascii_lowercase(),
+            script,
+            region,
+            variants,
+        })
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.language)?;
+        for subtag in self
+            .script
+            .iter()
+            .chain(self.region.iter())
+            .chain(self.variants.iter())
+        {
+            write!(f, "-{}", subtag)?;
+        }
+        Ok(())
+    }
+}
+
+/// The canonical form of a locale (`pt_br` becomes `pt-BR`), or the locale unchanged
+/// when it is not a language tag.
+pub fn canonicalize(locale: &str) -> String {
+    LanguageTag::parse(locale).map_or_else(|| locale.to_string(), |tag| tag.to_string())
+}
+
+/// Whether a file or directory name is a locale: a language tag whose language is
+/// written in lowercase and is a two-letter code or a known three-letter one. Variants
+/// are only taken after a script or region, so `ng-common` stays a plain name.
+pub fn is_locale_code(name: &str) -> bool {
+    let Some(tag) = LanguageTag::parse(name) else {
+        return false;
+    };
+    let written = &name[..tag.language.len()];
+    written == tag.language
+        && (tag.language.len() == 2 || THREE_LETTER_LANGUAGES.contains(&written))
+        && (tag.variants.is_empty() || tag.script.is_some() || tag.region.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_language_tags() {
+        assert_eq!(canonicalize("pt_br"), "pt-BR");
+        assert_eq!(canonicalize("zh_hant_tw"), "zh-Hant-TW");
+        assert_eq!(canonicalize("sr-Latn-RS"), "sr-Latn-RS");
+        assert_eq!(canonicalize("es-419"), "es-419");
+        assert_eq!(canonicalize("ca-ES-VALENCIA"), "ca-ES-valencia");
+        assert_eq!(canonicalize("messages"), "messages");
+        assert_eq!(canonicalize("en-"), "en-");
+    }
+
+    #[test]
+    fn test_locale_codes_in_paths() {
+        for name in [
+            "en",
+            "pt_BR",
+            "zh-Hant",
+            "sr-Latn-RS",
+            "es-419",
+            "fil",
+            "ca-ES-valencia",
+        ] {
+            assert!(is_locale_code(name), "{}", name);
+        }
+        for name in [
+            "app",
+            "src",
+            "EN",
+            "common",
+            "en-USA",
+            "de-1",
+            "app_en",
+            "ng-common",
+        ] {
+            assert!(!is_locale_code(name), "{}", name);
+        }
+    }
+}
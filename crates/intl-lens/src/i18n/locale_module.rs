@@ -0,0 +1,367 @@
+use std::path::{Path, PathBuf};
+
+use super::inline::skip_value;
+use super::language::LanguageFamily;
+use super::lexer::{self, Token, TokenKind};
+use super::parser::TranslationParser;
+
+/// A message read from a JavaScript or TypeScript locale module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleMessage {
+    pub key: String,
+    pub value: String,
+    /// File the value is written in, which is another module for spread objects.
+    pub path: PathBuf,
+    /// Zero-based line of the value, unknown for imported JSON.
+    pub line: Option<usize>,
+}
+
+/// How many names and imports are followed before giving up, which also ends cycles.
+const MAX_DEPTH: usize = 8;
+
+const MODULE_EXTENSIONS: [&str; 7] = ["ts", "js", "mts", "mjs", "cts", "cjs", "json"];
+
+/// Statically evaluate the default export of a locale module, `export default { ... }`
+/// or `module.exports = { ... }`. Only literals are read: strings, templates without
+/// interpolation and nested objects, plus names and spreads that refer to such objects
+/// declared in the module or imported from another one. Anything else is skipped.
+/// Returns `None` when the module has no default export.
+pub fn module_messages(path: &Path, content: &str) -> Option<Vec<ModuleMessage>> {
+    let module = Module::new(path, content);
+    let start = module.default_export()?;
+    let mut messages = Vec::new();
+    module.value(start, "", 0, &mut messages);
+    Some(messages)
+}
+
+struct Module<'a> {
+    path: &'a Path,
+    content: &'a str,
+    tokens: Vec<Token>,
+}
+
+impl<'a> Module<'a> {
+    fn new(path: &'a Path, content: &'a str) -> Self {
+        let family = LanguageFamily::from_path(path).unwrap_or(LanguageFamily::JavaScript);
+        let tokens = lexer::scan(content, Some(family)).tokens;
+        Self {
+            path,
+            content,
+            tokens,
+        }
+    }
+
+    /// Index of the value after `export default` or `module.exports =`.
+    fn default_export(&self) -> Option<usize> {
+        let tokens = &self.tokens;
+        (0..tokens.len()).find_map(|index| {
+            let ident = |offset: usize| tokens.get(index + offset).and_then(|t| t.ident());
+            let punct =
+                |offset: usize, c: char| tokens.get(index + offset).is_some_and(|t| t.is_punct(c));
+
+            if ident(0) == Some("export") && ident(1) == Some("default") {
+                return Some(index + 2);
+            }
+            if ident(0) == Some("module")
+                && punct(1, '.')
+                && ident(2) == Some("exports")
+                && punct(3, '=')
+            {
+                return Some(index + 4);
+            }
+            None
+        })
+    }
+
+    fn value(&self, index: usize, prefix: &str, depth: usize, out: &mut Vec<ModuleMessage>) {
+        let Some(token) = self.tokens.get(index) else {
+            return;
+        };
+
+        if token.is_punct('{') {
+            self.object(index, prefix, depth, out);
+        } else if let Some(value) = token.static_string() {
+            if !prefix.is_empty() {
+                out.push(ModuleMessage {
+                    key: prefix.to_string(),
+                    value: value.to_string(),
+                    path: self.path.to_path_buf(),
+                    line: Some(self.content[..token.start].matches('\n').count()),
+                });
+            }
+        } else if let Some(name) = token.ident() {
+            self.name(name, prefix, depth, out);
+        }
+    }
+
+    fn object(&self, brace: usize, prefix: &str, depth: usize, out: &mut Vec<ModuleMessage>) {
+        let tokens = &self.tokens;
+        let mut index = brace + 1;
+
+        while let Some(token) = tokens.get(index) {
+            if token.is_punct('}') {
+                return;
+            }
+            if token.is_punct(',') {
+                index += 1;
+                continue;
+            }
+
+            let is_spread = (0..3).all(|i| tokens.get(index + i).is_some_and(|t| t.is_punct('.')));
+            if is_spread {
+                self.value(index + 3, prefix, depth, out);
+                index = skip_value(tokens, index + 3);
+                continue;
+            }
+
+            let name = match &token.kind {
+                TokenKind::Ident(name) => Some(name.as_str()),
+                _ => token.static_string(),
+            };
+            let Some(name) = name else {
+                index = skip_value(tokens, index + 1);
+                continue;
+            };
+            let key = join(prefix, name);
+            let next = tokens.get(index + 1);
+
+            if next.is_some_and(|t| t.is_punct(':')) {
+                self.value(index + 2, &key, depth, out);
+                index = skip_value(tokens, index + 2);
+            } else if next.map_or(true, |t| t.is_punct(',') || t.is_punct('}')) {
+                // Shorthand property: `{ common }`
+                if token.ident().is_some() {
+                    self.name(name, &key, depth, out);
+                }
+                index += 1;
+            } else {
+                index = skip_value(tokens, index + 1);
+            }
+        }
+    }
+
+    /// Evaluate the object a name refers to: a declaration in this module or an import.
+    fn name(&self, name: &str, prefix: &str, depth: usize, out: &mut Vec<ModuleMessage>) {
+        if depth >= MAX_DEPTH {
+            return;
+        }
+
+        if let Some(start) = self.declaration(name) {
+            self.value(start, prefix, depth + 1, out);
+            return;
+        }
+
+        let Some((specifier, imported)) = self.import(name) else {
+            return;
+        };
+        let Some(path) = self.resolve(&specifier) else {
+            return;
+        };
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return;
+        };
+
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            let Ok(translations) = TranslationParser::parse_json(&content) else {
+                return;
+            };
+            let mut entries: Vec<_> = translations.into_iter().collect();
+            entries.sort();
+            out.extend(entries.into_iter().map(|(key, value)| ModuleMessage {
+                key: join(prefix, &key),
+                value,
+                path: path.clone(),
+                line: None,
+            }));
+            return;
+        }
+
+        let module = Module::new(&path, &content);
+        let start = match imported {
+            Some(imported) => module.declaration(&imported),
+            None => module.default_export(),
+        };
+        if let Some(start) = start {
+            module.value(start, prefix, depth + 1, out);
+        }
+    }
+
+    /// Index of the initializer of `const name = ...`, past any type annotation.
+    fn declaration(&self, name: &str) -> Option<usize> {
+        let tokens = &self.tokens;
+        (0..tokens.len()).find_map(|index| {
+            let keyword = tokens[index].ident()?;
+            if !matches!(keyword, "const" | "let" | "var")
+                || tokens.get(index + 1)?.ident() != Some(name)
+            {
+                return None;
+            }
+            (index + 2..(index + 10).min(tokens.len()))
+                .take_while(|&i| !tokens[i].is_punct(';'))
+                .find(|&i| tokens[i].is_punct('='))
+                .map(|equals| equals + 1)
+        })
+    }
+
+    /// The specifier `name` is imported from, with the imported name for named imports.
+    fn import(&self, name: &str) -> Option<(String, Option<String>)> {
+        let tokens = &self.tokens;
+        for (index, token) in tokens.iter().enumerate() {
+            if token.ident() != Some("import") {
+                continue;
+            }
+            let Some(from) = (index + 1..tokens.len())
+                .take_while(|&i| !tokens[i].is_punct(';') && tokens[i].ident() != Some("import"))
+                .find(|&i| tokens[i].ident() == Some("from"))
+            else {
+                continue;
+            };
+            let Some(specifier) = tokens.get(from + 1).and_then(|t| t.static_string()) else {
+                continue;
+            };
+
+            // `import name from`, or `import name, { ... } from`
+            if tokens.get(index + 1).and_then(|t| t.ident()) == Some(name) {
+                return Some((specifier.to_string(), None));
+            }
+
+            let Some(brace) = (index + 1..from).find(|&i| tokens[i].is_punct('{')) else {
+                continue;
+            };
+            for i in brace + 1..from {
+                // `{ name }` or `{ imported as name }`
+                if tokens[i].ident() != Some(name)
+                    || tokens.get(i + 1).and_then(|t| t.ident()) == Some("as")
+                {
+                    continue;
+                }
+                let imported = if tokens[i - 1].ident() == Some("as") {
+                    tokens.get(i.wrapping_sub(2)).and_then(|t| t.ident())?
+                } else {
+                    name
+                };
+                return Some((specifier.to_string(), Some(imported.to_string())));
+            }
+        }
+        None
+    }
+
+    /// The file a relative import specifier points at, trying the usual extensions and
+    /// `index` files. Package imports are not followed.
+    fn resolve(&self, specifier: &str) -> Option<PathBuf> {
+        if !specifier.starts_with('.') {
+            return None;
+        }
+        let base = self.path.parent()?.join(specifier);
+        if base.is_file() {
+            return Some(base);
+        }
+
+        // TypeScript ESM imports name the emitted `.js` file
+        let stem = match base.extension().and_then(|e| e.to_str()) {
+            Some("js" | "mjs" | "cjs") => base.with_extension(""),
+            _ => base.clone(),
+        };
+        MODULE_EXTENSIONS
+            .iter()
+            .map(|extension| PathBuf::from(format!("{}.{}", stem.display(), extension)))
+            .chain(
+                MODULE_EXTENSIONS
+                    .iter()
+                    .map(|extension| base.join(format!("index.{}", extension))),
+            )
+            .find(|candidate| candidate.is_file())
+    }
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn entries(messages: Vec<ModuleMessage>) -> Vec<(String, String, Option<usize>)> {
+        messages
+            .into_iter()
+            .map(|m| (m.key, m.value, m.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_export_object() {
+        let content = r#"import type { Messages } from "./types";
+
+export default {
+  home: {
+    title: "Home",
+    "sub-title": `Welcome`,
+  },
+  greeting: `Hello ${name}`,
+  count: (n: number) => `${n} items`,
+} as const satisfies Messages;
+"#;
+        let messages = module_messages(Path::new("locales/en.ts"), content).unwrap();
+        assert_eq!(
+            entries(messages),
+            vec![
+                ("home.title".into(), "Home".into(), Some(4)),
+                ("home.sub-title".into(), "Welcome".into(), Some(5)),
+            ]
+        );
+        assert!(module_messages(Path::new("locales/en.ts"), "export const a = 1;").is_none());
+    }
+
+    #[test]
+    fn test_spreads_and_imports() {
+        let root = TempDir::new("locale-module");
+        std::fs::create_dir_all(root.join("en")).unwrap();
+        std::fs::write(
+            root.join("en/common.ts"),
+            "export const buttons = { save: 'Save' };\nexport default { ok: 'OK' };\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("en/errors.json"), r#"{"notFound": "Not found"}"#).unwrap();
+
+        let content = r#"import common, { buttons as actions } from "./en/common.js";
+import errors from "./en/errors.json";
+
+const nav = { home: "Home" };
+
+module.exports = {
+  ...common,
+  nav,
+  actions,
+  errors,
+};
+"#;
+        let path = root.join("en.js");
+        let messages = module_messages(&path, content).unwrap();
+        let mut keys: Vec<_> = messages
+            .iter()
+            .map(|m| {
+                (
+                    m.key.as_str(),
+                    m.value.as_str(),
+                    m.path.file_name().unwrap(),
+                )
+            })
+            .collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                ("actions.save", "Save", "common.ts".as_ref()),
+                ("errors.notFound", "Not found", "errors.json".as_ref()),
+                ("nav.home", "Home", "en.js".as_ref()),
+                ("ok", "OK", "common.ts".as_ref()),
+            ]
+        );
+    }
+}
@@ -1,6 +1,31 @@
+mod arb;
+mod extractor;
+mod fallback;
+mod fuzzy;
+mod gitignore;
+mod inline;
 mod key_finder;
+mod key_pattern;
+mod language;
+mod lazy_lookup;
+mod lexer;
+mod locale;
+mod locale_module;
 mod parser;
+mod path_matcher;
+mod scan_options;
+mod scope;
 mod store;
 
+pub use arb::{check_arb, ArbIssue};
+pub use fallback::LocaleFallback;
 pub use key_finder::KeyFinder;
+pub use key_pattern::KeyPattern;
+pub use language::SourceLanguage;
+pub use locale::{canonicalize, is_locale_code};
+pub use parser::TranslationParser;
+pub use path_matcher::PathMatcher;
+pub use scan_options::ScanOptions;
 pub use store::TranslationStore;
+
+pub(crate) use store::extract_locale_from_arb_filename;
@@ -7,16 +7,54 @@ use serde_yaml::Value as YamlValue;
 
 pub struct TranslationParser;
 
+/// What a translation file tells translators about a message.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TranslatorNote {
+    pub description: Option<String>,
+    /// Disambiguating context, such as a gettext `msgctxt`.
+    pub context: Option<String>,
+}
+
 impl TranslationParser {
-    pub fn parse_file(path: &Path) -> Result<HashMap<String, String>> {
-        let content = std::fs::read_to_string(path)?;
-        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    /// Parse a translation file by its extension, as JSON unless it says otherwise.
+    pub fn parse_content(path: &Path, content: &str) -> Result<HashMap<String, String>> {
+        match extension(path) {
+            "yaml" | "yml" => Self::parse_yaml(content),
+            "php" => Self::parse_php(content),
+            "arb" => Self::parse_arb(content),
+            "po" => Self::parse_po(content),
+            _ => Self::parse_json(content),
+        }
+    }
 
-        match extension {
-            "yaml" | "yml" => Self::parse_yaml(&content),
-            "php" => Self::parse_php(&content),
-            "arb" => Self::parse_arb(&content),
-            _ => Self::parse_json(&content),
+    /// Translator notes by key: ARB `description` and `context`, gettext `#.` comments
+    /// and `msgctxt`, JSON `_comment` entries (describing the entry after them) and
+    /// ARB-style `@<key>` siblings, and the YAML comment lines right above a key.
+    pub fn parse_notes(path: &Path, content: &str) -> HashMap<String, TranslatorNote> {
+        match extension(path) {
+            "yaml" | "yml" => yaml_notes(content),
+            "arb" => arb_notes(content),
+            "po" => po_entries(content)
+                .into_iter()
+                .filter(|entry| !entry.msgid.is_empty())
+                .filter_map(|entry| {
+                    let key = entry.key();
+                    let note = TranslatorNote {
+                        description: (!entry.comments.is_empty())
+                            .then(|| entry.comments.join("\n")),
+                        context: entry.context,
+                    };
+                    (note != TranslatorNote::default()).then_some((key, note))
+                })
+                .collect(),
+            "json" => {
+                let mut notes = HashMap::new();
+                if let Ok(value) = serde_json::from_str::<JsonValue>(content) {
+                    json_notes(&value, "", &mut notes);
+                }
+                notes
+            }
+            _ => HashMap::new(),
         }
     }
 
@@ -43,6 +81,18 @@ impl TranslationParser {
         Ok(result)
     }
 
+    /// Parse gettext catalogs such as Lingui's `messages.po`: each `msgid` is a key, with
+    /// its `msgctxt` in front as gettext does (see `PO_CONTEXT_SEPARATOR`), and its
+    /// `msgstr` (the first form for plurals) the translation. The header and
+    /// untranslated entries are skipped.
+    pub fn parse_po(content: &str) -> Result<HashMap<String, String>> {
+        Ok(po_entries(content)
+            .into_iter()
+            .filter(|entry| !entry.msgid.is_empty() && !entry.msgstr.is_empty())
+            .map(|entry| (entry.key(), entry.msgstr))
+            .collect())
+    }
+
     pub fn parse_php(content: &str) -> Result<HashMap<String, String>> {
         let mut parser = PhpParser::new(content);
         let value = parser.parse_root_array()?;
@@ -52,7 +102,11 @@ impl TranslationParser {
     }
 
     pub fn parse_json(content: &str) -> Result<HashMap<String, String>> {
-        let value: JsonValue = serde_json::from_str(content)?;
+        let mut value: JsonValue = serde_json::from_str(content)?;
+        // Paraglide and other tooling reference their schema from the message file
+        if let JsonValue::Object(map) = &mut value {
+            map.remove("$schema");
+        }
         let mut result = HashMap::new();
         Self::flatten_json(&value, String::new(), &mut result);
         Ok(result)
@@ -65,10 +119,37 @@ impl TranslationParser {
         Ok(result)
     }
 
+    /// Parse a YAML file into its top-level entries, each flattened on its own. Every
+    /// root must be a mapping, as in Rails' `en: { users: ... }` locale files.
+    pub fn parse_yaml_roots(content: &str) -> Result<Vec<(String, HashMap<String, String>)>> {
+        let value: YamlValue = serde_yaml::from_str(content)?;
+        let YamlValue::Mapping(map) = value else {
+            bail!("YAML root is not a mapping");
+        };
+
+        let mut roots = Vec::new();
+        for (key, val) in map {
+            let Some(name) = key.as_str() else {
+                bail!("YAML root key is not a string");
+            };
+            if !val.is_mapping() {
+                bail!("YAML root '{}' is not a mapping", name);
+            }
+            let mut result = HashMap::new();
+            Self::flatten_yaml(&val, String::new(), &mut result);
+            roots.push((name.to_string(), result));
+        }
+
+        Ok(roots)
+    }
+
     fn flatten_json(value: &JsonValue, prefix: String, result: &mut HashMap<String, String>) {
         match value {
             JsonValue::Object(map) => {
                 for (key, val) in map {
+                    if is_json_note(map, key, val) {
+                        continue;
+                    }
                     let new_key = if prefix.is_empty() {
                         key.clone()
                     } else {
@@ -519,6 +600,277 @@ fn flatten_php(value: &PhpValue, prefix: String, result: &mut HashMap<String, St
     }
 }
 
+fn extension(path: &Path) -> &str {
+    path.extension().and_then(|e| e.to_str()).unwrap_or("")
+}
+
+/// Separates the `msgctxt` from the `msgid` in the key of a gettext entry with a
+/// context, as in gettext's own compiled catalogs.
+pub const PO_CONTEXT_SEPARATOR: char = '\u{4}';
+
+/// One entry of a gettext catalog.
+#[derive(Default)]
+struct PoEntry {
+    context: Option<String>,
+    /// Extracted comments (`#.`), left for translators by the developers.
+    comments: Vec<String>,
+    msgid: String,
+    /// The translation, the first form for plurals.
+    msgstr: String,
+}
+
+impl PoEntry {
+    /// The key of the entry: its `msgid`, after its context if it has one, so entries
+    /// that differ only in context do not collide.
+    fn key(&self) -> String {
+        match &self.context {
+            Some(context) => format!("{}{}{}", context, PO_CONTEXT_SEPARATOR, self.msgid),
+            None => self.msgid.clone(),
+        }
+    }
+}
+
+/// The entries of a gettext catalog, including the header and untranslated ones.
+/// Obsolete entries are skipped.
+fn po_entries(content: &str) -> Vec<PoEntry> {
+    enum Field {
+        Context,
+        Id,
+        Str,
+        Other,
+    }
+
+    let mut entries = Vec::new();
+    let mut entry = PoEntry::default();
+    let mut field = Field::Other;
+    let mut has_msgstr = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix("#.") {
+            if std::mem::take(&mut has_msgstr) {
+                entries.push(std::mem::take(&mut entry));
+            }
+            entry.comments.push(comment.trim().to_string());
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('"') {
+            match field {
+                Field::Context => entry
+                    .context
+                    .get_or_insert_with(String::new)
+                    .push_str(&unquote_po(line)),
+                Field::Id => entry.msgid.push_str(&unquote_po(line)),
+                Field::Str => entry.msgstr.push_str(&unquote_po(line)),
+                Field::Other => {}
+            }
+            continue;
+        }
+
+        let (keyword, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        if matches!(keyword, "msgctxt" | "msgid") && std::mem::take(&mut has_msgstr) {
+            entries.push(std::mem::take(&mut entry));
+        }
+        field = match keyword {
+            "msgctxt" => {
+                entry.context = Some(unquote_po(value));
+                Field::Context
+            }
+            "msgid" => {
+                entry.msgid = unquote_po(value);
+                Field::Id
+            }
+            "msgstr" | "msgstr[0]" => {
+                has_msgstr = true;
+                entry.msgstr = unquote_po(value);
+                Field::Str
+            }
+            _ => Field::Other,
+        };
+    }
+    if has_msgstr {
+        entries.push(entry);
+    }
+
+    entries
+}
+
+fn arb_notes(content: &str) -> HashMap<String, TranslatorNote> {
+    let Ok(JsonValue::Object(map)) = serde_json::from_str::<JsonValue>(content) else {
+        return HashMap::new();
+    };
+
+    map.iter()
+        .filter_map(|(name, entry)| {
+            let key = name.strip_prefix('@').filter(|key| !key.starts_with('@'))?;
+            let note = metadata_note(entry);
+            (note != TranslatorNote::default()).then(|| (key.to_string(), note))
+        })
+        .collect()
+}
+
+/// The `description` and `context` of an ARB-style `@key` metadata object.
+fn metadata_note(entry: &JsonValue) -> TranslatorNote {
+    let text = |field: &str| {
+        entry
+            .get(field)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    TranslatorNote {
+        description: text("description"),
+        context: text("context"),
+    }
+}
+
+/// Whether `key` of a JSON object is a note about another entry rather than a message.
+fn is_json_note(map: &serde_json::Map<String, JsonValue>, key: &str, value: &JsonValue) -> bool {
+    key == "_comment" || value.is_object() && json_note_target(map, key).is_some()
+}
+
+/// The sibling an ARB-style `@<key>` metadata object describes. Suffixes such as
+/// `_description` are left alone: `product_description` is as likely a message.
+fn json_note_target<'a>(map: &serde_json::Map<String, JsonValue>, key: &'a str) -> Option<&'a str> {
+    key.strip_prefix('@')
+        .filter(|target| !target.is_empty() && map.contains_key(*target))
+}
+
+fn json_notes(value: &JsonValue, prefix: &str, notes: &mut HashMap<String, TranslatorNote>) {
+    let JsonValue::Object(map) = value else {
+        return;
+    };
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+
+    let mut pending = None;
+    for (key, val) in map {
+        if key == "_comment" {
+            pending = val.as_str();
+            continue;
+        }
+        if let Some(target) = json_note_target(map, key).filter(|_| val.is_object()) {
+            let note = metadata_note(val);
+            if note != TranslatorNote::default() {
+                notes.insert(join(target), note);
+            }
+            continue;
+        }
+        if let Some(text) = pending.take() {
+            notes.entry(join(key)).or_default().description = Some(text.to_string());
+        }
+        json_notes(val, &join(key), notes);
+    }
+}
+
+/// The comment lines right above each key of a YAML file, by dotted key.
+fn yaml_notes(content: &str) -> HashMap<String, TranslatorNote> {
+    let mut notes = HashMap::new();
+    let mut path: Vec<(usize, String)> = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+    // Indentation of the key whose block scalar (`|` or `>`) is being read
+    let mut block: Option<usize> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if let Some(block_indent) = block {
+            if trimmed.is_empty() || indent > block_indent {
+                continue;
+            }
+            block = None;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            comments.push(comment.trim().to_string());
+            continue;
+        }
+        let Some((key, value)) = yaml_key(trimmed) else {
+            comments.clear();
+            continue;
+        };
+
+        while path.last().is_some_and(|(i, _)| *i >= indent) {
+            path.pop();
+        }
+        path.push((indent, key));
+        if value.starts_with('|') || value.starts_with('>') {
+            block = Some(indent);
+        }
+
+        if !comments.is_empty() {
+            let key: Vec<&str> = path.iter().map(|(_, key)| key.as_str()).collect();
+            notes.insert(
+                key.join("."),
+                TranslatorNote {
+                    description: Some(std::mem::take(&mut comments).join("\n")),
+                    context: None,
+                },
+            );
+        }
+    }
+
+    notes
+}
+
+/// The key of a `key: value` line, plain or quoted, with the rest of the line.
+fn yaml_key(line: &str) -> Option<(String, &str)> {
+    let (key, rest) = match line.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let end = line[1..].find(quote)? + 1;
+            (&line[1..end], &line[end + 1..])
+        }
+        _ => {
+            let colon = line
+                .match_indices(':')
+                .find(|(i, _)| line[i + 1..].is_empty() || line[i + 1..].starts_with(' '))?
+                .0;
+            (line[..colon].trim_end(), &line[colon..])
+        }
+    };
+    let value = rest.trim_start().strip_prefix(':')?;
+    if key.is_empty() || key.starts_with("- ") {
+        return None;
+    }
+    Some((key.to_string(), value.trim()))
+}
+
+/// The text of a quoted gettext string, with its escapes resolved.
+fn unquote_po(value: &str) -> String {
+    let inner = value.trim();
+    let inner = inner.strip_prefix('"').unwrap_or(inner);
+    let inner = inner.strip_suffix('"').unwrap_or(inner);
+
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -631,4 +983,211 @@ mod tests {
         );
         assert!(!result.contains_key("@itemCount"));
     }
+
+    #[test]
+    fn test_parse_po() {
+        let po = r#"msgid ""
+msgstr ""
+"Language: fr\n"
+
+#: src/App.tsx:4
+msgid "Hello {name}"
+msgstr "Bonjour {name}"
+
+msgctxt "button"
+msgid "checkout.submit"
+msgstr ""
+"Passer "
+"la commande"
+
+msgid "untranslated"
+msgstr ""
+
+msgid "{count, plural, one {# item} other {# items}}"
+msgid_plural "items"
+msgstr[0] "un \"article\""
+msgstr[1] "articles"
+
+#~ msgid "obsolete"
+#~ msgstr "obsolète"
+"#;
+        let result = TranslationParser::parse_po(po).unwrap();
+        assert_eq!(
+            result.get("Hello {name}"),
+            Some(&"Bonjour {name}".to_string())
+        );
+        assert_eq!(
+            result.get("button\u{4}checkout.submit"),
+            Some(&"Passer la commande".to_string())
+        );
+        assert!(!result.contains_key("checkout.submit"));
+        assert_eq!(
+            result.get("{count, plural, one {# item} other {# items}}"),
+            Some(&"un \"article\"".to_string())
+        );
+        assert!(!result.contains_key("untranslated"));
+        assert!(!result.contains_key("obsolete"));
+        assert!(!result.contains_key(""));
+        assert_eq!(result.len(), 3);
+    }
+
+    #[test]
+    fn test_po_context_keeps_entries_apart() {
+        let po = r#"msgctxt "verb"
+msgid "Open"
+msgstr "Ouvrir"
+
+msgctxt "adjective"
+msgid "Open"
+msgstr "Ouvert"
+
+msgid "Open"
+msgstr "Ouvrez"
+"#;
+        let result = TranslationParser::parse_po(po).unwrap();
+        assert_eq!(result.get("verb\u{4}Open"), Some(&"Ouvrir".to_string()));
+        assert_eq!(
+            result.get("adjective\u{4}Open"),
+            Some(&"Ouvert".to_string())
+        );
+        assert_eq!(result.get("Open"), Some(&"Ouvrez".to_string()));
+        assert_eq!(result.len(), 3);
+    }
+
+    fn note(description: Option<&str>, context: Option<&str>) -> TranslatorNote {
+        TranslatorNote {
+            description: description.map(str::to_string),
+            context: context.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_po_and_arb_notes() {
+        let po = r#"#. Shown on the checkout button
+#. Keep it short
+#: src/Checkout.tsx:12
+msgctxt "button"
+msgid "checkout.submit"
+msgstr "Place order"
+
+msgid "plain"
+msgstr "Plain"
+"#;
+        let notes = TranslationParser::parse_notes(Path::new("en.po"), po);
+        assert_eq!(
+            notes.get("button\u{4}checkout.submit"),
+            Some(&note(
+                Some("Shown on the checkout button\nKeep it short"),
+                Some("button")
+            ))
+        );
+        assert_eq!(notes.len(), 1);
+
+        let arb = r#"{
+  "save": "Save",
+  "@save": { "description": "Save button", "context": "toolbar" },
+  "@@locale": "en"
+}"#;
+        let notes = TranslationParser::parse_notes(Path::new("app_en.arb"), arb);
+        assert_eq!(
+            notes.get("save"),
+            Some(&note(Some("Save button"), Some("toolbar")))
+        );
+        assert_eq!(notes.len(), 1);
+    }
+
+    #[test]
+    fn test_json_notes() {
+        let json = r#"{
+  "_comment": "Greets a signed-in user",
+  "greeting": "Hello",
+  "nav": {
+    "home": "Home",
+    "@home": { "description": "Link to the start page" }
+  },
+  "order": "Order",
+  "@order": { "description": "A purchase", "context": "shop" },
+  "@cart": { "description": "Nothing to describe" }
+}"#;
+        let path = Path::new("en.json");
+        let notes = TranslationParser::parse_notes(path, json);
+        assert_eq!(
+            notes.get("greeting"),
+            Some(&note(Some("Greets a signed-in user"), None))
+        );
+        assert_eq!(
+            notes.get("nav.home"),
+            Some(&note(Some("Link to the start page"), None))
+        );
+        assert_eq!(
+            notes.get("order"),
+            Some(&note(Some("A purchase"), Some("shop")))
+        );
+        assert_eq!(notes.len(), 3);
+
+        // Notes are not messages, unless nothing they could describe exists
+        let mut keys: Vec<_> = TranslationParser::parse_content(path, json)
+            .unwrap()
+            .into_keys()
+            .collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec!["@cart.description", "greeting", "nav.home", "order"]
+        );
+    }
+
+    #[test]
+    fn test_json_description_keys_are_messages() {
+        let json = r#"{
+  "product": "Product",
+  "product_description": "Description",
+  "order": "Order",
+  "order_comment": "Comment"
+}"#;
+        let path = Path::new("en.json");
+        assert!(TranslationParser::parse_notes(path, json).is_empty());
+
+        let messages = TranslationParser::parse_content(path, json).unwrap();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(
+            messages.get("product_description"),
+            Some(&"Description".to_string())
+        );
+        assert_eq!(messages.get("order_comment"), Some(&"Comment".to_string()));
+    }
+
+    #[test]
+    fn test_yaml_notes() {
+        let yaml = r#"# Account pages
+en:
+  users:
+    # Page title
+    # Shown in the browser tab too
+    title: Users
+    intro: |
+      # Not a comment: part of the text
+      nested: text
+    # Button that opens the form
+    "new": New user
+
+  # Detached comment
+
+  count: "%{count} users"
+"#;
+        let notes = TranslationParser::parse_notes(Path::new("users.en.yml"), yaml);
+        assert_eq!(
+            notes.get("en.users.title"),
+            Some(&note(
+                Some("Page title\nShown in the browser tab too"),
+                None
+            ))
+        );
+        assert_eq!(
+            notes.get("en.users.new"),
+            Some(&note(Some("Button that opens the form"), None))
+        );
+        assert_eq!(notes.get("en"), Some(&note(Some("Account pages"), None)));
+        assert_eq!(notes.len(), 3);
+    }
 }
@@ -0,0 +1,138 @@
+use std::path::Path;
+
+use regex::Regex;
+
+use super::locale::{canonicalize, LanguageTag};
+
+/// An i18n-ally style `pathMatcher` such as `{locale}/{namespace}.json`, matched against
+/// the path of a translation file relative to its locale directory. `{locale}`,
+/// `{namespace}` and `{ext}` stand for one path segment or part of it, `{namespaces}`
+/// for one or more segments, `*` for any text within a segment and `**` for any number
+/// of directories.
+#[derive(Debug, Clone)]
+pub struct PathMatcher {
+    regex: Regex,
+}
+
+/// What a path matcher reads from the path of a translation file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathMatch {
+    /// Canonical locale.
+    pub locale: Option<String>,
+    /// Namespace of the file, with `/` between the segments of `{namespaces}`.
+    pub namespace: Option<String>,
+}
+
+impl PathMatcher {
+    pub fn new(pattern: &str) -> Option<Self> {
+        let mut regex = String::from("^");
+        let mut captured: Vec<&str> = Vec::new();
+        let mut rest = pattern.trim_start_matches("./");
+
+        while let Some(ch) = rest.chars().next() {
+            if let Some(placeholder) = rest.strip_prefix('{').and_then(|r| r.split_once('}')) {
+                let (name, after) = placeholder;
+                let group = match name {
+                    "locale" | "ext" => "[^/.]+",
+                    "namespace" => "[^/]+?",
+                    "namespaces" => ".+?",
+                    _ => return None,
+                };
+                // The regex crate has no backreferences: later uses only have to match
+                let capture = match name {
+                    "namespaces" => "namespace",
+                    other => other,
+                };
+                if captured.contains(&capture) {
+                    regex.push_str(&format!("(?:{})", group));
+                } else {
+                    regex.push_str(&format!("(?P<{}>{})", capture, group));
+                    captured.push(capture);
+                }
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("**/") {
+                regex.push_str("(?:.*/)?");
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("**") {
+                regex.push_str(".*");
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('*') {
+                regex.push_str("[^/]*");
+                rest = after;
+            } else {
+                regex.push_str(&regex::escape(&ch.to_string()));
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+        regex.push('$');
+
+        Regex::new(&regex).ok().map(|regex| Self { regex })
+    }
+
+    /// Locale and namespace of `relative`, or `None` when the path does not match or
+    /// what stands for `{locale}` is not a language tag.
+    pub fn matches(&self, relative: &Path) -> Option<PathMatch> {
+        let segments: Vec<&str> = relative.iter().filter_map(|c| c.to_str()).collect();
+        let relative = segments.join("/");
+        let captures = self.regex.captures(&relative)?;
+
+        let locale = match captures.name("locale") {
+            Some(locale) => {
+                LanguageTag::parse(locale.as_str())?;
+                Some(canonicalize(locale.as_str()))
+            }
+            None => None,
+        };
+        Some(PathMatch {
+            locale,
+            namespace: captures.name("namespace").map(|m| m.as_str().to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(pattern: &str, path: &str) -> Option<(Option<String>, Option<String>)> {
+        PathMatcher::new(pattern)
+            .unwrap()
+            .matches(Path::new(path))
+            .map(|m| (m.locale, m.namespace))
+    }
+
+    #[test]
+    fn test_locale_and_namespace_placeholders() {
+        assert_eq!(
+            matched("{locale}/{namespace}.json", "pt_BR/common.json"),
+            Some((Some("pt-BR".into()), Some("common".into())))
+        );
+        assert_eq!(
+            matched("{locale}/{namespaces}.{ext}", "en/admin/users.yml"),
+            Some((Some("en".into()), Some("admin/users".into())))
+        );
+        assert_eq!(
+            matched("{namespace}.{locale}.json", "app.zh-Hant.json"),
+            Some((Some("zh-Hant".into()), Some("app".into())))
+        );
+        assert_eq!(matched("{locale}.json", "messages.json"), None);
+        assert_eq!(matched("{locale}/{namespace}.json", "en.json"), None);
+    }
+
+    #[test]
+    fn test_globs() {
+        assert_eq!(
+            matched("**/{locale}.json", "features/auth/fr.json"),
+            Some((Some("fr".into()), None))
+        );
+        assert_eq!(
+            matched("**/{locale}.json", "fr.json"),
+            Some((Some("fr".into()), None))
+        );
+        assert_eq!(
+            matched("messages_*/{locale}.po", "messages_web/de/x.po"),
+            None
+        );
+        assert!(PathMatcher::new("{language}.json").is_none());
+    }
+}
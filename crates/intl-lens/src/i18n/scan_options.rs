@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::config::I18nConfig;
+
+/// Which files of the locale directories are scanned for translations.
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    pub depth: usize,
+    /// Files to load, when only some are; matched relative to the workspace root.
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    /// Directories every file of which is excluded, from the globs ending in `/**`.
+    exclude_dirs: GlobSet,
+    pub respect_gitignore: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self::from_config(&I18nConfig::default())
+    }
+}
+
+impl ScanOptions {
+    pub fn from_config(config: &I18nConfig) -> Self {
+        Self {
+            depth: config.scan_depth,
+            include: (!config.include_files.is_empty())
+                .then(|| compile_globs(&config.include_files)),
+            exclude: compile_globs(&config.exclude_files),
+            exclude_dirs: compile_globs(
+                &config
+                    .exclude_files
+                    .iter()
+                    .filter_map(|pattern| pattern.strip_suffix("/**"))
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            ),
+            respect_gitignore: config.respect_gitignore,
+        }
+    }
+
+    /// Whether a path relative to the workspace root is left out of the scan. Directories
+    /// are only left out by exclude globs, since include globs name files.
+    pub fn is_excluded(&self, relative: &Path, is_dir: bool) -> bool {
+        if is_dir {
+            return self.exclude_dirs.is_match(relative);
+        }
+        self.exclude.is_match(relative)
+            || self
+                .include
+                .as_ref()
+                .is_some_and(|include| !include.is_match(relative))
+    }
+}
+
+fn compile_globs(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(err) => tracing::warn!("Invalid glob '{}': {}", pattern, err),
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_and_exclude_globs() {
+        let config: I18nConfig = serde_json::from_str(
+            r#"{"includeFiles": ["locales/**/*.json"], "excludeFiles": ["**/drafts/**"]}"#,
+        )
+        .unwrap();
+        let options = ScanOptions::from_config(&config);
+
+        assert!(!options.is_excluded(Path::new("locales/en/common.json"), false));
+        assert!(options.is_excluded(Path::new("locales/en/common.yml"), false));
+        assert!(options.is_excluded(Path::new("locales/drafts/en.json"), false));
+        assert!(options.is_excluded(Path::new("locales/drafts"), true));
+        assert!(!options.is_excluded(Path::new("locales/en"), true));
+
+        let defaults = ScanOptions::default();
+        assert!(defaults.is_excluded(Path::new("locales/package.json"), false));
+        assert!(defaults.is_excluded(Path::new("i18n/node_modules/x/en.json"), false));
+        assert!(!defaults.is_excluded(Path::new("locales/en.json"), false));
+    }
+}
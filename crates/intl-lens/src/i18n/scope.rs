@@ -0,0 +1,279 @@
+use super::extractor::{argument_start, callee_chain, object_field, Preset};
+use super::lexer::{Token, TokenKind};
+
+/// Where a translation hook takes the key prefix of the function it returns.
+#[derive(Debug, Clone, Copy)]
+pub enum PrefixSource {
+    None,
+    /// A string argument, or its `namespace` field when the argument is an object.
+    Arg(usize),
+    /// A string field of an options object argument, e.g. `{ keyPrefix: "..." }`.
+    Field(usize, &'static str),
+}
+
+/// A call that returns a translation function bound to a key prefix, such as
+/// `useTranslation("ns", { keyPrefix })` or `useTranslations("Settings")`.
+#[derive(Debug)]
+pub struct ScopeHook {
+    pub callee: &'static str,
+    pub prefix: PrefixSource,
+}
+
+/// A local name bound to a translation function, visible from its declaration to the
+/// end of the enclosing block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    pub prefix: Option<String>,
+    /// Token index range the binding is visible in.
+    start: usize,
+    end: usize,
+}
+
+/// Every translation function binding in the token stream.
+pub fn collect_bindings(content: &str, tokens: &[Token], presets: &[&Preset]) -> Vec<Binding> {
+    let mut bindings = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        let Some(keyword) = token.ident() else {
+            continue;
+        };
+        if !matches!(keyword, "const" | "let" | "var") {
+            continue;
+        }
+
+        let binding = declaration(content, tokens, index, presets).or_else(|| {
+            (keyword == "let" && presets.iter().any(|preset| preset.template_scopes))
+                .then(|| template_declaration(content, tokens, index))
+                .flatten()
+        });
+
+        if let Some((name, prefix)) = binding {
+            bindings.push(Binding {
+                name,
+                prefix: prefix.filter(|prefix| !prefix.is_empty()),
+                start: index,
+                end: block_end(tokens, index),
+            });
+        }
+    }
+
+    bindings
+}
+
+/// The innermost binding for `name` visible at token `index`.
+pub fn binding_at<'a>(bindings: &'a [Binding], name: &str, index: usize) -> Option<&'a Binding> {
+    bindings
+        .iter()
+        .rev()
+        .find(|binding| binding.name == name && binding.start <= index && index <= binding.end)
+}
+
+/// `const { t } = useTranslation(...)`, `const { t: tp } = ...`, `const [t] = ...` and
+/// `const t = await getTranslations(...)`.
+fn declaration(
+    content: &str,
+    tokens: &[Token],
+    keyword: usize,
+    presets: &[&Preset],
+) -> Option<(String, Option<String>)> {
+    let (name, mut index) = binding_pattern(tokens, keyword + 1)?;
+
+    // Skip a type annotation: `const t: TFunction = ...`
+    while !tokens.get(index)?.is_punct('=') {
+        if index > keyword + 8 || tokens[index].is_punct(';') {
+            return None;
+        }
+        index += 1;
+    }
+    index += 1;
+
+    if tokens.get(index)?.ident() == Some("await") {
+        index += 1;
+    }
+
+    let mut paren = index;
+    while !tokens.get(paren)?.is_punct('(') {
+        let token = &tokens[paren];
+        if token.ident().is_none() && !token.is_punct('.') {
+            return None;
+        }
+        paren += 1;
+    }
+
+    let (chain, _) = callee_chain(tokens, paren)?;
+    let hook = presets
+        .iter()
+        .flat_map(|preset| preset.scope_hooks)
+        .find(|hook| chain.ends_with(&hook.callee.split('.').collect::<Vec<_>>()))?;
+
+    let prefix = match hook.prefix {
+        PrefixSource::None => None,
+        PrefixSource::Arg(arg) => {
+            let start = argument_start(tokens, paren, arg)?;
+            match tokens[start].static_string() {
+                Some(value) => Some(value.to_string()),
+                None => object_field(content, tokens, start, "namespace")
+                    .map(|range| content[range].to_string()),
+            }
+        }
+        PrefixSource::Field(arg, field) => argument_start(tokens, paren, arg)
+            .and_then(|start| object_field(content, tokens, start, field))
+            .map(|range| content[range].to_string()),
+    };
+
+    Some((name, prefix))
+}
+
+/// The name bound by a declaration pattern starting at `index`, and the index after it.
+/// For object patterns only the `t` property is of interest.
+fn binding_pattern(tokens: &[Token], index: usize) -> Option<(String, usize)> {
+    let token = tokens.get(index)?;
+
+    if let Some(name) = token.ident() {
+        return Some((name.to_string(), index + 1));
+    }
+
+    if token.is_punct('[') {
+        let name = tokens.get(index + 1)?.ident()?;
+        let close = (index + 2..tokens.len()).find(|&i| tokens[i].is_punct(']'))?;
+        return Some((name.to_string(), close + 1));
+    }
+
+    if token.is_punct('{') {
+        let close = (index + 1..tokens.len()).find(|&i| tokens[i].is_punct('}'))?;
+        let mut name = None;
+        for i in index + 1..close {
+            if tokens[i].ident() != Some("t") || tokens[i - 1].is_punct(':') {
+                continue;
+            }
+            name = match (tokens.get(i + 1), tokens.get(i + 2)) {
+                (Some(colon), Some(alias)) if colon.is_punct(':') => alias.ident(),
+                _ => Some("t"),
+            };
+        }
+        return Some((name?.to_string(), close + 1));
+    }
+
+    None
+}
+
+/// Transloco's structural directive: `*transloco="let t; read: 'dashboard'"`.
+fn template_declaration(
+    content: &str,
+    tokens: &[Token],
+    keyword: usize,
+) -> Option<(String, Option<String>)> {
+    let in_attribute = content[..tokens[keyword].start]
+        .trim_end()
+        .ends_with(['"', '\'']);
+    if !in_attribute {
+        return None;
+    }
+
+    let name = tokens.get(keyword + 1)?.ident()?;
+    let mut prefix = None;
+
+    for index in keyword + 2..(keyword + 12).min(tokens.len()) {
+        let token = &tokens[index];
+        if token.ident() == Some("read") && tokens.get(index + 1)?.is_punct(':') {
+            let value = tokens.get(index + 2)?;
+            prefix = value
+                .static_string()
+                .map(|_| content[value.inner_range(content)].to_string());
+            break;
+        }
+        if !matches!(
+            token.kind,
+            TokenKind::Ident(_) | TokenKind::Str(_) | TokenKind::Punct(';' | ':' | ',')
+        ) {
+            break;
+        }
+    }
+
+    Some((name.to_string(), prefix))
+}
+
+/// Index of the `}` closing the block that contains token `index`, or the last token.
+fn block_end(tokens: &[Token], index: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(index) {
+        if token.is_punct('{') {
+            depth += 1;
+        } else if token.is_punct('}') {
+            if depth == 0 {
+                return i;
+            }
+            depth -= 1;
+        }
+    }
+    tokens.len().saturating_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::extractor::PRESETS;
+    use super::super::language::LanguageFamily;
+    use super::super::lexer;
+    use super::*;
+
+    fn bindings(content: &str, family: LanguageFamily) -> Vec<(String, Option<String>)> {
+        let scan = lexer::scan(content, Some(family));
+        let presets: Vec<&Preset> = PRESETS.iter().collect();
+        collect_bindings(content, &scan.tokens, &presets)
+            .into_iter()
+            .map(|binding| (binding.name, binding.prefix))
+            .collect()
+    }
+
+    #[test]
+    fn test_react_i18next_key_prefix() {
+        let content = r#"const { t: tp, i18n } = useTranslation("account", { keyPrefix: "settings.profile" });"#;
+        assert_eq!(
+            bindings(content, LanguageFamily::JavaScript),
+            vec![("tp".to_string(), Some("settings.profile".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_next_intl_namespace() {
+        let content = r#"
+            const t = useTranslations("Settings");
+            const tServer = await getTranslations({ locale, namespace: "Server" });
+        "#;
+        assert_eq!(
+            bindings(content, LanguageFamily::JavaScript),
+            vec![
+                ("t".to_string(), Some("Settings".to_string())),
+                ("tServer".to_string(), Some("Server".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transloco_read() {
+        let content = r#"<ng-container *transloco="let t; read: 'dashboard'">{{ t('title') }}</ng-container>"#;
+        assert_eq!(
+            bindings(content, LanguageFamily::Html),
+            vec![("t".to_string(), Some("dashboard".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_binding_ends_with_block() {
+        let content = r#"
+            function A() { const { t } = useTranslation("ns", { keyPrefix: "a" }); t("x"); }
+            function B() { t("y"); }
+        "#;
+        let scan = lexer::scan(content, Some(LanguageFamily::JavaScript));
+        let presets: Vec<&Preset> = PRESETS.iter().collect();
+        let bindings = collect_bindings(content, &scan.tokens, &presets);
+
+        let position = |needle: &str| {
+            let offset = content.find(needle).unwrap();
+            scan.tokens.iter().position(|t| t.start == offset).unwrap()
+        };
+        assert!(binding_at(&bindings, "t", position("\"x\"")).is_some());
+        assert!(binding_at(&bindings, "t", position("\"y\"")).is_none());
+    }
+}
@@ -5,14 +5,35 @@ use dashmap::DashMap;
 use globset::Glob;
 use walkdir::WalkDir;
 
-use super::parser::TranslationParser;
+use super::arb::{self, ArbMetadata};
+use super::fallback::LocaleFallback;
+use super::fuzzy;
+use super::gitignore::Gitignore;
+use super::inline::{self, InlineMessage};
+use super::key_pattern::KeyPattern;
+use super::language::{LanguageFamily, SourceLanguage};
+use super::locale::{canonicalize, is_locale_code};
+use super::locale_module::{self, ModuleMessage};
+use super::parser::{TranslationParser, TranslatorNote, PO_CONTEXT_SEPARATOR};
+use super::path_matcher::PathMatcher;
+use super::scan_options::ScanOptions;
 
 #[derive(Debug, Clone)]
 pub struct TranslationEntry {
     pub value: String,
     pub file_path: PathBuf,
+    /// Line of the entry when it is known from parsing, as for messages defined in
+    /// source files.
+    pub line: Option<usize>,
+    /// What the translation file tells translators about the message.
+    pub description: Option<String>,
+    /// Disambiguating context, such as a gettext `msgctxt`.
+    pub context: Option<String>,
 }
 
+/// Translations by locale, then key.
+type Messages = HashMap<String, HashMap<String, TranslationEntry>>;
+
 #[derive(Debug, Clone)]
 pub struct TranslationLocation {
     pub file_path: PathBuf,
@@ -22,7 +43,24 @@ pub struct TranslationLocation {
 pub struct TranslationStore {
     translations: DashMap<String, HashMap<String, TranslationEntry>>,
     locale_files: DashMap<String, HashSet<PathBuf>>,
+    /// Messages components define for themselves, such as vue-i18n `<i18n>` blocks,
+    /// by component path.
+    components: DashMap<PathBuf, Messages>,
+    /// Locale and key of every global message a source file registers, such as
+    /// svelte-i18n `addMessages`, so indexing the file again replaces only its own.
+    registered: DashMap<PathBuf, Vec<(String, String)>>,
+    /// ARB `@key` metadata by key, from the source locale's file when it has any.
+    arb_metadata: DashMap<String, ArbMetadata>,
     workspace_root: PathBuf,
+    /// Locale of the default messages written in source code, such as react-intl's
+    /// `defaultMessage`.
+    source_locale: String,
+    fallback: LocaleFallback,
+    /// How locale and namespace are read from paths, instead of guessing them.
+    path_matcher: Option<PathMatcher>,
+    /// Namespace of each file whose path gives one, such as a Laravel group.
+    namespaces: DashMap<PathBuf, String>,
+    scan_options: ScanOptions,
 }
 
 impl TranslationStore {
@@ -30,48 +68,182 @@ impl TranslationStore {
         Self {
             translations: DashMap::new(),
             locale_files: DashMap::new(),
+            components: DashMap::new(),
+            registered: DashMap::new(),
+            arb_metadata: DashMap::new(),
             workspace_root,
+            source_locale: "en".to_string(),
+            fallback: LocaleFallback::default(),
+            path_matcher: None,
+            namespaces: DashMap::new(),
+            scan_options: ScanOptions::default(),
+        }
+    }
+
+    pub fn with_source_locale(mut self, source_locale: &str) -> Self {
+        self.source_locale = canonicalize(source_locale);
+        self
+    }
+
+    pub fn with_path_matcher(mut self, path_matcher: Option<PathMatcher>) -> Self {
+        self.path_matcher = path_matcher;
+        self
+    }
+
+    pub fn with_scan_options(mut self, scan_options: ScanOptions) -> Self {
+        self.scan_options = scan_options;
+        self
+    }
+
+    pub fn with_fallback(mut self, fallback: LocaleFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// The locale of `translations` that provides the message of `locale`, which is
+    /// `locale` itself when it has one.
+    pub fn resolve_locale<'a>(
+        &self,
+        locale: &'a str,
+        translations: &'a HashMap<String, TranslationEntry>,
+    ) -> Option<&'a str> {
+        if translations.contains_key(locale) {
+            return Some(locale);
         }
+        self.fallback
+            .chain(locale)
+            .into_iter()
+            .find_map(|fallback| translations.get_key_value(&fallback))
+            .map(|(fallback, _)| fallback.as_str())
     }
 
-    pub fn scan_and_load(&self, locale_paths: &[String]) {
+    /// Load the translations of the locale directories, and return the translation files
+    /// skipped because no locale could be read from them.
+    pub fn scan_and_load(&self, locale_paths: &[String]) -> Vec<PathBuf> {
+        let mut skipped = Vec::new();
         for locale_path in locale_paths {
             let full_path = self.workspace_root.join(locale_path);
             if full_path.exists() {
-                self.scan_directory(&full_path);
+                skipped.extend(self.scan_directory(&full_path));
             }
         }
+        skipped
     }
 
-    fn scan_directory(&self, dir: &Path) {
+    fn scan_directory(&self, dir: &Path) -> Vec<PathBuf> {
         let json_glob = Glob::new("*.json").unwrap().compile_matcher();
         let yaml_glob = Glob::new("*.{yaml,yml}").unwrap().compile_matcher();
         let php_glob = Glob::new("*.php").unwrap().compile_matcher();
         let arb_glob = Glob::new("*.arb").unwrap().compile_matcher();
+        let po_glob = Glob::new("*.po").unwrap().compile_matcher();
+        let source_glob = Glob::new("*.{js,mjs,cjs,ts,mts,cts,svelte}")
+            .unwrap()
+            .compile_matcher();
+
+        let options = &self.scan_options;
+        let mut gitignore = options
+            .respect_gitignore
+            .then(|| Gitignore::for_dir(&self.workspace_root, dir));
+        let mut skipped = Vec::new();
 
-        for entry in WalkDir::new(dir)
-            .max_depth(3)
+        let entries = WalkDir::new(dir)
+            .max_depth(options.depth)
             .into_iter()
-            .filter_map(|e| e.ok())
-        {
+            .filter_entry(|entry| {
+                if entry.depth() == 0 {
+                    return true;
+                }
+                let path = entry.path();
+                let is_dir = entry.file_type().is_dir();
+                let relative = path.strip_prefix(&self.workspace_root).unwrap_or(path);
+                if options.is_excluded(relative, is_dir) {
+                    return false;
+                }
+                match &mut gitignore {
+                    Some(gitignore) if gitignore.is_ignored(path, is_dir) => false,
+                    Some(gitignore) => {
+                        if is_dir {
+                            gitignore.add_dir(path);
+                        }
+                        true
+                    }
+                    None => true,
+                }
+            });
+        for entry in entries.filter_map(|e| e.ok()) {
             let path = entry.path();
             let file_name = path.file_name().unwrap_or_default();
+            if !path.is_file() {
+                continue;
+            }
 
-            if path.is_file()
-                && (json_glob.is_match(file_name)
-                    || yaml_glob.is_match(file_name)
-                    || php_glob.is_match(file_name)
-                    || arb_glob.is_match(file_name))
+            let is_source = source_glob.is_match(file_name);
+            let is_arb = arb_glob.is_match(file_name);
+            let is_yaml = yaml_glob.is_match(file_name);
+            let is_php = php_glob.is_match(file_name);
+            if !(is_source
+                || is_arb
+                || is_yaml
+                || is_php
+                || json_glob.is_match(file_name)
+                || po_glob.is_match(file_name))
             {
-                if let Some(locale) = self.extract_locale_from_path(path) {
-                    self.locale_files
-                        .entry(locale.clone())
-                        .or_default()
-                        .insert(path.to_path_buf());
-                    self.load_translation_file(path, &locale);
+                continue;
+            }
+
+            let (locale, namespace) = match &self.path_matcher {
+                Some(matcher) => {
+                    let Some(found) = path
+                        .strip_prefix(dir)
+                        .ok()
+                        .and_then(|relative| matcher.matches(relative))
+                    else {
+                        if !is_source {
+                            skipped.push(path.to_path_buf());
+                        }
+                        continue;
+                    };
+                    let locale = found.locale.or_else(|| self.extract_locale_from_path(path));
+                    (locale, found.namespace)
+                }
+                None => match is_php.then(|| laravel_group(dir, path)).flatten() {
+                    Some((locale, group)) => (Some(locale), Some(group)),
+                    None => (self.extract_locale_from_path(path), None),
+                },
+            };
+            let locale = locale.map(|locale| canonicalize(&locale));
+            if let Some(namespace) = &namespace {
+                self.namespaces
+                    .insert(path.to_path_buf(), namespace.clone());
+            }
+
+            if is_source {
+                self.load_source_file(path, locale);
+                continue;
+            }
+            if is_arb {
+                if !self.load_arb_file(path, locale) {
+                    skipped.push(path.to_path_buf());
                 }
+                continue;
+            }
+            if is_yaml && self.load_locale_rooted_yaml(path) {
+                continue;
             }
+            let Some(locale) = locale else {
+                skipped.push(path.to_path_buf());
+                continue;
+            };
+            self.locale_files
+                .entry(locale.clone())
+                .or_default()
+                .insert(path.to_path_buf());
+            // The namespace of a PHP file is the group its keys are prefixed with
+            let group = namespace.filter(|_| is_php);
+            self.load_translation_file(path, &locale, group.as_deref());
         }
+
+        skipped
     }
 
     fn extract_locale_from_path(&self, path: &Path) -> Option<String> {
@@ -81,6 +253,13 @@ impl TranslationStore {
             return Some(file_stem.to_string());
         }
 
+        // Rails naming convention: devise.en.yml, users.pt-BR.yml
+        if let Some((_, suffix)) = file_stem.rsplit_once('.') {
+            if is_locale_code(suffix) {
+                return Some(suffix.to_string());
+            }
+        }
+
         // Handle ARB naming convention: app_en.arb, app_es.arb, etc.
         if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
             if ext == "arb" {
@@ -102,40 +281,122 @@ impl TranslationStore {
         None
     }
 
-    fn load_translation_file(&self, path: &Path, locale: &str) {
-        match TranslationParser::parse_file(path) {
-            Ok(translations) => {
-                let mut locale_map = self.translations.entry(locale.to_string()).or_default();
-                let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                let file_stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-                let prefix =
-                    if extension == "php" && !file_stem.is_empty() && !is_locale_code(file_stem) {
-                        Some(file_stem)
-                    } else {
-                        None
-                    };
+    /// Load an ARB file under its `@@locale`, falling back to the locale of its path, and
+    /// keep the metadata of its messages. Returns whether it had a locale.
+    fn load_arb_file(&self, path: &Path, locale: Option<String>) -> bool {
+        let file = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| arb::parse_arb_file(&content).ok())
+            .unwrap_or_default();
+        let Some(locale) = file.locale.map(|locale| canonicalize(&locale)).or(locale) else {
+            return false;
+        };
 
-                for (key, value) in translations {
-                    let full_key = match prefix {
-                        Some(prefix) => format!("{}.{}", prefix, key),
-                        None => key,
-                    };
+        self.locale_files
+            .entry(locale.clone())
+            .or_default()
+            .insert(path.to_path_buf());
+        self.load_translation_file(path, &locale, None);
 
-                    locale_map.insert(
-                        full_key,
-                        TranslationEntry {
-                            value,
-                            file_path: path.to_path_buf(),
-                        },
-                    );
-                }
+        let is_template = locale == self.source_locale;
+        for (key, metadata) in file.metadata {
+            if is_template {
+                self.arb_metadata.insert(key, metadata);
+            } else {
+                self.arb_metadata.entry(key).or_insert(metadata);
+            }
+        }
+        true
+    }
 
-                tracing::debug!(
-                    "Loaded {} translations from {:?} for locale {}",
-                    locale_map.len(),
-                    path,
-                    locale
-                );
+    pub fn arb_metadata(&self, key: &str) -> Option<ArbMetadata> {
+        self.arb_metadata
+            .get(key)
+            .map(|entry| entry.value().clone())
+    }
+
+    /// Load a source file found among the locale files: the default export of a locale
+    /// module such as `en.ts`, or the messages it registers or describes.
+    fn load_source_file(&self, path: &Path, locale: Option<String>) {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return;
+        };
+
+        let module = locale.and_then(|locale| {
+            locale_module::module_messages(path, &content).map(|messages| (locale, messages))
+        });
+        if let Some((locale, messages)) = module {
+            self.insert_module_messages(&locale, messages);
+            return;
+        }
+
+        let messages = self.source_messages(&content, LanguageFamily::from_path(path));
+        if !messages.is_empty() {
+            self.set_registered_messages(path, messages);
+        }
+    }
+
+    fn insert_module_messages(&self, locale: &str, messages: Vec<ModuleMessage>) {
+        let mut locale_map = self.translations.entry(locale.to_string()).or_default();
+        for message in messages {
+            locale_map.insert(
+                message.key,
+                TranslationEntry {
+                    value: message.value,
+                    file_path: message.path,
+                    line: message.line,
+                    description: None,
+                    context: None,
+                },
+            );
+        }
+    }
+
+    /// Load a YAML file whose top-level keys are all locales (`en:`, `fr:`), as Rails
+    /// writes them, into each of those locales. Returns false for any other file.
+    fn load_locale_rooted_yaml(&self, path: &Path) -> bool {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return false;
+        };
+        let Ok(roots) = TranslationParser::parse_yaml_roots(&content) else {
+            return false;
+        };
+        if roots.is_empty() || !roots.iter().all(|(locale, _)| is_locale_code(locale)) {
+            return false;
+        }
+
+        let notes = TranslationParser::parse_notes(path, &content);
+        for (name, translations) in roots {
+            let locale = canonicalize(&name);
+            self.locale_files
+                .entry(locale.clone())
+                .or_default()
+                .insert(path.to_path_buf());
+            let root = format!("{}.", name);
+            let notes = notes
+                .iter()
+                .filter_map(|(key, note)| {
+                    Some((key.strip_prefix(&root)?.to_string(), note.clone()))
+                })
+                .collect();
+            self.insert_translations(path, &locale, None, translations, notes);
+        }
+
+        true
+    }
+
+    fn load_translation_file(&self, path: &Path, locale: &str, group: Option<&str>) {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Failed to read {:?}: {}", path, e);
+                return;
+            }
+        };
+        match TranslationParser::parse_content(path, &content) {
+            Ok(translations) => {
+                let notes = TranslationParser::parse_notes(path, &content);
+                self.insert_translations(path, locale, group, translations, notes);
             }
             Err(e) => {
                 tracing::warn!("Failed to parse {:?}: {}", path, e);
@@ -143,9 +404,198 @@ impl TranslationStore {
         }
     }
 
+    /// Insert the translations of one file with their translator notes. `group`
+    /// prefixes every key, and defaults to the file stem for PHP files.
+    fn insert_translations(
+        &self,
+        path: &Path,
+        locale: &str,
+        group: Option<&str>,
+        translations: HashMap<String, String>,
+        mut notes: HashMap<String, TranslatorNote>,
+    ) {
+        let mut locale_map = self.translations.entry(locale.to_string()).or_default();
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let file_stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        let prefix = group.or_else(|| {
+            (extension == "php" && !file_stem.is_empty() && !is_locale_code(file_stem))
+                .then_some(file_stem)
+        });
+
+        for (key, value) in translations {
+            let note = notes.remove(&key).unwrap_or_default();
+            let full_key = match prefix {
+                Some(prefix) => format!("{}.{}", prefix, key),
+                None => key,
+            };
+
+            locale_map.insert(
+                full_key,
+                TranslationEntry {
+                    value,
+                    file_path: path.to_path_buf(),
+                    line: None,
+                    description: note.description,
+                    context: note.context,
+                },
+            );
+        }
+
+        tracing::debug!(
+            "Loaded {} translations from {:?} for locale {}",
+            locale_map.len(),
+            path,
+            locale
+        );
+    }
+
+    /// Index the messages a source document defines: the `<i18n>` blocks of a Vue
+    /// component, and svelte-i18n `addMessages` literals and react-intl default
+    /// messages, which apply everywhere.
+    pub fn index_document(&self, path: &Path, content: &str, source: &SourceLanguage) {
+        let family = source.family();
+        if family == Some(LanguageFamily::Vue) {
+            self.set_component_messages(path, inline::component_messages(content));
+        }
+        // A file that registered messages before may have had the calls deleted
+        let registers = content.contains("addMessages") || content.contains("defineMessage");
+        if registers || self.registered.contains_key(path) {
+            self.set_registered_messages(path, self.source_messages(content, family));
+        }
+    }
+
+    fn source_messages(&self, content: &str, family: Option<LanguageFamily>) -> Vec<InlineMessage> {
+        let mut messages = Vec::new();
+        if content.contains("addMessages") {
+            messages.extend(inline::registered_messages(content, family));
+        }
+        if content.contains("defineMessage") {
+            messages.extend(inline::default_messages(
+                content,
+                family,
+                &self.source_locale,
+            ));
+        }
+        messages
+    }
+
+    fn set_component_messages(&self, component: &Path, messages: Vec<InlineMessage>) {
+        if messages.is_empty() {
+            self.components.remove(component);
+            return;
+        }
+        self.components.insert(
+            component.to_path_buf(),
+            Self::group_messages(component, messages),
+        );
+    }
+
+    /// Replace the global messages defined in `path` with `messages`. Keys another file
+    /// already translates keep that translation.
+    fn set_registered_messages(&self, path: &Path, messages: Vec<InlineMessage>) {
+        if let Some((_, previous)) = self.registered.remove(path) {
+            for (locale, key) in previous {
+                if let Some(mut locale_map) = self.translations.get_mut(&locale) {
+                    if locale_map
+                        .get(&key)
+                        .is_some_and(|entry| entry.file_path == path)
+                    {
+                        locale_map.remove(&key);
+                    }
+                }
+                // A locale only registered in source goes away with its last message
+                if !self.locale_files.contains_key(&locale) {
+                    self.translations
+                        .remove_if(&locale, |_, locale_map| locale_map.is_empty());
+                }
+            }
+        }
+
+        let mut inserted = Vec::new();
+        for (locale, entries) in Self::group_messages(path, messages) {
+            let mut locale_map = self.translations.entry(locale.clone()).or_default();
+            for (key, entry) in entries {
+                if let std::collections::hash_map::Entry::Vacant(slot) = locale_map.entry(key) {
+                    inserted.push((locale.clone(), slot.key().clone()));
+                    slot.insert(entry);
+                }
+            }
+        }
+        if !inserted.is_empty() {
+            self.registered.insert(path.to_path_buf(), inserted);
+        }
+    }
+
+    fn group_messages(path: &Path, messages: Vec<InlineMessage>) -> Messages {
+        let mut grouped = Messages::new();
+        for message in messages {
+            grouped
+                .entry(canonicalize(&message.locale))
+                .or_default()
+                .insert(
+                    message.key,
+                    TranslationEntry {
+                        value: message.value,
+                        file_path: path.to_path_buf(),
+                        line: Some(message.line),
+                        description: message.description,
+                        context: None,
+                    },
+                );
+        }
+        grouped
+    }
+
+    /// Translations of `key` that `component` defines itself, by locale.
+    pub fn component_translations(
+        &self,
+        component: &Path,
+        key: &str,
+    ) -> HashMap<String, TranslationEntry> {
+        self.components
+            .get(component)
+            .map(|messages| {
+                messages
+                    .iter()
+                    .filter_map(|(locale, entries)| {
+                        entries
+                            .get(key)
+                            .map(|entry| (locale.clone(), entry.clone()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Keys `component` defines itself.
+    pub fn component_keys(&self, component: &Path) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .components
+            .get(component)
+            .map(|messages| messages.values().flat_map(|m| m.keys().cloned()).collect())
+            .unwrap_or_default();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    /// Translations of `key` as seen from `component`: its own messages first, then
+    /// the global ones for the remaining locales.
+    pub fn get_all_translations_in(
+        &self,
+        key: &str,
+        component: Option<&Path>,
+    ) -> HashMap<String, TranslationEntry> {
+        let mut translations = self.get_all_translations(key);
+        if let Some(component) = component {
+            translations.extend(self.component_translations(component, key));
+        }
+        translations
+    }
+
     pub fn get_translation(&self, key: &str, locale: &str) -> Option<String> {
         self.translations
-            .get(locale)
+            .get(&canonicalize(locale))
             .and_then(|map| map.get(key).map(|e| e.value.clone()))
     }
 
@@ -160,21 +610,46 @@ impl TranslationStore {
         result
     }
 
-    pub fn get_translation_location(&self, key: &str, locale: &str) -> Option<TranslationLocation> {
-        self.translations.get(locale).and_then(|map| {
-            map.get(key).map(|e| {
-                let line = Self::find_key_line_in_file(&e.file_path, key).unwrap_or(0);
-                TranslationLocation {
-                    file_path: e.file_path.clone(),
-                    line,
-                }
-            })
-        })
+    /// Where `entry`, the translation of `key`, is defined.
+    pub fn location_of(key: &str, entry: &TranslationEntry) -> TranslationLocation {
+        let line = entry
+            .line
+            .or_else(|| Self::find_key_line_in_file(&entry.file_path, key))
+            .unwrap_or(0);
+        TranslationLocation {
+            file_path: entry.file_path.clone(),
+            line,
+        }
     }
 
     fn find_key_line_in_file(file_path: &Path, key: &str) -> Option<usize> {
         let content = std::fs::read_to_string(file_path).ok()?;
 
+        // Gettext keys are whole messages and may contain dots
+        if file_path.extension().and_then(|e| e.to_str()) == Some("po") {
+            let quote = |text: &str| format!("\"{}\"", text.replace('"', "\\\""));
+            let (context, msgid) = match key.split_once(PO_CONTEXT_SEPARATOR) {
+                Some((context, msgid)) => (Some(format!("msgctxt {}", quote(context))), msgid),
+                None => (None, key),
+            };
+            let msgid = format!("msgid {}", quote(msgid));
+            let lines: Vec<&str> = content.lines().map(str::trim).collect();
+            return lines.iter().enumerate().position(|(index, line)| {
+                let previous = index.checked_sub(1).map(|i| lines[i]);
+                *line == msgid
+                    && match &context {
+                        Some(context) => previous == Some(context.as_str()),
+                        None => !previous.is_some_and(|line| line.starts_with("msgctxt")),
+                    }
+            });
+        }
+
+        // Flat keys such as Laravel's JSON strings may contain dots themselves
+        let quoted = format!("\"{}\"", key);
+        if let Some(line) = content.lines().position(|line| line.contains(&quoted)) {
+            return Some(line);
+        }
+
         let last_part = key.split('.').next_back().unwrap_or(key);
         let search_patterns = [
             format!("\"{}\"", last_part),
@@ -204,6 +679,23 @@ impl TranslationStore {
         keys.into_iter().collect()
     }
 
+    /// Existing keys a dynamic key can resolve to, sorted.
+    pub fn keys_matching(&self, pattern: &KeyPattern) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .get_all_keys()
+            .into_iter()
+            .filter(|key| pattern.matches(key))
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Existing keys that look like a typo of `key`, best match first.
+    pub fn find_similar_keys(&self, key: &str, limit: usize) -> Vec<String> {
+        let all_keys = self.get_all_keys();
+        fuzzy::find_similar_keys(key, &all_keys, limit)
+    }
+
     pub fn get_locales(&self) -> Vec<String> {
         self.translations.iter().map(|e| e.key().clone()).collect()
     }
@@ -217,79 +709,600 @@ impl TranslationStore {
     pub fn get_locale_file_paths(&self, locale: &str) -> Vec<PathBuf> {
         let mut result: Vec<PathBuf> = self
             .locale_files
-            .get(locale)
+            .get(&canonicalize(locale))
             .map(|set| set.value().iter().cloned().collect())
             .unwrap_or_default();
         result.sort();
         result
     }
 
+    /// Narrow `candidates` (files of `locale`) down to the ones a new `key` belongs in.
+    ///
+    /// A file whose stem matches the namespace wins outright. Otherwise each file is scored
+    /// by the longest run of leading key segments it already contains (`auth.login.title`
+    /// scores 2 against a file holding `auth.login.button`), with a file stem matching the
+    /// first segment counting as one segment. A single best file is returned on its own;
+    /// ties are all returned so the caller can let the user choose.
+    pub fn resolve_target_files(
+        &self,
+        locale: &str,
+        key: &str,
+        namespace: Option<&str>,
+        candidates: &[PathBuf],
+    ) -> Vec<PathBuf> {
+        let mut candidates = candidates.to_vec();
+        candidates.sort();
+        if candidates.len() <= 1 {
+            return candidates;
+        }
+
+        let file_stem = |path: &Path| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.to_string())
+                .unwrap_or_default()
+        };
+
+        if let Some(namespace) = namespace {
+            let matching: Vec<PathBuf> = candidates
+                .iter()
+                .filter(|path| match self.namespaces.get(*path) {
+                    Some(recorded) => recorded.value() == namespace,
+                    None => file_stem(path) == namespace,
+                })
+                .cloned()
+                .collect();
+            if !matching.is_empty() {
+                return matching;
+            }
+        }
+
+        let key_segments: Vec<&str> = key.split('.').collect();
+        let mut scores: HashMap<&PathBuf, usize> = candidates
+            .iter()
+            .map(|path| {
+                let stem_score = usize::from(file_stem(path) == key_segments[0]);
+                (path, stem_score)
+            })
+            .collect();
+
+        if let Some(locale_map) = self.translations.get(locale) {
+            for (existing_key, entry) in locale_map.iter() {
+                let Some(score) = scores.get_mut(&entry.file_path) else {
+                    continue;
+                };
+                let shared = existing_key
+                    .split('.')
+                    .zip(&key_segments)
+                    .take_while(|(a, b)| a == *b)
+                    .count()
+                    // Never count the leaf itself: the key is missing, a sibling is what matters
+                    .min(key_segments.len().saturating_sub(1));
+                *score = (*score).max(shared);
+            }
+        }
+
+        let best = scores.values().copied().max().unwrap_or(0);
+        candidates
+            .iter()
+            .filter(|path| scores.get(path).copied().unwrap_or(0) == best)
+            .cloned()
+            .collect()
+    }
+
+    /// Locales without a message for `key`. A regional locale is not missing a message
+    /// its parent or configured chain provides, while the default fallback locale does
+    /// not count: it is what users of an untranslated locale see.
     pub fn get_missing_locales(&self, key: &str) -> Vec<String> {
-        let all_locales: Vec<String> = self.get_locales();
-        all_locales
+        let has_key = |locale: &str| {
+            self.translations
+                .get(locale)
+                .is_some_and(|m| m.contains_key(key))
+        };
+        self.get_locales()
             .into_iter()
             .filter(|locale| {
-                self.translations
-                    .get(locale)
-                    .map(|m| !m.contains_key(key))
-                    .unwrap_or(true)
+                !has_key(locale)
+                    && !self
+                        .fallback
+                        .variant_chain(locale)
+                        .iter()
+                        .any(|fallback| has_key(fallback))
             })
             .collect()
     }
 }
 
-fn is_locale_code(s: &str) -> bool {
-    let locale_patterns = [
-        r"^[a-z]{2}$",
-        r"^[a-z]{2}[-_][A-Z]{2}$",
-        r"^[a-z]{2}[-_][a-z]{2}$",
-    ];
+/// Locale and key group of a Laravel PHP translation file below the `lang` directory
+/// `dir`. Files in nested directories are grouped by their path and package files are
+/// namespaced by the package: `en/admin/users.php` holds the `admin/users` group and
+/// `vendor/courier/en/messages.php` the `courier::messages` group.
+fn laravel_group(dir: &Path, path: &Path) -> Option<(String, String)> {
+    let relative = path.strip_prefix(dir).ok()?;
+    let stem = relative.file_stem()?.to_str()?;
+    let dirs: Vec<&str> = relative
+        .parent()?
+        .iter()
+        .filter_map(|c| c.to_str())
+        .collect();
 
-    for pattern in &locale_patterns {
-        if regex::Regex::new(pattern).unwrap().is_match(s) {
-            return true;
-        }
-    }
+    let (package, dirs) = match dirs.iter().position(|dir| *dir == "vendor") {
+        Some(index) if dirs.len() > index + 2 => (Some(dirs[index + 1]), &dirs[index + 2..]),
+        _ => (None, &dirs[..]),
+    };
+    let locale = dirs.iter().position(|dir| is_locale_code(dir))?;
+    let group = dirs[locale + 1..]
+        .iter()
+        .copied()
+        .chain(std::iter::once(stem))
+        .collect::<Vec<_>>()
+        .join("/");
 
-    let common_locales = [
-        "en", "en-US", "en-GB", "es", "es-ES", "fr", "fr-FR", "de", "de-DE", "it", "it-IT", "pt",
-        "pt-BR", "ja", "ja-JP", "ko", "ko-KR", "zh", "zh-CN", "zh-TW", "ru", "ru-RU", "ar",
-        "ar-SA", "vi", "vi-VN",
-    ];
+    let group = match package {
+        Some(package) => format!("{}::{}", package, group),
+        None => group,
+    };
+    Some((dirs[locale].to_string(), group))
+}
 
-    common_locales.contains(&s)
+/// Extract the locale from ARB file names such as `app_en`, `messages_en_US` or
+/// `intl_zh_Hant_TW`: the longest run of trailing `_` parts that is a locale.
+pub(crate) fn extract_locale_from_arb_filename(file_stem: &str) -> Option<String> {
+    let starts = file_stem.match_indices('_').map(|(index, _)| index + 1);
+    starts
+        .map(|start| &file_stem[start..])
+        .find(|suffix| is_locale_code(suffix))
+        .map(str::to_string)
 }
 
-/// Extract locale from ARB filename patterns like "app_en", "messages_en_US", "intl_vi"
-fn extract_locale_from_arb_filename(file_stem: &str) -> Option<String> {
-    // Common ARB file prefixes
-    let prefixes = ["app_", "intl_", "messages_", "l10n_", "strings_"];
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
 
-    for prefix in prefixes {
-        if let Some(locale_part) = file_stem.strip_prefix(prefix) {
-            if is_locale_code(locale_part) {
-                return Some(locale_part.to_string());
-            }
-        }
+    fn write_locale_files(name: &str, files: &[(&str, &str)]) -> TempDir {
+        TempDir::with_files(&format!("store-{}", name), files)
     }
 
-    // Try splitting by underscore and check if last part(s) form a locale
-    let parts: Vec<&str> = file_stem.split('_').collect();
-    if parts.len() >= 2 {
-        // Try last part as locale (e.g., "app_en" -> "en")
-        let last = parts[parts.len() - 1];
-        if is_locale_code(last) {
-            return Some(last.to_string());
-        }
+    #[test]
+    fn test_resolve_target_files_by_key_prefix() {
+        let root = write_locale_files(
+            "target-prefix",
+            &[
+                ("locales/en/common.json", r#"{"common": {"save": "Save"}}"#),
+                (
+                    "locales/en/auth.json",
+                    r#"{"auth": {"login": {"title": "Login"}}}"#,
+                ),
+                (
+                    "locales/en/errors.json",
+                    r#"{"errors": {"404": "Not found"}}"#,
+                ),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["locales".to_string()]);
+        let files = store.get_locale_file_paths("en");
 
-        // Try last two parts as locale (e.g., "app_en_US" -> "en_US")
-        if parts.len() >= 3 {
-            let locale = format!("{}_{}", parts[parts.len() - 2], parts[parts.len() - 1]);
-            if is_locale_code(&locale) {
-                return Some(locale);
-            }
-        }
+        let result = store.resolve_target_files("en", "auth.login.button", None, &files);
+        assert_eq!(result, vec![root.join("locales/en/auth.json")]);
+
+        let result = store.resolve_target_files("en", "errors.500", None, &files);
+        assert_eq!(result, vec![root.join("locales/en/errors.json")]);
+    }
+
+    #[test]
+    fn test_resolve_target_files_by_file_stem_and_namespace() {
+        let root = write_locale_files(
+            "target-stem",
+            &[
+                ("locales/en/common.json", r#"{"save": "Save"}"#),
+                ("locales/en/auth.json", r#"{"title": "Login"}"#),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["locales".to_string()]);
+        let files = store.get_locale_file_paths("en");
+
+        let result = store.resolve_target_files("en", "auth.subtitle", None, &files);
+        assert_eq!(result, vec![root.join("locales/en/auth.json")]);
+
+        let result = store.resolve_target_files("en", "subtitle", Some("common"), &files);
+        assert_eq!(result, vec![root.join("locales/en/common.json")]);
+    }
+
+    #[test]
+    fn test_resolve_target_files_returns_all_when_ambiguous() {
+        let root = write_locale_files(
+            "target-ambiguous",
+            &[
+                ("locales/en/common.json", r#"{"save": "Save"}"#),
+                ("locales/en/auth.json", r#"{"title": "Login"}"#),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["locales".to_string()]);
+        let files = store.get_locale_file_paths("en");
+
+        let result = store.resolve_target_files("en", "dashboard.title", None, &files);
+        assert_eq!(
+            result,
+            vec![
+                root.join("locales/en/auth.json"),
+                root.join("locales/en/common.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rails_locale_rooted_yaml() {
+        let root = write_locale_files(
+            "rails",
+            &[
+                (
+                    "config/locales/devise.en.yml",
+                    "en:\n  devise:\n    failure:\n      locked: Locked\n",
+                ),
+                (
+                    "config/locales/users.yml",
+                    "en:\n  users:\n    title: Users\nfr:\n  users:\n    title: Utilisateurs\n",
+                ),
+                ("config/locales/plain.en.yml", "greeting: Hello\n"),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["config/locales".to_string()]);
+
+        assert_eq!(
+            store.get_translation("devise.failure.locked", "en"),
+            Some("Locked".to_string())
+        );
+        assert_eq!(
+            store.get_translation("users.title", "fr"),
+            Some("Utilisateurs".to_string())
+        );
+        assert_eq!(
+            store.get_translation("greeting", "en"),
+            Some("Hello".to_string())
+        );
+        assert!(!store.key_exists("en.users.title"));
+    }
+
+    #[test]
+    fn test_translator_notes_on_entries() {
+        let root = write_locale_files(
+            "notes",
+            &[
+                (
+                    "config/locales/users.yml",
+                    "en:\n  users:\n    # Page heading\n    title: Users\nfr:\n  users:\n    title: Utilisateurs\n",
+                ),
+                (
+                    "locales/fr/messages.po",
+                    "#. Checkout button\nmsgctxt \"button\"\nmsgid \"submit\"\nmsgstr \"Commander\"\n",
+                ),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["config/locales".to_string(), "locales".to_string()]);
+
+        let users = store.get_all_translations("users.title");
+        assert_eq!(users["en"].description.as_deref(), Some("Page heading"));
+        assert_eq!(users["fr"].description, None);
+
+        let submit = &store.get_all_translations("button\u{4}submit")["fr"];
+        assert_eq!(submit.description.as_deref(), Some("Checkout button"));
+        assert_eq!(submit.context.as_deref(), Some("button"));
+    }
+
+    #[test]
+    fn test_po_key_lines() {
+        let root = write_locale_files(
+            "po-lines",
+            &[(
+                "locales/fr.po",
+                "msgctxt \"verb\"\nmsgid \"Open\"\nmsgstr \"Ouvrir\"\n\nmsgid \"Open\"\nmsgstr \"Ouvrez\"\n",
+            )],
+        );
+        let path = root.join("locales/fr.po");
+        let line = |key: &str| TranslationStore::find_key_line_in_file(&path, key);
+        assert_eq!(line("verb\u{4}Open"), Some(1));
+        assert_eq!(line("Open"), Some(4));
+        assert_eq!(line("noun\u{4}Open"), None);
+    }
+
+    #[test]
+    fn test_component_and_registered_messages() {
+        let root = write_locale_files(
+            "inline",
+            &[
+                (
+                    "locales/en.json",
+                    r#"{"hello": "Hello", "shared": "Shared"}"#,
+                ),
+                (
+                    "locales/setup.js",
+                    "addMessages('fr', { hello: 'Bonjour' });",
+                ),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["locales".to_string()]);
+        assert_eq!(
+            store.get_translation("hello", "fr"),
+            Some("Bonjour".to_string())
+        );
+
+        let component = root.join("src/Greeting.vue");
+        let content =
+            "<template></template>\n<i18n>\n{ \"en\": { \"hello\": \"Hi there\" } }\n</i18n>\n";
+        let source = SourceLanguage::new(Some("vue".to_string()), Some(component.clone()));
+        store.index_document(&component, content, &source);
+
+        let translations = store.get_all_translations_in("hello", Some(&component));
+        assert_eq!(translations["en"].value, "Hi there");
+        assert_eq!(translations["fr"].value, "Bonjour");
+        let location = TranslationStore::location_of("hello", &translations["en"]);
+        assert_eq!((location.file_path, location.line), (component.clone(), 2));
+
+        assert_eq!(
+            store.get_translation("hello", "en"),
+            Some("Hello".to_string())
+        );
+        assert_eq!(store.component_keys(&component), vec!["hello"]);
+
+        store.index_document(&component, "<template></template>", &source);
+        assert!(store.component_translations(&component, "hello").is_empty());
+
+        let setup = root.join("src/i18n.js");
+        let source = SourceLanguage::new(Some("javascript".to_string()), Some(setup.clone()));
+        store.index_document(&setup, "addMessages('de', { bye: 'Tschüss' });", &source);
+        assert_eq!(
+            store.get_translation("bye", "de"),
+            Some("Tschüss".to_string())
+        );
+
+        // Deleting the call drops its messages, and the locale only it registered
+        store.index_document(&setup, "export {};", &source);
+        assert_eq!(store.get_translation("bye", "de"), None);
+        assert!(!store.get_locales().contains(&"de".to_string()));
+        assert_eq!(
+            store.get_translation("hello", "fr"),
+            Some("Bonjour".to_string())
+        );
+    }
+
+    #[test]
+    fn test_locale_modules_and_default_messages() {
+        let root = write_locale_files(
+            "modules",
+            &[
+                ("src/i18n/common.ts", "export default { ok: 'OK' };"),
+                (
+                    "src/i18n/en.ts",
+                    "import common from './common';\nexport default { ...common, home: { title: 'Home' } } as const;",
+                ),
+                ("src/i18n/fr.js", "module.exports = { home: { title: 'Accueil' } };"),
+                (
+                    "src/i18n/messages.ts",
+                    "export default defineMessages({ save: { id: 'actions.save', defaultMessage: 'Speichern' } });",
+                ),
+            ],
+        );
+        let store = TranslationStore::new(root.clone()).with_source_locale("de");
+        store.scan_and_load(&["src/i18n".to_string()]);
+
+        assert_eq!(
+            store.get_translation("home.title", "en"),
+            Some("Home".to_string())
+        );
+        assert_eq!(
+            store.get_translation("home.title", "fr"),
+            Some("Accueil".to_string())
+        );
+        assert_eq!(
+            store.get_translation("actions.save", "de"),
+            Some("Speichern".to_string())
+        );
+
+        let ok = &store.get_all_translations("ok")["en"];
+        let location = TranslationStore::location_of("ok", ok);
+        assert!(location.file_path.ends_with("common.ts"));
+        assert_eq!(location.line, 0);
     }
 
-    None
+    #[test]
+    fn test_laravel_json_vendor_and_nested_groups() {
+        let root = write_locale_files(
+            "laravel",
+            &[
+                (
+                    "lang/en.json",
+                    r#"{"Welcome to our app.": "Welcome to our app."}"#,
+                ),
+                ("lang/en/auth.php", "<?php return ['failed' => 'Failed'];"),
+                (
+                    "lang/en/admin/users.php",
+                    "<?php return ['title' => 'Users'];",
+                ),
+                (
+                    "lang/vendor/courier/fr/messages.php",
+                    "<?php return ['sent' => 'Envoyé'];",
+                ),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["lang".to_string()]);
+
+        assert_eq!(
+            store.get_translation("Welcome to our app.", "en"),
+            Some("Welcome to our app.".to_string())
+        );
+        assert_eq!(
+            store.get_translation("auth.failed", "en"),
+            Some("Failed".to_string())
+        );
+        assert_eq!(
+            store.get_translation("admin/users.title", "en"),
+            Some("Users".to_string())
+        );
+        assert_eq!(
+            store.get_translation("courier::messages.sent", "fr"),
+            Some("Envoyé".to_string())
+        );
+
+        let entry = &store.get_all_translations("Welcome to our app.")["en"];
+        assert_eq!(
+            TranslationStore::location_of("Welcome to our app.", entry).line,
+            0
+        );
+    }
+
+    #[test]
+    fn test_arb_locale_and_metadata() {
+        let root = write_locale_files(
+            "arb",
+            &[
+                (
+                    "lib/l10n/app_en.arb",
+                    r#"{"title": "Home", "@title": {"description": "Page title"}}"#,
+                ),
+                (
+                    "lib/l10n/intl_messages.arb",
+                    r#"{"@@locale": "pt_BR", "title": "Início", "@title": {"description": "Título"}}"#,
+                ),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["lib/l10n".to_string()]);
+
+        assert_eq!(
+            store.get_translation("title", "pt_BR"),
+            Some("Início".to_string())
+        );
+        assert_eq!(
+            store.arb_metadata("title").and_then(|m| m.description),
+            Some("Page title".to_string())
+        );
+    }
+
+    #[test]
+    fn test_missing_locales_with_fallbacks() {
+        let root = write_locale_files(
+            "fallback",
+            &[
+                ("locales/en.json", r#"{"hello": "Hello", "bye": "Bye"}"#),
+                ("locales/pt.json", r#"{"hello": "Olá"}"#),
+                ("locales/pt-BR.json", r#"{}"#),
+            ],
+        );
+        let config: crate::config::I18nConfig =
+            serde_json::from_str(r#"{"fallbackLocale": "en"}"#).unwrap();
+        let store =
+            TranslationStore::new(root.clone()).with_fallback(LocaleFallback::from_config(&config));
+        store.scan_and_load(&["locales".to_string()]);
+
+        assert!(store.get_missing_locales("hello").is_empty());
+        let mut missing = store.get_missing_locales("bye");
+        missing.sort();
+        assert_eq!(missing, vec!["pt", "pt-BR"]);
+
+        let translations = store.get_all_translations("hello");
+        assert_eq!(store.resolve_locale("pt-BR", &translations), Some("pt"));
+        let translations = store.get_all_translations("bye");
+        assert_eq!(store.resolve_locale("pt-BR", &translations), Some("en"));
+    }
+
+    #[test]
+    fn test_canonical_locales_and_path_matcher() {
+        let root = write_locale_files(
+            "path-matcher",
+            &[
+                ("locales/pt_BR/common.json", r#"{"save": "Salvar"}"#),
+                ("locales/pt-BR.json", r#"{"cancel": "Cancelar"}"#),
+                ("locales/sr-Latn-RS.json", r#"{"save": "Sačuvaj"}"#),
+                ("i18n/auth/es-419.json", r#"{"login": "Ingresar"}"#),
+                ("i18n/common/es-419.json", r#"{"save": "Guardar"}"#),
+                ("i18n/schema.json", r#"{"title": "Schema"}"#),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        store.scan_and_load(&["locales".to_string()]);
+
+        let mut locales = store.get_locales();
+        locales.sort();
+        assert_eq!(locales, vec!["pt-BR", "sr-Latn-RS"]);
+        assert_eq!(
+            store.get_translation("cancel", "pt_BR"),
+            Some("Cancelar".to_string())
+        );
+
+        let store = TranslationStore::new(root.clone())
+            .with_path_matcher(PathMatcher::new("{namespace}/{locale}.json"));
+        store.scan_and_load(&["i18n".to_string()]);
+
+        assert_eq!(store.get_locales(), vec!["es-419"]);
+        assert!(!store.key_exists("title"));
+        let files = store.get_locale_file_paths("es-419");
+        assert_eq!(
+            store.resolve_target_files("es-419", "logout", Some("auth"), &files),
+            vec![root.join("i18n/auth/es-419.json")]
+        );
+    }
+
+    #[test]
+    fn test_scan_options_and_skipped_files() {
+        let root = write_locale_files(
+            "scan-options",
+            &[
+                (".gitignore", "locales/generated/\n"),
+                ("locales/en.json", r#"{"save": "Save"}"#),
+                ("locales/package.json", r#"{"name": "locales"}"#),
+                ("locales/messages.json", r#"{"save": "Save"}"#),
+                ("locales/generated/fr.json", r#"{"save": "Enregistrer"}"#),
+                ("locales/a/b/c/de.json", r#"{"save": "Speichern"}"#),
+            ],
+        );
+        let store = TranslationStore::new(root.clone());
+        let skipped = store.scan_and_load(&["locales".to_string()]);
+
+        let mut locales = store.get_locales();
+        locales.sort();
+        assert_eq!(locales, vec!["de", "en"]);
+        assert_eq!(skipped, vec![root.join("locales/messages.json")]);
+
+        let config: crate::config::I18nConfig = serde_json::from_str(
+            r#"{"scanDepth": 2, "respectGitignore": false, "includeFiles": ["locales/**/*.json"]}"#,
+        )
+        .unwrap();
+        let store = TranslationStore::new(root.clone())
+            .with_scan_options(ScanOptions::from_config(&config));
+        store.scan_and_load(&["locales".to_string()]);
+
+        let mut locales = store.get_locales();
+        locales.sort();
+        assert_eq!(locales, vec!["en", "fr"]);
+    }
+
+    #[test]
+    fn test_keys_matching_dynamic_pattern() {
+        let root = write_locale_files(
+            "keys-matching",
+            &[(
+                "locales/en.json",
+                r#"{"errors": {"404": "Not found", "500": "Server error"}, "status": {"ok": "OK"}}"#,
+            )],
+        );
+        let store = TranslationStore::new(root.to_path_buf());
+        store.scan_and_load(&["locales".to_string()]);
+
+        let pattern = KeyPattern::from_template("errors.${code}").unwrap();
+        assert_eq!(
+            store.keys_matching(&pattern),
+            vec!["errors.404", "errors.500"]
+        );
+
+        let pattern = KeyPattern::from_template("missing.${code}").unwrap();
+        assert!(store.keys_matching(&pattern).is_empty());
+    }
 }
@@ -1,13 +1,24 @@
 mod backend;
 mod config;
+mod config_check;
+mod diagnostics;
 mod document;
 mod i18n;
+mod project;
+mod pseudo;
+#[cfg(test)]
+mod test_support;
+mod translator;
+
+use std::path::PathBuf;
 
 use anyhow::Result;
 use tower_lsp::{LspService, Server};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 use crate::backend::I18nBackend;
+use crate::project::Project;
+use crate::pseudo::PseudoStyle;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -16,6 +27,11 @@ async fn main() -> Result<()> {
         .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
         .init();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("pseudo") {
+        return generate_pseudo_locales(&args[1..]);
+    }
+
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
@@ -24,3 +40,33 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// `intl-lens pseudo [--style accented|bidi] [workspace]`: write pseudo locales next to the
+/// source locale files and exit.
+fn generate_pseudo_locales(args: &[String]) -> Result<()> {
+    let mut styles = PseudoStyle::all().to_vec();
+    let mut root = std::env::current_dir()?;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--style" {
+            let name = args.next().map(String::as_str).unwrap_or_default();
+            let style = PseudoStyle::from_name(name)
+                .ok_or_else(|| anyhow::anyhow!("Unknown pseudo locale style '{}'", name))?;
+            styles = vec![style];
+        } else {
+            root = PathBuf::from(arg);
+        }
+    }
+
+    // The same store the server builds, so the same files count as translations
+    let (project, _) = Project::load(root, &serde_json::Value::Null);
+
+    for style in styles {
+        for path in pseudo::generate(&project.store, &project.config.source_locale, style)? {
+            println!("{}", path.display());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::sync::RwLock;
+use walkdir::WalkDir;
+
+use crate::config::{I18nConfig, CONFIG_FILES};
+use crate::i18n::{KeyFinder, LocaleFallback, PathMatcher, ScanOptions, TranslationStore};
+use crate::translator::ReviewLog;
+
+/// How deep below a workspace folder nested projects are looked for; `apps/web` and
+/// `packages/ui` are two levels down.
+const PROJECT_SEARCH_DEPTH: usize = 4;
+
+/// Directories that hold dependencies or build output rather than projects.
+const SKIPPED_DIRS: [&str; 6] = ["build", "dist", "node_modules", "out", "target", "vendor"];
+
+/// A workspace folder, or a package of a monorepo with translations of its own: its
+/// config, the keys it looks for and its translations.
+pub struct Project {
+    pub root: PathBuf,
+    pub config: I18nConfig,
+    pub key_finder: KeyFinder,
+    pub store: TranslationStore,
+    pub review_log: RwLock<ReviewLog>,
+}
+
+impl Project {
+    /// Load the project at `root` with the config found there and the editor `settings`.
+    /// Also returns the translation files skipped because no locale could be read from
+    /// them.
+    pub fn load(root: PathBuf, settings: &Value) -> (Self, Vec<PathBuf>) {
+        let config = I18nConfig::load(&root, settings);
+        Self::with_config(root, config)
+    }
+
+    pub fn with_config(root: PathBuf, config: I18nConfig) -> (Self, Vec<PathBuf>) {
+        let key_finder = KeyFinder::from_config(&config, &root);
+        let store = TranslationStore::new(root.clone())
+            .with_source_locale(&config.source_locale)
+            .with_fallback(LocaleFallback::from_config(&config))
+            .with_path_matcher(config.path_matcher.as_deref().and_then(PathMatcher::new))
+            .with_scan_options(ScanOptions::from_config(&config));
+        let skipped = store.scan_and_load(&config.locale_paths);
+        let review_log = RwLock::new(ReviewLog::load(&root));
+
+        let project = Self {
+            root,
+            config,
+            key_finder,
+            store,
+            review_log,
+        };
+        (project, skipped)
+    }
+}
+
+/// Roots of the projects in a workspace folder: the folder itself, then every directory
+/// below it that `I18nConfig::is_project_root` recognises.
+pub fn discover_roots(folder: &Path) -> Vec<PathBuf> {
+    let mut roots = vec![folder.to_path_buf()];
+
+    let dirs = WalkDir::new(folder)
+        .min_depth(1)
+        .max_depth(PROJECT_SEARCH_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.file_type().is_dir()
+                && !name.starts_with('.')
+                && !SKIPPED_DIRS.contains(&name.as_ref())
+        });
+    for entry in dirs.filter_map(|e| e.ok()) {
+        if I18nConfig::is_project_root(entry.path()) {
+            roots.push(entry.into_path());
+        }
+    }
+
+    roots
+}
+
+/// Files whose creation can make their directory a project root that
+/// `I18nConfig::is_project_root` recognises, relative to that directory.
+pub fn root_file_names() -> impl Iterator<Item = &'static str> {
+    CONFIG_FILES
+        .into_iter()
+        .chain(["composer.json", "package.json", "pubspec.yaml"])
+}
+
+/// The project root that the new or changed file at `path` makes `discover_roots` find
+/// below `folder`, for packages added to a monorepo after it was opened.
+pub fn discovered_root(folder: &Path, path: &Path) -> Option<PathBuf> {
+    let dir = root_file_names().find_map(|name| {
+        let name = Path::new(name);
+        let depth = name.components().count();
+        path.ends_with(name)
+            .then(|| path.ancestors().nth(depth))
+            .flatten()
+    })?;
+    let relative = dir.strip_prefix(folder).ok()?;
+    let depth = relative.components().count();
+    let searched = relative.components().all(|component| {
+        let name = component.as_os_str().to_string_lossy();
+        !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref())
+    });
+
+    let searched = searched && (1..=PROJECT_SEARCH_DEPTH).contains(&depth);
+    (searched && I18nConfig::is_project_root(dir)).then(|| dir.to_path_buf())
+}
+
+/// The project `path` belongs to: the one with the nearest root above it.
+pub fn nearest<'a>(projects: &'a [Arc<Project>], path: &Path) -> Option<&'a Arc<Project>> {
+    projects
+        .iter()
+        .filter(|project| path.starts_with(&project.root))
+        .max_by_key(|project| project.root.components().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_discovers_nested_projects() {
+        let root = TempDir::with_files(
+            "project-discovery",
+            &[
+                (
+                    "apps/web/package.json",
+                    r#"{"dependencies": {"react-i18next": "^14"}}"#,
+                ),
+                ("apps/admin/.zed/i18n.json", r#"{"localePaths": ["lang"]}"#),
+                (
+                    "apps/docs/package.json",
+                    r#"{"dependencies": {"react": "^18"}}"#,
+                ),
+                ("packages/mobile/pubspec.yaml", "name: mobile\n"),
+                (
+                    "node_modules/i18next/package.json",
+                    r#"{"dependencies": {"i18next": "*"}}"#,
+                ),
+            ],
+        );
+
+        let mut roots = discover_roots(&root);
+        roots.sort();
+        assert_eq!(
+            roots,
+            vec![
+                root.clone(),
+                root.join("apps/admin"),
+                root.join("apps/web"),
+                root.join("packages/mobile"),
+            ]
+        );
+
+        let projects: Vec<Arc<Project>> = roots
+            .into_iter()
+            .map(|root| Arc::new(Project::load(root, &Value::Null).0))
+            .collect();
+        let owner = |path: &str| nearest(&projects, &root.join(path)).map(|p| p.root.clone());
+        assert_eq!(owner("apps/web/src/App.tsx"), Some(root.join("apps/web")));
+        assert_eq!(owner("apps/docs/index.ts"), Some(root.clone()));
+        assert!(nearest(&projects, &std::env::temp_dir().join("other/a.ts")).is_none());
+        assert_eq!(projects[1].config.locale_paths, vec!["lang"]);
+
+        // A package added later is found from its new manifest
+        std::fs::create_dir_all(root.join("apps/shop")).unwrap();
+        let manifest = root.join("apps/shop/pubspec.yaml");
+        std::fs::write(&manifest, "name: shop\n").unwrap();
+        assert_eq!(
+            discovered_root(&root, &manifest),
+            Some(root.join("apps/shop"))
+        );
+        let config = root.join("apps/admin/.zed/i18n.json");
+        assert_eq!(
+            discovered_root(&root, &config),
+            Some(root.join("apps/admin"))
+        );
+        assert_eq!(
+            discovered_root(&root, &root.join("apps/docs/package.json")),
+            None
+        );
+        assert_eq!(discovered_root(&root, &root.join("package.json")), None);
+        let dependency = root.join("node_modules/i18next/package.json");
+        assert_eq!(discovered_root(&root, &dependency), None);
+    }
+}
@@ -0,0 +1,689 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use serde_yaml::Value as YamlValue;
+
+use crate::i18n::{canonicalize, is_locale_code, TranslationParser, TranslationStore};
+
+const RLM: char = '\u{200F}';
+const RLO: char = '\u{202E}';
+const PDF: char = '\u{202C}';
+
+/// Flavour of pseudo locale to generate from the source locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PseudoStyle {
+    /// `en-XA`: accented letters, ~35% longer, wrapped in brackets to expose
+    /// truncation and string concatenation.
+    Accented,
+    /// `ar-XB`: every word forced right-to-left to expose mirroring bugs.
+    Bidi,
+}
+
+impl PseudoStyle {
+    pub fn all() -> [PseudoStyle; 2] {
+        [PseudoStyle::Accented, PseudoStyle::Bidi]
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "accented" | "en-xa" => Some(PseudoStyle::Accented),
+            "bidi" | "rtl" | "ar-xb" => Some(PseudoStyle::Bidi),
+            _ => None,
+        }
+    }
+
+    pub fn is_pseudo_locale(locale: &str) -> bool {
+        let normalized = locale.replace('_', "-");
+        Self::all()
+            .iter()
+            .any(|style| style.locale().eq_ignore_ascii_case(&normalized))
+    }
+
+    pub fn locale(&self) -> &'static str {
+        match self {
+            PseudoStyle::Accented => "en-XA",
+            PseudoStyle::Bidi => "ar-XB",
+        }
+    }
+
+    fn transform_run(&self, run: &str, out: &mut String) {
+        match self {
+            PseudoStyle::Accented => out.extend(run.chars().map(accent)),
+            PseudoStyle::Bidi => {
+                let mut word = String::new();
+                for ch in run.chars() {
+                    if ch.is_alphanumeric() {
+                        word.push(ch);
+                        continue;
+                    }
+                    flush_rtl_word(&mut word, out);
+                    out.push(ch);
+                }
+                flush_rtl_word(&mut word, out);
+            }
+        }
+    }
+}
+
+fn flush_rtl_word(word: &mut String, out: &mut String) {
+    if word.is_empty() {
+        return;
+    }
+    out.push(RLM);
+    out.push(RLO);
+    out.push_str(word);
+    out.push(PDF);
+    out.push(RLM);
+    word.clear();
+}
+
+fn accent(ch: char) -> char {
+    const LOWER: [char; 26] = [
+        'å', 'ƀ', 'ç', 'ð', 'é', 'ƒ', 'ĝ', 'ĥ', 'î', 'ĵ', 'ķ', 'ļ', 'ɱ', 'ñ', 'ö', 'þ', 'ǫ', 'ŕ',
+        'š', 'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+    ];
+    const UPPER: [char; 26] = [
+        'Å', 'Ɓ', 'Ç', 'Ð', 'É', 'Ƒ', 'Ĝ', 'Ĥ', 'Î', 'Ĵ', 'Ķ', 'Ļ', 'Ṁ', 'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ',
+        'Š', 'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+    ];
+
+    match ch {
+        'a'..='z' => LOWER[(ch as u8 - b'a') as usize],
+        'A'..='Z' => UPPER[(ch as u8 - b'A') as usize],
+        _ => ch,
+    }
+}
+
+/// Placeholders that are not brace-delimited: printf directives, Laravel `:name`,
+/// template `${expr}` and vue-i18n linked `@:key` messages.
+fn inline_placeholder_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"^(?:%(?:\d+\$)?[sdif@]|:[A-Za-z_]\w*|\$\{[^}]*\}|@:[\w.]+)").unwrap()
+    })
+}
+
+/// Pseudo-localize a message, leaving placeholders, ICU argument syntax and markup intact.
+pub fn pseudo_localize(text: &str, style: PseudoStyle) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let mut walker = MessageWalker {
+        text,
+        pos: 0,
+        style,
+        letters: 0,
+    };
+    let mut body = String::new();
+    walker.message(&mut body, false, false);
+
+    match style {
+        PseudoStyle::Accented => {
+            let padding = "~".repeat(((walker.letters + 2) / 3).max(1));
+            format!("[{} {}]", body, padding)
+        }
+        PseudoStyle::Bidi => body,
+    }
+}
+
+struct MessageWalker<'a> {
+    text: &'a str,
+    pos: usize,
+    style: PseudoStyle,
+    letters: usize,
+}
+
+impl MessageWalker<'_> {
+    fn rest(&self) -> &str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn copy_char(&mut self, out: &mut String) {
+        if let Some(ch) = self.peek() {
+            out.push(ch);
+            self.pos += ch.len_utf8();
+        }
+    }
+
+    fn copy_until_inclusive(&mut self, delimiter: &str, out: &mut String) {
+        let end = match self.rest().find(delimiter) {
+            Some(index) => self.pos + index + delimiter.len(),
+            None => self.text.len(),
+        };
+        out.push_str(&self.text[self.pos..end]);
+        self.pos = end;
+    }
+
+    /// Walk message text until the end of input, or until an unmatched `}` when nested
+    /// inside an ICU branch (the caller consumes it). `#` is kept verbatim in plural branches.
+    fn message(&mut self, out: &mut String, nested: bool, plural: bool) {
+        let mut run = String::new();
+
+        while let Some(ch) = self.peek() {
+            let protected_start = match ch {
+                '}' if nested => break,
+                '{' => true,
+                '<' => self.rest()[1..]
+                    .chars()
+                    .next()
+                    .is_some_and(|next| next.is_ascii_alphabetic() || next == '/'),
+                '#' => plural,
+                '%' | ':' | '$' | '@' => inline_placeholder_regex().is_match(self.rest()),
+                _ => false,
+            };
+
+            if !protected_start {
+                if ch.is_alphabetic() {
+                    self.letters += 1;
+                }
+                run.push(ch);
+                self.pos += ch.len_utf8();
+                continue;
+            }
+
+            self.style.transform_run(&run, out);
+            run.clear();
+
+            match ch {
+                '{' if self.rest().starts_with("{{") => self.copy_until_inclusive("}}", out),
+                '{' => self.argument(out),
+                '<' => self.copy_until_inclusive(">", out),
+                '#' => self.copy_char(out),
+                _ => {
+                    let length = inline_placeholder_regex()
+                        .find(self.rest())
+                        .map(|m| m.end())
+                        .unwrap_or(1);
+                    out.push_str(&self.text[self.pos..self.pos + length]);
+                    self.pos += length;
+                }
+            }
+        }
+
+        self.style.transform_run(&run, out);
+    }
+
+    /// Copy an ICU argument. Simple `{name}` and formatted `{n, number}` arguments are kept
+    /// verbatim; for plural/select only the branch messages are pseudo-localized.
+    fn argument(&mut self, out: &mut String) {
+        let start = self.pos;
+        let header_end = self.rest().find([',', '}']).map(|i| self.pos + i);
+        let Some(header_end) = header_end else {
+            out.push_str(self.rest());
+            self.pos = self.text.len();
+            return;
+        };
+
+        if self.text[header_end..].starts_with('}') {
+            out.push_str(&self.text[start..=header_end]);
+            self.pos = header_end + 1;
+            return;
+        }
+
+        let after_name = &self.text[header_end + 1..];
+        let kind_end = after_name.find([',', '}']).unwrap_or(after_name.len());
+        let kind = after_name[..kind_end].trim();
+
+        if !matches!(kind, "plural" | "select" | "selectordinal") {
+            self.copy_balanced(out);
+            return;
+        }
+
+        // Copy `{name, plural,` then alternate between selectors and `{branch}` messages
+        let branches_start = header_end + 1 + kind_end + 1;
+        out.push_str(&self.text[start..branches_start.min(self.text.len())]);
+        self.pos = branches_start.min(self.text.len());
+
+        while let Some(ch) = self.peek() {
+            match ch {
+                '}' => {
+                    self.copy_char(out);
+                    return;
+                }
+                '{' => {
+                    self.copy_char(out);
+                    self.message(out, true, kind != "select");
+                    if self.peek() == Some('}') {
+                        self.copy_char(out);
+                    }
+                }
+                _ => self.copy_char(out),
+            }
+        }
+    }
+
+    fn copy_balanced(&mut self, out: &mut String) {
+        let mut depth = 0usize;
+        while let Some(ch) = self.peek() {
+            self.copy_char(out);
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Generate the pseudo locale next to every source locale file, in the same format.
+/// Returns the written files.
+pub fn generate(
+    store: &TranslationStore,
+    source_locale: &str,
+    style: PseudoStyle,
+) -> Result<Vec<PathBuf>> {
+    let source_files = store.get_locale_file_paths(source_locale);
+    if source_files.is_empty() {
+        bail!("No files found for source locale '{}'", source_locale);
+    }
+
+    let mut written = Vec::new();
+    for source_path in source_files {
+        let Some(target_path) = pseudo_path(&source_path, source_locale, style) else {
+            tracing::warn!(
+                "Cannot derive a pseudo locale path for {:?}, skipping",
+                source_path
+            );
+            continue;
+        };
+
+        let content = std::fs::read_to_string(&source_path)
+            .with_context(|| format!("Failed to read {:?}", source_path))?;
+        let generated = pseudo_file_content(&source_path, &content, source_locale, style)
+            .with_context(|| format!("Failed to pseudo-localize {:?}", source_path))?;
+
+        if let Some(parent) = target_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target_path, generated)
+            .with_context(|| format!("Failed to write {:?}", target_path))?;
+        written.push(target_path);
+    }
+
+    Ok(written)
+}
+
+fn pseudo_file_content(
+    path: &Path,
+    content: &str,
+    source_locale: &str,
+    style: PseudoStyle,
+) -> Result<String> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    match extension {
+        "yaml" | "yml" => {
+            let mut value: YamlValue = serde_yaml::from_str(content)?;
+            rename_locale_root(&mut value, source_locale, style);
+            pseudo_yaml(&mut value, style);
+            Ok(serde_yaml::to_string(&value)?)
+        }
+        "php" => {
+            let translations = TranslationParser::parse_php(content)?;
+            Ok(render_php(&translations, style))
+        }
+        "arb" => {
+            let mut value: JsonValue = serde_json::from_str(content)?;
+            if let JsonValue::Object(map) = &mut value {
+                for (key, entry) in map.iter_mut() {
+                    if key == "@@locale" {
+                        *entry = JsonValue::String(style.locale().replace('-', "_"));
+                    } else if !key.starts_with('@') {
+                        if let JsonValue::String(s) = entry {
+                            *s = pseudo_localize(s, style);
+                        }
+                    }
+                }
+            }
+            Ok(to_json_string(&value, content)?)
+        }
+        _ => {
+            let mut value: JsonValue = serde_json::from_str(content)?;
+            pseudo_json(&mut value, style);
+            Ok(to_json_string(&value, content)?)
+        }
+    }
+}
+
+fn pseudo_json(value: &mut JsonValue, style: PseudoStyle) {
+    match value {
+        JsonValue::String(s) => *s = pseudo_localize(s, style),
+        JsonValue::Object(map) => map.values_mut().for_each(|v| pseudo_json(v, style)),
+        JsonValue::Array(items) => items.iter_mut().for_each(|v| pseudo_json(v, style)),
+        _ => {}
+    }
+}
+
+/// In a YAML file rooted at its locales, as Rails writes them, keep only the source
+/// locale's root and rename it to the pseudo locale. Otherwise the pseudo file would be
+/// loaded as the source locale.
+fn rename_locale_root(value: &mut YamlValue, source_locale: &str, style: PseudoStyle) {
+    let YamlValue::Mapping(map) = value else {
+        return;
+    };
+    let is_rooted = !map.is_empty()
+        && map
+            .keys()
+            .all(|key| key.as_str().is_some_and(is_locale_code));
+    if !is_rooted {
+        return;
+    }
+
+    let source_locale = canonicalize(source_locale);
+    let source = map
+        .iter()
+        .find(|(key, _)| key.as_str().map(canonicalize).as_deref() == Some(source_locale.as_str()))
+        .map(|(key, messages)| {
+            (
+                key.as_str().unwrap_or_default().to_string(),
+                messages.clone(),
+            )
+        });
+    let Some((written, messages)) = source else {
+        return;
+    };
+
+    let pseudo_locale = if written.contains('_') {
+        style.locale().replace('-', "_")
+    } else {
+        style.locale().to_string()
+    };
+    map.clear();
+    map.insert(YamlValue::String(pseudo_locale), messages);
+}
+
+fn pseudo_yaml(value: &mut YamlValue, style: PseudoStyle) {
+    match value {
+        YamlValue::String(s) => *s = pseudo_localize(s, style),
+        YamlValue::Mapping(map) => map.values_mut().for_each(|v| pseudo_yaml(v, style)),
+        YamlValue::Sequence(items) => items.iter_mut().for_each(|v| pseudo_yaml(v, style)),
+        _ => {}
+    }
+}
+
+/// Serialize with the indentation of the source file so generated files diff cleanly.
+fn to_json_string(value: &JsonValue, original: &str) -> Result<String> {
+    let indent = original
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim_start();
+            let leading = &line[..line.len() - trimmed.len()];
+            (!trimmed.is_empty() && !leading.is_empty()).then_some(leading)
+        })
+        .min_by_key(|leading| leading.len())
+        .unwrap_or("  ");
+
+    let mut buffer = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+    serde::Serialize::serialize(value, &mut serializer)?;
+
+    let mut output = String::from_utf8(buffer)?;
+    output.push('\n');
+    Ok(output)
+}
+
+enum PhpNode {
+    Leaf(String),
+    Branch(BTreeMap<String, PhpNode>),
+}
+
+fn render_php(
+    translations: &std::collections::HashMap<String, String>,
+    style: PseudoStyle,
+) -> String {
+    let mut root = BTreeMap::new();
+    for (key, value) in translations {
+        let mut node = &mut root;
+        let parts: Vec<&str> = key.split('.').collect();
+        for (index, part) in parts.iter().enumerate() {
+            if index == parts.len() - 1 {
+                node.insert(
+                    part.to_string(),
+                    PhpNode::Leaf(pseudo_localize(value, style)),
+                );
+                break;
+            }
+            let entry = node
+                .entry(part.to_string())
+                .or_insert_with(|| PhpNode::Branch(BTreeMap::new()));
+            let PhpNode::Branch(children) = entry else {
+                break;
+            };
+            node = children;
+        }
+    }
+
+    let mut output = String::from("<?php\n\nreturn [\n");
+    render_php_entries(&root, 1, &mut output);
+    output.push_str("];\n");
+    output
+}
+
+fn render_php_entries(entries: &BTreeMap<String, PhpNode>, depth: usize, output: &mut String) {
+    let indent = "    ".repeat(depth);
+    for (key, node) in entries {
+        match node {
+            PhpNode::Leaf(value) => output.push_str(&format!(
+                "{}'{}' => '{}',\n",
+                indent,
+                php_escape(key),
+                php_escape(value)
+            )),
+            PhpNode::Branch(children) => {
+                output.push_str(&format!("{}'{}' => [\n", indent, php_escape(key)));
+                render_php_entries(children, depth + 1, output);
+                output.push_str(&format!("{}],\n", indent));
+            }
+        }
+    }
+}
+
+fn php_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Map a source locale file to its pseudo locale sibling: `en.json` → `en-XA.json`,
+/// `en/common.json` → `en-XA/common.json`, `app_en.arb` → `app_en_XA.arb`. The pseudo
+/// locale is written with the separator the source locale is written with.
+fn pseudo_path(path: &Path, source_locale: &str, style: PseudoStyle) -> Option<PathBuf> {
+    let file_stem = path.file_stem()?.to_str()?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let parent = path.parent()?;
+
+    let source_locale = canonicalize(source_locale);
+    let is_source = |name: &str| canonicalize(name) == source_locale;
+    let pseudo_locale = |written: &str| {
+        if written.contains('_') || extension == "arb" {
+            style.locale().replace('-', "_")
+        } else {
+            style.locale().to_string()
+        }
+    };
+
+    if is_source(file_stem) {
+        let name = format!("{}.{}", pseudo_locale(file_stem), extension);
+        return Some(parent.join(name));
+    }
+
+    for (index, _) in file_stem.match_indices('_') {
+        let (prefix, locale) = file_stem.split_at(index + 1);
+        if is_source(locale) {
+            let pseudo_locale = style.locale().replace('-', "_");
+            return Some(parent.join(format!("{}{}.{}", prefix, pseudo_locale, extension)));
+        }
+    }
+
+    let dir = parent.file_name()?.to_str()?;
+    if is_source(dir) {
+        return Some(
+            parent
+                .parent()?
+                .join(pseudo_locale(dir))
+                .join(path.file_name()?),
+        );
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_accented_expands_and_brackets() {
+        let result = pseudo_localize("Save", PseudoStyle::Accented);
+        assert_eq!(result, "[Šåṽé ~~]");
+    }
+
+    #[test]
+    fn test_preserves_placeholders_and_tags() {
+        let result = pseudo_localize(
+            "Hi {name}, {{count}} <b>new</b> :user %s",
+            PseudoStyle::Accented,
+        );
+        assert_eq!(result, "[Ĥî {name}, {{count}} <b>ñéŵ</b> :user %s ~~]");
+    }
+
+    #[test]
+    fn test_preserves_icu_plural_structure() {
+        let result = pseudo_localize(
+            "{count, plural, one {# item} other {# items}}",
+            PseudoStyle::Accented,
+        );
+        assert_eq!(
+            result,
+            "[{count, plural, one {# îţéɱ} other {# îţéɱš}} ~~~]"
+        );
+    }
+
+    #[test]
+    fn test_preserves_icu_number_argument() {
+        let result = pseudo_localize("Total {amount, number, currency}", PseudoStyle::Accented);
+        assert_eq!(result, "[Ţöţåļ {amount, number, currency} ~~]");
+    }
+
+    #[test]
+    fn test_select_branches_keep_hash() {
+        let result = pseudo_localize(
+            "{g, select, male {He #} other {They}}",
+            PseudoStyle::Accented,
+        );
+        assert_eq!(result, "[{g, select, male {Ĥé #} other {Ţĥéý}} ~~]");
+    }
+
+    #[test]
+    fn test_bidi_wraps_words_only() {
+        let result = pseudo_localize("Hi {name}", PseudoStyle::Bidi);
+        assert_eq!(result, "\u{200F}\u{202E}Hi\u{202C}\u{200F} {name}");
+    }
+
+    #[test]
+    fn test_pseudo_path_variants() {
+        assert_eq!(
+            pseudo_path(Path::new("/l/en.json"), "en", PseudoStyle::Accented),
+            Some(PathBuf::from("/l/en-XA.json"))
+        );
+        assert_eq!(
+            pseudo_path(Path::new("/l/en/common.json"), "en", PseudoStyle::Bidi),
+            Some(PathBuf::from("/l/ar-XB/common.json"))
+        );
+        assert_eq!(
+            pseudo_path(Path::new("/l/app_en.arb"), "en", PseudoStyle::Accented),
+            Some(PathBuf::from("/l/app_en_XA.arb"))
+        );
+        assert_eq!(
+            pseudo_path(Path::new("/l/messages.json"), "en", PseudoStyle::Accented),
+            None
+        );
+        assert_eq!(
+            pseudo_path(Path::new("/l/en_US.json"), "en-US", PseudoStyle::Accented),
+            Some(PathBuf::from("/l/en_XA.json"))
+        );
+    }
+
+    #[test]
+    fn test_json_keeps_key_order_and_indent() {
+        let content = "{\n    \"b\": \"Bye\",\n    \"a\": {\n        \"x\": \"Hi\"\n    }\n}\n";
+        let result =
+            pseudo_file_content(Path::new("en.json"), content, "en", PseudoStyle::Accented)
+                .unwrap();
+        assert_eq!(
+            result,
+            "{\n    \"b\": \"[Ɓýé ~]\",\n    \"a\": {\n        \"x\": \"[Ĥî ~]\"\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_arb_updates_locale_and_skips_metadata() {
+        let content =
+            r#"{"@@locale": "en", "hello": "Hello", "@hello": {"description": "Greeting"}}"#;
+        let result = pseudo_file_content(
+            Path::new("app_en.arb"),
+            content,
+            "en",
+            PseudoStyle::Accented,
+        )
+        .unwrap();
+        let value: JsonValue = serde_json::from_str(&result).unwrap();
+        assert_eq!(value["@@locale"], "en_XA");
+        assert_eq!(value["hello"], "[Ĥéļļö ~~]");
+        assert_eq!(value["@hello"]["description"], "Greeting");
+    }
+
+    #[test]
+    fn test_rooted_yaml_is_loaded_as_pseudo_locale() {
+        let root = TempDir::with_files(
+            "pseudo-rooted-yaml",
+            &[("config/locales/en.yml", "en:\n  users:\n    title: Users\n")],
+        );
+        let locale_paths = ["config/locales".to_string()];
+        let store = TranslationStore::new(root.to_path_buf());
+        store.scan_and_load(&locale_paths);
+
+        let written = generate(&store, "en", PseudoStyle::Accented).unwrap();
+        assert_eq!(written, vec![root.join("config/locales/en-XA.yml")]);
+        let content = std::fs::read_to_string(&written[0]).unwrap();
+        assert!(content.starts_with("en-XA:\n"));
+
+        let store = TranslationStore::new(root.to_path_buf());
+        store.scan_and_load(&locale_paths);
+        assert_eq!(
+            store.get_translation("users.title", "en").as_deref(),
+            Some("Users")
+        );
+        assert_eq!(
+            store.get_translation("users.title", "en-XA").as_deref(),
+            Some("[Ûšéŕš ~~]")
+        );
+    }
+
+    #[test]
+    fn test_php_output_round_trips() {
+        let content = "<?php return ['auth' => ['failed' => 'Failed :attempts']];";
+        let result =
+            pseudo_file_content(Path::new("auth.php"), content, "en", PseudoStyle::Accented)
+                .unwrap();
+        let parsed = TranslationParser::parse_php(&result).unwrap();
+        assert_eq!(
+            parsed.get("auth.failed"),
+            Some(&"[Ƒåîļéð :attempts ~~]".to_string())
+        );
+    }
+}
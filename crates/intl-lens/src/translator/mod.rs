@@ -0,0 +1,7 @@
+mod placeholders;
+mod provider;
+mod review;
+
+pub use placeholders::{has_icu_branches, protect};
+pub use provider::TranslationProvider;
+pub use review::ReviewLog;
@@ -0,0 +1,142 @@
+use std::sync::OnceLock;
+
+use anyhow::{bail, Result};
+use regex::Regex;
+
+/// Interpolations, printf directives, Laravel `:name` parameters and markup tags.
+/// Each match is swapped for an opaque `<x id="N"/>` tag before the text leaves the
+/// machine, which every supported provider passes through untouched in XML/HTML mode.
+fn placeholder_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r#"\{\{[^{}]*\}\}|\{[\w.]+\}|\$\{[^}]*\}|%(?:\d+\$)?[sdif@]|:[A-Za-z_]\w*|@:[\w.]+|</?[A-Za-z][^<>]*>"#,
+        )
+        .unwrap()
+    })
+}
+
+fn token_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r#"<x\s+id\s*=\s*"(\d+)"\s*(?:/>|>\s*</x>)"#).unwrap())
+}
+
+fn icu_branch_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\{\s*\w+\s*,\s*(?:plural|select|selectordinal)\s*,").unwrap())
+}
+
+/// Source text with its placeholders replaced by stable tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectedText {
+    pub text: String,
+    placeholders: Vec<String>,
+}
+
+/// ICU plural/select messages carry translatable text inside their syntax; sending them
+/// through a flat text translator mangles the branch keywords, so callers skip them.
+pub fn has_icu_branches(text: &str) -> bool {
+    icu_branch_regex().is_match(text)
+}
+
+pub fn protect(text: &str) -> ProtectedText {
+    let mut placeholders = Vec::new();
+    let protected = placeholder_regex().replace_all(text, |caps: &regex::Captures| {
+        let token = format!("<x id=\"{}\"/>", placeholders.len());
+        placeholders.push(caps[0].to_string());
+        token
+    });
+
+    ProtectedText {
+        text: protected.into_owned(),
+        placeholders,
+    }
+}
+
+impl ProtectedText {
+    /// Put the original placeholders back into a translated string. Fails when the
+    /// provider dropped or invented a token, since the result would break at runtime.
+    pub fn restore(&self, translated: &str) -> Result<String> {
+        let mut seen = vec![false; self.placeholders.len()];
+        let mut unknown = None;
+
+        let restored = token_regex().replace_all(translated, |caps: &regex::Captures| {
+            let index: usize = caps[1].parse().unwrap_or(usize::MAX);
+            match self.placeholders.get(index) {
+                Some(placeholder) => {
+                    seen[index] = true;
+                    placeholder.clone()
+                }
+                None => {
+                    unknown = Some(index);
+                    String::new()
+                }
+            }
+        });
+
+        if let Some(index) = unknown {
+            bail!("Translation contains unknown placeholder token {}", index);
+        }
+
+        if let Some(index) = seen.iter().position(|seen| !seen) {
+            bail!(
+                "Translation lost placeholder '{}'",
+                self.placeholders[index]
+            );
+        }
+
+        Ok(restored.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protect_and_restore_placeholders() {
+        let protected = protect("Hello {name}, you have {{count}} new <b>messages</b>");
+        assert_eq!(
+            protected.text,
+            "Hello <x id=\"0\"/>, you have <x id=\"1\"/> new <x id=\"2\"/>messages<x id=\"3\"/>"
+        );
+
+        let translated =
+            "Xin chào <x id=\"0\"/>, bạn có <x id=\"1\"/> <x id=\"2\"/>tin nhắn<x id=\"3\"/> mới";
+        assert_eq!(
+            protected.restore(translated).unwrap(),
+            "Xin chào {name}, bạn có {{count}} <b>tin nhắn</b> mới"
+        );
+    }
+
+    #[test]
+    fn test_protect_printf_and_laravel_parameters() {
+        let protected = protect("Welcome :name, %s items and %1$d files");
+        assert_eq!(
+            protected.text,
+            "Welcome <x id=\"0\"/>, <x id=\"1\"/> items and <x id=\"2\"/> files"
+        );
+    }
+
+    #[test]
+    fn test_restore_accepts_expanded_tags() {
+        let protected = protect("Hi {name}");
+        let restored = protected.restore("Salut <x id=\"0\"></x>").unwrap();
+        assert_eq!(restored, "Salut {name}");
+    }
+
+    #[test]
+    fn test_restore_fails_on_lost_placeholder() {
+        let protected = protect("Hi {name}");
+        assert!(protected.restore("Salut").is_err());
+        assert!(protected.restore("Salut <x id=\"4\"/>").is_err());
+    }
+
+    #[test]
+    fn test_detects_icu_branches() {
+        assert!(has_icu_branches(
+            "{count, plural, one{# item} other{# items}}"
+        ));
+        assert!(!has_icu_branches("Hello {name}"));
+    }
+}
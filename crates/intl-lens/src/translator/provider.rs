@@ -0,0 +1,387 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+
+use crate::config::{TranslationProviderConfig, TranslationProviderKind};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Client for the machine translation service configured in `translationProvider`.
+pub struct TranslationProvider {
+    config: TranslationProviderConfig,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl TranslationProvider {
+    pub fn new(config: TranslationProviderConfig) -> Result<Self> {
+        let api_key = config.resolve_api_key();
+        if api_key.is_none()
+            && matches!(
+                config.kind,
+                TranslationProviderKind::Deepl | TranslationProviderKind::Google
+            )
+        {
+            bail!("Translation provider {:?} requires an API key", config.kind);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            config,
+            api_key,
+            client,
+        })
+    }
+
+    /// Translate `texts` from `source` to `target`, returning results in the same order.
+    /// Texts are expected to be placeholder-protected (see `placeholders::protect`).
+    pub async fn translate(
+        &self,
+        texts: &[String],
+        source: &str,
+        target: &str,
+    ) -> Result<Vec<String>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (url, body) = self.build_request(texts, source, target);
+        let mut request = self.client.post(&url).json(&body);
+
+        if self.config.kind == TranslationProviderKind::Deepl {
+            if let Some(api_key) = &self.api_key {
+                request = request.header("Authorization", format!("DeepL-Auth-Key {}", api_key));
+            }
+        }
+        // In a header rather than the `key` query parameter, since errors show the URL
+        if self.config.kind == TranslationProviderKind::Google {
+            if let Some(api_key) = &self.api_key {
+                request = request.header("x-goog-api-key", api_key.as_str());
+            }
+        }
+        if self.config.kind == TranslationProviderKind::Http {
+            if let Some(api_key) = &self.api_key {
+                request = request.bearer_auth(api_key);
+            }
+        }
+        for (name, value) in &self.config.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Request to {} failed", url))?;
+        let status = response.status();
+        let payload: Value = response
+            .json()
+            .await
+            .with_context(|| format!("Invalid JSON response from {}", url))?;
+
+        if !status.is_success() {
+            bail!("{} returned {}: {}", url, status, payload);
+        }
+
+        let translations = self.parse_response(&payload)?;
+        if translations.len() != texts.len() {
+            bail!(
+                "Expected {} translations from {}, got {}",
+                texts.len(),
+                url,
+                translations.len()
+            );
+        }
+
+        Ok(translations)
+    }
+
+    fn endpoint(&self) -> String {
+        let default = match self.config.kind {
+            TranslationProviderKind::Deepl => "https://api-free.deepl.com",
+            TranslationProviderKind::Google => "https://translation.googleapis.com",
+            TranslationProviderKind::LibreTranslate => "https://libretranslate.com",
+            TranslationProviderKind::Http => "http://localhost:8080/translate",
+        };
+
+        self.config
+            .endpoint
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    fn build_request(&self, texts: &[String], source: &str, target: &str) -> (String, Value) {
+        let endpoint = self.endpoint();
+
+        match self.config.kind {
+            TranslationProviderKind::Deepl => (
+                format!("{}/v2/translate", endpoint),
+                json!({
+                    "text": texts,
+                    "source_lang": deepl_language(source, true),
+                    "target_lang": deepl_language(target, false),
+                    "tag_handling": "xml",
+                    "ignore_tags": ["x"],
+                }),
+            ),
+            TranslationProviderKind::Google => (
+                format!("{}/language/translate/v2", endpoint),
+                json!({
+                    "q": texts,
+                    "source": bcp47_language(source),
+                    "target": bcp47_language(target),
+                    "format": "html",
+                }),
+            ),
+            TranslationProviderKind::LibreTranslate => {
+                let mut body = json!({
+                    "q": texts,
+                    "source": base_language(source),
+                    "target": base_language(target),
+                    "format": "html",
+                });
+                if let Some(api_key) = &self.api_key {
+                    body["api_key"] = Value::String(api_key.clone());
+                }
+                (format!("{}/translate", endpoint), body)
+            }
+            TranslationProviderKind::Http => (
+                endpoint,
+                json!({
+                    "texts": texts,
+                    "source": source,
+                    "target": target,
+                }),
+            ),
+        }
+    }
+
+    fn parse_response(&self, payload: &Value) -> Result<Vec<String>> {
+        let strings = |values: Option<&Vec<Value>>, field: Option<&str>| -> Result<Vec<String>> {
+            let values = values.ok_or_else(|| anyhow!("Unexpected response: {}", payload))?;
+            values
+                .iter()
+                .map(|value| {
+                    let value = match field {
+                        Some(field) => value.get(field),
+                        None => Some(value),
+                    };
+                    value
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .ok_or_else(|| anyhow!("Unexpected response: {}", payload))
+                })
+                .collect()
+        };
+
+        match self.config.kind {
+            TranslationProviderKind::Deepl => {
+                strings(payload["translations"].as_array(), Some("text"))
+            }
+            TranslationProviderKind::Google => strings(
+                payload["data"]["translations"].as_array(),
+                Some("translatedText"),
+            ),
+            TranslationProviderKind::LibreTranslate => match &payload["translatedText"] {
+                Value::String(text) => Ok(vec![text.clone()]),
+                value => strings(value.as_array(), None),
+            },
+            TranslationProviderKind::Http => strings(payload["translations"].as_array(), None),
+        }
+    }
+}
+
+fn bcp47_language(locale: &str) -> String {
+    locale.replace('_', "-")
+}
+
+fn base_language(locale: &str) -> String {
+    bcp47_language(locale)
+        .split('-')
+        .next()
+        .unwrap_or(locale)
+        .to_ascii_lowercase()
+}
+
+/// DeepL wants upper-case codes, accepts regional variants only for targets
+/// (`EN-GB`, `PT-BR`), and rejects them for the source language.
+fn deepl_language(locale: &str, is_source: bool) -> String {
+    if is_source {
+        base_language(locale).to_ascii_uppercase()
+    } else {
+        bcp47_language(locale).to_ascii_uppercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Serve a single canned JSON response and hand back the raw request it received.
+    async fn mock_server(response: Value) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 4096];
+
+            loop {
+                let read = socket.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+                if read == 0 {
+                    break;
+                }
+            }
+
+            let body = response.to_string();
+            let reply = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(reply.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+
+        (format!("http://{}", address), handle)
+    }
+
+    fn provider(kind: TranslationProviderKind, endpoint: String) -> TranslationProvider {
+        TranslationProvider::new(TranslationProviderConfig {
+            kind,
+            endpoint: Some(endpoint),
+            api_key: Some("secret".to_string()),
+            api_key_env: None,
+            headers: HashMap::from([("X-Project".to_string(), "demo".to_string())]),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_deepl_request_and_response() {
+        let (url, request) = mock_server(json!({"translations": [{"text": "Speichern"}]})).await;
+        let result = provider(TranslationProviderKind::Deepl, url)
+            .translate(&["Save".to_string()], "en", "de")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["Speichern".to_string()]);
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with("POST /v2/translate"));
+        assert!(request.contains("DeepL-Auth-Key secret"));
+        assert!(request.to_ascii_lowercase().contains("x-project: demo"));
+        assert!(request.contains("\"target_lang\":\"DE\""));
+        assert!(request.contains("\"tag_handling\":\"xml\""));
+    }
+
+    #[tokio::test]
+    async fn test_google_request_and_response() {
+        let (url, request) = mock_server(
+            json!({"data": {"translations": [{"translatedText": "Lưu"}, {"translatedText": "Hủy"}]}}),
+        )
+        .await;
+        let result = provider(TranslationProviderKind::Google, url)
+            .translate(&["Save".to_string(), "Cancel".to_string()], "en", "vi")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["Lưu".to_string(), "Hủy".to_string()]);
+
+        let request = request.await.unwrap();
+        let request_line = request.lines().next().unwrap();
+        assert_eq!(request_line, "POST /language/translate/v2 HTTP/1.1");
+        assert!(!request_line.contains("secret"));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("x-goog-api-key: secret"));
+        assert!(request.contains("\"format\":\"html\""));
+    }
+
+    #[tokio::test]
+    async fn test_libretranslate_request_and_response() {
+        let (url, request) = mock_server(json!({"translatedText": ["Enregistrer"]})).await;
+        let result = provider(TranslationProviderKind::LibreTranslate, url)
+            .translate(&["Save".to_string()], "en_US", "fr-CA")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["Enregistrer".to_string()]);
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with("POST /translate"));
+        assert!(request.contains("\"api_key\":\"secret\""));
+        assert!(request.contains("\"source\":\"en\""));
+        assert!(request.contains("\"target\":\"fr\""));
+    }
+
+    #[tokio::test]
+    async fn test_generic_http_request_and_response() {
+        let (url, request) = mock_server(json!({"translations": ["保存"]})).await;
+        let result = provider(TranslationProviderKind::Http, format!("{}/mt", url))
+            .translate(&["Save".to_string()], "en", "ja")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["保存".to_string()]);
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with("POST /mt"));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: bearer secret"));
+        assert!(request.contains("\"texts\":[\"Save\"]"));
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_translation_count_is_an_error() {
+        let (url, _request) = mock_server(json!({"translations": []})).await;
+        let result = provider(TranslationProviderKind::Http, url)
+            .translate(&["Save".to_string()], "en", "ja")
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deepl_requires_api_key() {
+        let result = TranslationProvider::new(TranslationProviderConfig {
+            kind: TranslationProviderKind::Deepl,
+            endpoint: None,
+            api_key: None,
+            api_key_env: None,
+            headers: HashMap::new(),
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_language_code_mapping() {
+        assert_eq!(deepl_language("pt_BR", false), "PT-BR");
+        assert_eq!(deepl_language("en-US", true), "EN");
+        assert_eq!(base_language("zh-TW"), "zh");
+        assert_eq!(bcp47_language("zh_TW"), "zh-TW");
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+const REVIEW_FILE: &str = ".zed/i18n-review.json";
+
+/// Keys whose value was filled in by machine translation and still needs a human look,
+/// persisted per locale next to the project config so the mark survives restarts.
+#[derive(Debug, Default)]
+pub struct ReviewLog {
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ReviewLog {
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(REVIEW_FILE);
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            path: Some(path),
+            entries,
+        }
+    }
+
+    pub fn mark(&mut self, locale: &str, key: &str) {
+        self.entries
+            .entry(locale.to_string())
+            .or_default()
+            .insert(key.to_string());
+    }
+
+    /// Clear the mark for `key` in every locale. Returns whether anything changed.
+    pub fn resolve(&mut self, key: &str) -> bool {
+        let mut changed = false;
+        for keys in self.entries.values_mut() {
+            changed |= keys.remove(key);
+        }
+        self.entries.retain(|_, keys| !keys.is_empty());
+        changed
+    }
+
+    /// Locales in which `key` is still awaiting review.
+    pub fn pending_locales(&self, key: &str) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|(_, keys)| keys.contains(key))
+            .map(|(locale, _)| locale.clone())
+            .collect()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if self.entries.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut content = serde_json::to_string_pretty(&self.entries)?;
+        content.push('\n');
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_mark_save_load_and_resolve() {
+        let root = TempDir::new("review-log");
+
+        let mut log = ReviewLog::load(&root);
+        log.mark("vi", "common.save");
+        log.mark("ja", "common.save");
+        log.save().unwrap();
+
+        let mut log = ReviewLog::load(&root);
+        assert_eq!(
+            log.pending_locales("common.save"),
+            vec!["ja".to_string(), "vi".to_string()]
+        );
+
+        assert!(log.resolve("common.save"));
+        assert!(!log.resolve("common.save"));
+        log.save().unwrap();
+        assert!(!root.join(REVIEW_FILE).exists());
+    }
+}